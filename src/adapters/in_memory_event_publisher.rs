@@ -0,0 +1,202 @@
+//! In-memory implementation of the EventPublisher port
+
+use crate::events::PolicyEvent;
+use crate::ports::event_publisher::{event_to_subject, EventPublisher, PublishError, QueryError};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use cim_domain::{CorrelationId, DomainEvent};
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// An event recorded by the in-memory publisher
+#[derive(Debug, Clone)]
+pub struct PublishedEvent {
+    pub subject: String,
+    pub event: PolicyEvent,
+    pub published_at: DateTime<Utc>,
+}
+
+/// EventPublisher that records events in memory
+///
+/// Events are kept in publish order under the subject the NATS adapter would
+/// have used, so handlers can be tested without a NATS server.
+#[derive(Debug, Default)]
+pub struct InMemoryEventPublisher {
+    published: Mutex<Vec<PublishedEvent>>,
+}
+
+impl InMemoryEventPublisher {
+    /// Create an empty in-memory publisher
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All events published so far, in order
+    pub fn published_events(&self) -> Vec<PolicyEvent> {
+        self.query(|_| true)
+    }
+
+    /// Events published to exactly `subject`, in order
+    pub fn published_to_subject(&self, subject: &str) -> Vec<PolicyEvent> {
+        self.query(|published| published.subject == subject)
+    }
+
+    /// Subjects published to so far, in order
+    pub fn subjects(&self) -> Vec<String> {
+        self.published
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|published| published.subject.clone())
+            .collect()
+    }
+
+    /// Forget everything published so far
+    pub fn clear(&self) {
+        self.published.lock().unwrap().clear();
+    }
+
+    fn query(&self, predicate: impl Fn(&PublishedEvent) -> bool) -> Vec<PolicyEvent> {
+        self.published
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|published| predicate(published))
+            .map(|published| published.event.clone())
+            .collect()
+    }
+}
+
+#[async_trait]
+impl EventPublisher for InMemoryEventPublisher {
+    async fn publish(&self, event: &PolicyEvent) -> Result<(), PublishError> {
+        self.published.lock().unwrap().push(PublishedEvent {
+            subject: event_to_subject(event),
+            event: event.clone(),
+            published_at: Utc::now(),
+        });
+        Ok(())
+    }
+
+    async fn publish_batch(&self, events: &[PolicyEvent]) -> Result<(), PublishError> {
+        for event in events {
+            self.publish(event).await?;
+        }
+        Ok(())
+    }
+
+    async fn query_by_correlation(&self, correlation_id: Uuid) -> Result<Vec<PolicyEvent>, QueryError> {
+        Ok(self.query(|published| {
+            matches!(
+                &published.event.identity().correlation_id,
+                CorrelationId::Single(id) if *id == correlation_id
+            )
+        }))
+    }
+
+    async fn query_by_aggregate(&self, aggregate_id: Uuid) -> Result<Vec<PolicyEvent>, QueryError> {
+        Ok(self.query(|published| published.event.aggregate_id() == aggregate_id))
+    }
+
+    /// Events published between `start` and `end`, inclusive
+    async fn query_by_time_range(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<PolicyEvent>, QueryError> {
+        Ok(self.query(|published| {
+            published.published_at >= start && published.published_at <= end
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::Policy;
+    use crate::commands::{ActivatePolicy, SuspendPolicy};
+    use crate::sagas::create_root_command;
+    use crate::value_objects::PolicyStatus;
+
+    /// Activate-then-suspend, publishing through the port as a handler would
+    async fn activate_and_suspend(
+        policy: &Policy,
+        publisher: &dyn EventPublisher,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let now = Utc::now();
+        let activated = policy.activate(
+            &ActivatePolicy {
+                identity: create_root_command(),
+                policy_id: policy.id,
+                activated_by: "ops".to_string(),
+                effective_immediately: true,
+                schedule_activation: None,
+            },
+            now,
+        )?;
+        publisher.publish(&activated).await?;
+
+        let active = policy.apply_event_pure(&activated)?;
+        let suspended = active.suspend(
+            &SuspendPolicy {
+                identity: activated.identity().clone(),
+                policy_id: policy.id,
+                suspended_by: "ops".to_string(),
+                reason: "incident".to_string(),
+                expected_resume_date: None,
+            },
+            now,
+        )?;
+        publisher.publish(&suspended).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_handler_publishes_to_expected_subjects() {
+        let mut policy = Policy::new("Key Size", "Minimum key size");
+        policy.status = PolicyStatus::Approved;
+        let publisher = InMemoryEventPublisher::new();
+
+        activate_and_suspend(&policy, &publisher).await.unwrap();
+
+        assert_eq!(
+            publisher.subjects(),
+            vec![
+                format!("events.policy.{}.policyactivated", policy.id.0),
+                format!("events.policy.{}.policysuspended", policy.id.0),
+            ]
+        );
+        let activated =
+            publisher.published_to_subject(&format!("events.policy.{}.policyactivated", policy.id.0));
+        assert!(matches!(&activated[..], [PolicyEvent::PolicyActivated(e)] if e.activated_by == "ops"));
+
+        let by_aggregate = publisher.query_by_aggregate(policy.id.0).await.unwrap();
+        assert_eq!(by_aggregate.len(), 2);
+        assert!(publisher.query_by_aggregate(Uuid::now_v7()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_queries_by_correlation_and_time() {
+        let mut policy = Policy::new("Key Size", "Minimum key size");
+        policy.status = PolicyStatus::Approved;
+        let publisher = InMemoryEventPublisher::new();
+        let before = Utc::now();
+
+        activate_and_suspend(&policy, &publisher).await.unwrap();
+
+        let events = publisher.published_events();
+        let correlation = match &events[0].identity().correlation_id {
+            CorrelationId::Single(id) => *id,
+            other => panic!("unexpected correlation {:?}", other),
+        };
+        assert_eq!(publisher.query_by_correlation(correlation).await.unwrap().len(), 2);
+
+        let in_range = publisher.query_by_time_range(before, Utc::now()).await.unwrap();
+        assert_eq!(in_range.len(), 2);
+        let later = Utc::now() + chrono::Duration::hours(1);
+        assert!(publisher.query_by_time_range(later, later).await.unwrap().is_empty());
+
+        publisher.clear();
+        assert!(publisher.published_events().is_empty());
+    }
+}
@@ -1,5 +1,7 @@
 //! Adapters - concrete implementations of ports
 
+pub mod in_memory_event_publisher;
 pub mod nats_event_publisher;
 
+pub use in_memory_event_publisher::{InMemoryEventPublisher, PublishedEvent};
 pub use nats_event_publisher::NatsEventPublisher;
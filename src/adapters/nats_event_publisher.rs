@@ -1,6 +1,7 @@
 //! NATS implementation of the EventPublisher port
 
 use crate::events::PolicyEvent;
+use crate::infrastructure::{decode_stored_event, UpcasterRegistry};
 use crate::ports::event_publisher::{event_to_subject, EventPublisher, PublishError, QueryError};
 use async_nats::jetstream;
 use async_trait::async_trait;
@@ -13,6 +14,7 @@ use uuid::Uuid;
 pub struct NatsEventPublisher {
     jetstream: jetstream::Context,
     stream_name: String,
+    upcasters: UpcasterRegistry,
 }
 
 impl NatsEventPublisher {
@@ -21,8 +23,26 @@ impl NatsEventPublisher {
         Self {
             jetstream,
             stream_name,
+            upcasters: UpcasterRegistry::new(),
         }
     }
+
+    /// Set the upcasters applied to queried events of older schema versions
+    pub fn with_upcasters(mut self, upcasters: UpcasterRegistry) -> Self {
+        self.upcasters = upcasters;
+        self
+    }
+}
+
+/// Decode a queried message the way `NatsEventStore` reads the stream
+fn decode_event(
+    upcasters: &UpcasterRegistry,
+    headers: Option<&async_nats::HeaderMap>,
+    payload: &[u8],
+) -> Result<PolicyEvent, QueryError> {
+    decode_stored_event(upcasters, headers, payload)
+        .map(|envelope| envelope.event)
+        .map_err(|e| QueryError::Deserialization(e.to_string()))
 }
 
 #[async_trait]
@@ -86,9 +106,11 @@ impl EventPublisher for NatsEventPublisher {
         while let Some(message) = messages.next().await {
             match message {
                 Ok(msg) => {
-                    let event: PolicyEvent = serde_json::from_slice(&msg.payload)
-                        .map_err(|e| QueryError::Deserialization(e.to_string()))?;
-                    events.push(event);
+                    events.push(decode_event(
+                        &self.upcasters,
+                        msg.headers.as_ref(),
+                        &msg.payload,
+                    )?);
                     let _ = msg.ack().await;
                 }
                 Err(e) => {
@@ -112,3 +134,45 @@ impl EventPublisher for NatsEventPublisher {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{EventEnvelope, PolicyApproved};
+    use crate::infrastructure::EventCodec;
+    use crate::sagas::create_root_command;
+    use crate::value_objects::PolicyId;
+
+    #[test]
+    fn test_queried_events_decode_like_the_event_store_wrote_them() {
+        let event = PolicyEvent::PolicyApproved(PolicyApproved {
+            event_id: Uuid::now_v7(),
+            identity: create_root_command(),
+            policy_id: PolicyId::new(),
+            approved_by: "approver".to_string(),
+            approved_at: Utc::now(),
+            approval_notes: None,
+        });
+        let envelope = EventEnvelope::from(event.clone());
+
+        for codec in [EventCodec::Json, EventCodec::Cbor, EventCodec::MessagePack] {
+            let mut headers = async_nats::HeaderMap::new();
+            headers.insert("schema-version", envelope.schema_version.to_string().as_str());
+            headers.insert("codec", codec.header_value());
+            let payload = codec.encode(&envelope).unwrap();
+
+            let decoded =
+                decode_event(&UpcasterRegistry::new(), Some(&headers), &payload).unwrap();
+            assert_eq!(decoded.event_id(), event.event_id(), "{:?}", codec);
+            assert_eq!(decoded.aggregate_id(), event.aggregate_id());
+        }
+
+        // Bare events published without headers are still readable
+        let bare = serde_json::to_vec(&event).unwrap();
+        let decoded = decode_event(&UpcasterRegistry::new(), None, &bare).unwrap();
+        assert_eq!(decoded.event_id(), event.event_id());
+
+        let garbage = decode_event(&UpcasterRegistry::new(), None, b"not an event");
+        assert!(matches!(garbage, Err(QueryError::Deserialization(_))));
+    }
+}
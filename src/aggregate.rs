@@ -1,6 +1,6 @@
 //! Policy aggregates - the core domain models
 
-use crate::entities::PolicyRule;
+use crate::entities::{Change, PolicyDiff, PolicyRule};
 use crate::value_objects::*;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -21,6 +21,15 @@ pub struct Policy {
     pub expiry_date: Option<DateTime<Utc>>,
     pub parent_policy_id: Option<PolicyId>,
     pub metadata: PolicyMetadata,
+    #[serde(default)]
+    pub approval_requirements: ApprovalRequirements,
+    #[serde(default)]
+    pub approvals: Vec<ApprovalRecord>,
+    #[serde(default)]
+    pub pending_external_approvals: Vec<PendingExternalApproval>,
+    /// Stream sequence of the last event applied, for idempotent replay
+    #[serde(default)]
+    pub last_sequence: u64,
 }
 
 impl Policy {
@@ -39,6 +48,10 @@ impl Policy {
             expiry_date: None,
             parent_policy_id: None,
             metadata: PolicyMetadata::default(),
+            approval_requirements: ApprovalRequirements::default(),
+            approvals: Vec::new(),
+            pending_external_approvals: Vec::new(),
+            last_sequence: 0,
         }
     }
 
@@ -46,6 +59,7 @@ impl Policy {
     ///
     /// This is the core of event sourcing - deriving aggregate state from events.
     /// Each event transforms the policy into a new state without mutation.
+    /// Use `apply_sequenced` for events that may be delivered more than once.
     pub fn apply_event_pure(&self, event: &crate::events::PolicyEvent) -> Result<Self, crate::PolicyError> {
         use crate::events::PolicyEvent;
 
@@ -66,6 +80,9 @@ impl Policy {
                 new_policy.parent_policy_id = None;
                 new_policy.metadata.created_at = e.created_at;
                 new_policy.metadata.created_by = e.created_by.clone();
+                new_policy.approval_requirements = e.approval_requirements.clone();
+                new_policy.approvals = Vec::new();
+                new_policy.pending_external_approvals = Vec::new();
             }
             PolicyEvent::PolicyUpdated(e) => {
                 new_policy.version = e.version;
@@ -76,6 +93,44 @@ impl Policy {
             PolicyEvent::PolicyApproved(_e) => {
                 new_policy.status = PolicyStatus::Approved;
             }
+            PolicyEvent::PolicyApprovalRecorded(e) => {
+                new_policy.approvals.push(ApprovalRecord {
+                    approver: e.approver.clone(),
+                    role: e.role.clone(),
+                    external: e.external,
+                    approved_at: e.recorded_at,
+                    kind: e.approver_kind,
+                });
+            }
+            PolicyEvent::PolicyApprovalDelegated(e) => {
+                new_policy.approval_requirements.delegations.push(ApprovalDelegation {
+                    from: e.from.clone(),
+                    to: e.to.clone(),
+                    role: e.role.clone(),
+                    valid_until: e.valid_until,
+                });
+            }
+            PolicyEvent::PolicyExternalApprovalRequested(e) => {
+                new_policy.pending_external_approvals.push(PendingExternalApproval {
+                    request_id: e.event_id,
+                    requested_by: e.requested_by.clone(),
+                    requested_at: e.requested_at,
+                    expires_at: e.expires_at,
+                });
+            }
+            PolicyEvent::PolicyExternalApprovalVerified(e) => {
+                new_policy.pending_external_approvals.retain(|p| p.request_id != e.request_id);
+                new_policy.approvals.push(ApprovalRecord {
+                    approver: e.verifier.clone(),
+                    role: Some(e.method.clone()),
+                    external: true,
+                    approved_at: e.verified_at,
+                    kind: None,
+                });
+            }
+            PolicyEvent::PolicyExternalApprovalExpired(e) => {
+                new_policy.pending_external_approvals.retain(|p| p.request_id != e.request_id);
+            }
             PolicyEvent::PolicyActivated(e) => {
                 new_policy.status = PolicyStatus::Active;
                 new_policy.effective_date = Some(e.effective_from);
@@ -91,6 +146,7 @@ impl Policy {
                 new_policy.status = PolicyStatus::Archived;
             }
             // Evaluation events don't modify the policy aggregate itself
+            PolicyEvent::PolicyExpiringSoon(_) |
             PolicyEvent::PolicyEvaluated(_) |
             PolicyEvent::PolicyViolationDetected(_) |
             PolicyEvent::PolicyCompliancePassed(_) => {
@@ -99,7 +155,10 @@ impl Policy {
             // Exemption events don't modify the policy aggregate
             PolicyEvent::PolicyExemptionGranted(_) |
             PolicyEvent::PolicyExemptionRevoked(_) |
-            PolicyEvent::PolicyExemptionExpired(_) => {
+            PolicyEvent::PolicyExemptionExpired(_) |
+            PolicyEvent::PolicyExemptionRenewed(_) |
+            PolicyEvent::PolicyExemptionUsed(_) |
+            PolicyEvent::PolicyExemptionExhausted(_) => {
                 // These modify PolicyExemption aggregate, not Policy
             }
             // PolicySet events don't modify policy aggregate
@@ -109,8 +168,33 @@ impl Policy {
             PolicyEvent::PolicyConflictDetected(_) => {
                 // These modify PolicySet aggregate, not Policy
             }
+            // Template events are persisted by the template repository
+            PolicyEvent::PolicyTemplateSaved(_) |
+            PolicyEvent::PolicyTemplateDeleted(_) |
+            PolicyEvent::PolicyInstantiatedFromTemplate(_) => {
+                // These don't modify Policy
+            }
+        }
+
+        Ok(new_policy)
+    }
+
+    /// Apply an event read from the policy's stream at `sequence`
+    ///
+    /// Stream sequences only grow, so an event at or below `last_sequence`
+    /// has already been applied and re-delivering it is a no-op. This makes
+    /// at-least-once delivery safe.
+    pub fn apply_sequenced(
+        &self,
+        sequence: u64,
+        event: &crate::events::PolicyEvent,
+    ) -> Result<Self, crate::PolicyError> {
+        if sequence <= self.last_sequence {
+            return Ok(self.clone());
         }
 
+        let mut new_policy = self.apply_event_pure(event)?;
+        new_policy.last_sequence = sequence;
         Ok(new_policy)
     }
 
@@ -119,38 +203,283 @@ impl Policy {
         self.rules.push(rule);
     }
 
-    /// Update policy status
-    pub fn update_status(&mut self, status: PolicyStatus) -> Result<(), crate::PolicyError> {
-        // Validate state transitions
-        match (self.status, status) {
-            (PolicyStatus::Draft, PolicyStatus::UnderReview) => {},
-            (PolicyStatus::UnderReview, PolicyStatus::Approved) => {},
-            (PolicyStatus::UnderReview, PolicyStatus::Draft) => {}, // Send back for revision
-            (PolicyStatus::Approved, PolicyStatus::Active) => {},
-            (PolicyStatus::Active, PolicyStatus::Suspended) => {},
-            (PolicyStatus::Suspended, PolicyStatus::Active) => {},
-            (PolicyStatus::Active, PolicyStatus::Revoked) => {},
-            (PolicyStatus::Suspended, PolicyStatus::Revoked) => {},
-            (PolicyStatus::Revoked, PolicyStatus::Archived) => {},
-            _ => {
-                return Err(crate::PolicyError::ValidationError(
-                    format!("Invalid status transition from {:?} to {:?}", self.status, status)
+    /// Record an approval and approve the policy once its requirements are met
+    ///
+    /// Always emits `PolicyApprovalRecorded`. When the internal requirements
+    /// are satisfied, `PolicyApproved` follows, unless an external approval is
+    /// still missing, in which case `PolicyExternalApprovalRequested` is
+    /// emitted unless a request is already pending.
+    pub fn approve(
+        &self,
+        command: &crate::commands::ApprovePolicy,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<crate::events::PolicyEvent>, crate::PolicyError> {
+        use crate::events::*;
+        use crate::sagas::create_caused_by;
+
+        if !self.status.can_transition_to(PolicyStatus::Approved) {
+            return Err(crate::PolicyError::ValidationError(format!(
+                "Cannot approve policy in status {:?}",
+                self.status
+            )));
+        }
+        if self.approvals.iter().any(|a| a.approver == command.approved_by) {
+            return Err(crate::PolicyError::ValidationError(format!(
+                "{} has already approved this policy",
+                command.approved_by
+            )));
+        }
+
+        let recorded = PolicyEvent::PolicyApprovalRecorded(PolicyApprovalRecorded {
+            event_id: Uuid::now_v7(),
+            identity: create_caused_by(&command.identity),
+            policy_id: self.id,
+            approver: command.approved_by.clone(),
+            role: command.approver_role.clone(),
+            external: command.external,
+            recorded_at: now,
+            approver_kind: command.approver_kind,
+        });
+        let recorded_state = self.apply_event_pure(&recorded)?;
+        let requirements = &recorded_state.approval_requirements;
+        let mut events = vec![recorded];
+
+        if !requirements.outstanding(&recorded_state.approvals).is_empty() {
+            return Ok(events);
+        }
+
+        if requirements.needs_external_approval(&recorded_state.approvals) {
+            if recorded_state.pending_external_approvals.is_empty() {
+                events.push(PolicyEvent::PolicyExternalApprovalRequested(
+                    PolicyExternalApprovalRequested {
+                        event_id: Uuid::now_v7(),
+                        identity: create_caused_by(&command.identity),
+                        policy_id: self.id,
+                        requested_by: command.approved_by.clone(),
+                        requested_at: now,
+                        expires_at: now
+                            + chrono::Duration::hours(
+                                requirements.external_approval_timeout_hours as i64,
+                            ),
+                    },
                 ));
             }
+            return Ok(events);
+        }
+
+        events.push(PolicyEvent::PolicyApproved(PolicyApproved {
+            event_id: Uuid::now_v7(),
+            identity: create_caused_by(&command.identity),
+            policy_id: self.id,
+            approved_by: command.approved_by.clone(),
+            approved_at: now,
+            approval_notes: command.approval_notes.clone(),
+        }));
+        Ok(events)
+    }
+
+    /// Activate an approved (or resume a suspended) policy
+    pub fn activate(
+        &self,
+        command: &crate::commands::ActivatePolicy,
+        now: DateTime<Utc>,
+    ) -> Result<crate::events::PolicyEvent, crate::PolicyError> {
+        use crate::events::*;
+
+        self.clone().update_status(PolicyStatus::Active)?;
+
+        let effective_from = match command.schedule_activation {
+            Some(at) if !command.effective_immediately => at,
+            _ => now,
+        };
+
+        Ok(PolicyEvent::PolicyActivated(PolicyActivated {
+            event_id: Uuid::now_v7(),
+            identity: crate::sagas::create_caused_by(&command.identity),
+            policy_id: self.id,
+            activated_by: command.activated_by.clone(),
+            activated_at: now,
+            effective_from,
+            effective_until: self.expiry_date,
+        }))
+    }
+
+    /// Suspend an active policy
+    pub fn suspend(
+        &self,
+        command: &crate::commands::SuspendPolicy,
+        now: DateTime<Utc>,
+    ) -> Result<crate::events::PolicyEvent, crate::PolicyError> {
+        use crate::events::*;
+
+        self.clone().update_status(PolicyStatus::Suspended)?;
+
+        Ok(PolicyEvent::PolicySuspended(PolicySuspended {
+            event_id: Uuid::now_v7(),
+            identity: crate::sagas::create_caused_by(&command.identity),
+            policy_id: self.id,
+            suspended_by: command.suspended_by.clone(),
+            suspended_at: now,
+            reason: command.reason.clone(),
+            expected_resume_date: command.expected_resume_date,
+        }))
+    }
+
+    /// Let another approver stand in for `command.from` until `command.valid_until`
+    ///
+    /// Emits `PolicyApprovalDelegated`; apply it with `apply_event_pure`.
+    /// Approvals the delegate gives inside the window then count as the
+    /// delegator's, both as a required approver and for `command.role`.
+    pub fn delegate_approval(
+        &self,
+        command: &crate::commands::DelegateApproval,
+        now: DateTime<Utc>,
+    ) -> Result<crate::events::PolicyEvent, crate::PolicyError> {
+        use crate::events::{PolicyApprovalDelegated, PolicyEvent};
+        use crate::sagas::create_caused_by;
+
+        if !self.status.can_transition_to(PolicyStatus::Approved) {
+            return Err(crate::PolicyError::ValidationError(format!(
+                "Cannot delegate approval of policy in status {:?}",
+                self.status
+            )));
+        }
+        if command.from == command.to {
+            return Err(crate::PolicyError::ValidationError(format!(
+                "{} cannot delegate approval to themselves",
+                command.from
+            )));
+        }
+        if command.valid_until <= now {
+            return Err(crate::PolicyError::ValidationError(
+                "Delegation must end in the future".to_string(),
+            ));
+        }
+
+        Ok(PolicyEvent::PolicyApprovalDelegated(PolicyApprovalDelegated {
+            event_id: Uuid::now_v7(),
+            identity: create_caused_by(&command.identity),
+            policy_id: self.id,
+            from: command.from.clone(),
+            to: command.to.clone(),
+            role: command.role.clone(),
+            valid_until: command.valid_until,
+            delegated_at: now,
+        }))
+    }
+
+    /// Record an internal approval
+    ///
+    /// Emits `PolicyApprovalRecorded`; apply it with `apply_event_pure`.
+    pub fn record_approval(
+        &self,
+        approver: impl Into<String>,
+        role: Option<String>,
+        now: DateTime<Utc>,
+    ) -> Result<crate::events::PolicyEvent, crate::PolicyError> {
+        use crate::events::{PolicyApprovalRecorded, PolicyEvent};
+
+        let approver = approver.into();
+        if self.approvals.iter().any(|a| a.approver == approver) {
+            return Err(crate::PolicyError::ValidationError(format!(
+                "{} has already approved this policy",
+                approver
+            )));
+        }
+
+        Ok(PolicyEvent::PolicyApprovalRecorded(PolicyApprovalRecorded {
+            event_id: Uuid::now_v7(),
+            identity: crate::sagas::create_root_command(),
+            policy_id: self.id,
+            approver,
+            role,
+            external: false,
+            recorded_at: now,
+            approver_kind: None,
+        }))
+    }
+
+    /// Record the external verification answering a pending approval request
+    ///
+    /// Emits `PolicyExternalApprovalVerified`; apply it with `apply_event_pure`.
+    pub fn record_external_verification(
+        &self,
+        request_id: Uuid,
+        verification: ExternalVerification,
+    ) -> Result<crate::events::PolicyEvent, crate::PolicyError> {
+        use crate::events::{PolicyEvent, PolicyExternalApprovalVerified};
+
+        let pending = self
+            .pending_external_approvals
+            .iter()
+            .find(|p| p.request_id == request_id)
+            .ok_or(crate::PolicyError::UnknownApprovalRequest(request_id))?;
+
+        if verification.verified_at > pending.expires_at {
+            return Err(crate::PolicyError::ValidationError(format!(
+                "External approval request {} expired before verification",
+                request_id
+            )));
+        }
+
+        Ok(PolicyEvent::PolicyExternalApprovalVerified(PolicyExternalApprovalVerified {
+            event_id: Uuid::now_v7(),
+            identity: crate::sagas::create_root_command(),
+            policy_id: self.id,
+            request_id,
+            verifier: verification.verifier,
+            method: verification.method,
+            verified_at: verification.verified_at,
+        }))
+    }
+
+    /// Expire external approval requests that went unanswered until `now`
+    ///
+    /// Emits one `PolicyExternalApprovalExpired` per expired request.
+    pub fn expire_stale_external_approvals(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Vec<crate::events::PolicyEvent> {
+        use crate::events::{PolicyEvent, PolicyExternalApprovalExpired};
+
+        self.pending_external_approvals
+            .iter()
+            .filter(|p| p.expires_at <= now)
+            .map(|p| {
+                PolicyEvent::PolicyExternalApprovalExpired(PolicyExternalApprovalExpired {
+                    event_id: Uuid::now_v7(),
+                    identity: crate::sagas::create_root_command(),
+                    policy_id: self.id,
+                    request_id: p.request_id,
+                    expired_at: now,
+                })
+            })
+            .collect()
+    }
+
+    /// Update policy status
+    pub fn update_status(&mut self, status: PolicyStatus) -> Result<(), crate::PolicyError> {
+        if !self.status.can_transition_to(status) {
+            return Err(crate::PolicyError::ValidationError(
+                format!("Invalid status transition from {:?} to {:?}", self.status, status)
+            ));
         }
 
         self.status = status;
         Ok(())
     }
 
-    /// Check if policy is currently effective
-    pub fn is_effective(&self) -> bool {
+    /// Check if policy is effective at the clock's current time
+    pub fn is_effective(&self, clock: &dyn crate::Clock) -> bool {
+        self.is_effective_at(clock.now())
+    }
+
+    /// Check if policy is effective at the given point in time
+    pub fn is_effective_at(&self, now: DateTime<Utc>) -> bool {
         if self.status != PolicyStatus::Active {
             return false;
         }
 
-        let now = Utc::now();
-
         if let Some(effective_date) = self.effective_date {
             if now < effective_date {
                 return false;
@@ -176,6 +505,74 @@ impl Policy {
         new_version.metadata.created_at = Utc::now();
         new_version
     }
+
+    /// Compare this policy with another version of it
+    ///
+    /// Rules are matched by id, so a rule edited in place is reported as
+    /// modified while a replaced rule shows up as removed and added.
+    pub fn diff(&self, other: &Policy) -> PolicyDiff {
+        let added_rules = other
+            .rules
+            .iter()
+            .filter(|rule| !self.rules.iter().any(|r| r.id == rule.id))
+            .cloned()
+            .collect();
+        let removed_rules = self
+            .rules
+            .iter()
+            .filter(|rule| !other.rules.iter().any(|r| r.id == rule.id))
+            .cloned()
+            .collect();
+        let modified_rules = self
+            .rules
+            .iter()
+            .filter_map(|before| {
+                let after = other.rules.iter().find(|r| r.id == before.id)?;
+                Change::between(before.clone(), after.clone())
+            })
+            .collect();
+
+        let mut other_metadata = other.metadata.clone();
+        other_metadata.created_at = self.metadata.created_at;
+
+        PolicyDiff {
+            from_version: self.version,
+            to_version: other.version,
+            added_rules,
+            removed_rules,
+            modified_rules,
+            name: Change::between(self.name.clone(), other.name.clone()),
+            description: Change::between(self.description.clone(), other.description.clone()),
+            target: Change::between(self.target.clone(), other.target.clone()),
+            enforcement_level: Change::between(self.enforcement_level, other.enforcement_level),
+            metadata: Change::between(self.metadata.clone(), other_metadata),
+            effective_date: Change::between(self.effective_date, other.effective_date),
+            expiry_date: Change::between(self.expiry_date, other.expiry_date),
+        }
+    }
+
+    /// SHA-256 of the policy's canonical form, for detecting tampering
+    ///
+    /// Covers every field, so any change (including status and approvals)
+    /// produces a different hash, while map insertion order does not.
+    pub fn content_hash(&self) -> [u8; 32] {
+        crate::value_objects::content_hash(self).expect("policies serialize to JSON")
+    }
+
+    /// Export the policy definition as YAML for review in version control
+    ///
+    /// See [`PolicyDocument`](crate::documents::PolicyDocument) for what is
+    /// included; lifecycle state is not.
+    pub fn to_yaml(&self) -> Result<String, crate::PolicyError> {
+        crate::documents::to_yaml(&crate::documents::PolicyDocument::from(self))
+    }
+
+    /// Import a policy definition from YAML as a draft
+    ///
+    /// Malformed or invalid documents are rejected with `ValidationError`.
+    pub fn from_yaml(yaml: &str) -> Result<Self, crate::PolicyError> {
+        crate::documents::from_yaml::<crate::documents::PolicyDocument>(yaml)?.into_policy()
+    }
 }
 
 /// PolicySet aggregate - groups multiple policies
@@ -236,17 +633,29 @@ impl PolicySet {
             PolicyEvent::PolicyCreated(_) |
             PolicyEvent::PolicyUpdated(_) |
             PolicyEvent::PolicyApproved(_) |
+            PolicyEvent::PolicyApprovalRecorded(_) |
+            PolicyEvent::PolicyApprovalDelegated(_) |
+            PolicyEvent::PolicyExternalApprovalRequested(_) |
+            PolicyEvent::PolicyExternalApprovalVerified(_) |
+            PolicyEvent::PolicyExternalApprovalExpired(_) |
             PolicyEvent::PolicyActivated(_) |
             PolicyEvent::PolicySuspended(_) |
             PolicyEvent::PolicyRevoked(_) |
             PolicyEvent::PolicyArchived(_) |
+            PolicyEvent::PolicyExpiringSoon(_) |
             PolicyEvent::PolicyEvaluated(_) |
             PolicyEvent::PolicyViolationDetected(_) |
             PolicyEvent::PolicyCompliancePassed(_) |
             PolicyEvent::PolicyExemptionGranted(_) |
             PolicyEvent::PolicyExemptionRevoked(_) |
             PolicyEvent::PolicyExemptionExpired(_) |
-            PolicyEvent::PolicyConflictDetected(_) => {
+            PolicyEvent::PolicyExemptionRenewed(_) |
+            PolicyEvent::PolicyExemptionUsed(_) |
+            PolicyEvent::PolicyExemptionExhausted(_) |
+            PolicyEvent::PolicyConflictDetected(_) |
+            PolicyEvent::PolicyTemplateSaved(_) |
+            PolicyEvent::PolicyTemplateDeleted(_) |
+            PolicyEvent::PolicyInstantiatedFromTemplate(_) => {
                 // These don't modify PolicySet
             }
         }
@@ -254,6 +663,16 @@ impl PolicySet {
         Ok(new_set)
     }
 
+    /// Export the policy set definition as YAML for review in version control
+    pub fn to_yaml(&self) -> Result<String, crate::PolicyError> {
+        crate::documents::to_yaml(&crate::documents::PolicySetDocument::from(self))
+    }
+
+    /// Import a policy set definition from YAML as a draft
+    pub fn from_yaml(yaml: &str) -> Result<Self, crate::PolicyError> {
+        crate::documents::from_yaml::<crate::documents::PolicySetDocument>(yaml)?.into_policy_set()
+    }
+
     /// Add a policy to the set
     pub fn add_policy(&mut self, policy_id: PolicyId) {
         if !self.policies.contains(&policy_id) {
@@ -261,6 +680,106 @@ impl PolicySet {
         }
     }
 
+    /// Add a policy after checking that it may join the set
+    ///
+    /// Only draft and active policies can be added, and only once.
+    pub fn add_policy_checked(&mut self, policy: &Policy) -> Result<(), crate::PolicyError> {
+        if !matches!(policy.status, PolicyStatus::Draft | PolicyStatus::Active) {
+            return Err(crate::PolicyError::ValidationError(format!(
+                "Cannot add policy {} in status {:?} to a set",
+                policy.id.0, policy.status
+            )));
+        }
+        if self.policies.contains(&policy.id) {
+            return Err(crate::PolicyError::ValidationError(format!(
+                "Policy {} is already a member of set {}",
+                policy.id.0, self.id.0
+            )));
+        }
+
+        self.policies.push(policy.id);
+        Ok(())
+    }
+
+    /// Handle an add-to-set command
+    ///
+    /// Emits `PolicyAddedToSet`, followed by a `PolicyConflictDetected` for
+    /// every conflict between the added policy and the current `members`.
+    pub fn add_member(
+        &self,
+        command: &crate::commands::AddPolicyToSet,
+        policy: &Policy,
+        members: &[Policy],
+        now: DateTime<Utc>,
+    ) -> Result<Vec<crate::events::PolicyEvent>, crate::PolicyError> {
+        use crate::entities::ConflictType;
+        use crate::events::*;
+        use crate::sagas::create_caused_by;
+
+        self.clone().add_policy_checked(policy)?;
+
+        let mut events = vec![PolicyEvent::PolicyAddedToSet(PolicyAddedToSet {
+            event_id: Uuid::now_v7(),
+            identity: create_caused_by(&command.identity),
+            policy_set_id: self.id,
+            policy_id: policy.id,
+            added_at: now,
+            added_by: command.added_by.clone(),
+        })];
+
+        let resolver = crate::services::PolicyConflictResolver::new(self.conflict_resolution);
+        let candidates: Vec<Policy> = members
+            .iter()
+            .filter(|member| self.policies.contains(&member.id))
+            .cloned()
+            .chain(std::iter::once(policy.clone()))
+            .collect();
+
+        events.extend(
+            resolver
+                .detect_conflicts(&candidates)
+                .into_iter()
+                .filter(|conflict| conflict.policy_ids.contains(&policy.id))
+                .map(|conflict| {
+                    PolicyEvent::PolicyConflictDetected(PolicyConflictDetected {
+                        event_id: Uuid::now_v7(),
+                        identity: create_caused_by(&command.identity),
+                        conflict_id: conflict.id,
+                        policy_ids: conflict.policy_ids,
+                        conflict_type: format!("{:?}", conflict.conflict_type),
+                        description: conflict.description,
+                        detected_at: now,
+                        severity: match conflict.conflict_type {
+                            ConflictType::Contradiction | ConflictType::Impossible => Severity::High,
+                            ConflictType::EnforcementMismatch => Severity::Medium,
+                            ConflictType::Overlap | ConflictType::Ambiguous => Severity::Low,
+                        },
+                    })
+                }),
+        );
+
+        Ok(events)
+    }
+
+    /// Every rule the set would evaluate, attributed to its source policy
+    ///
+    /// Rules are gathered from member policies in set order; conflicting
+    /// rules are resolved with the set's `conflict_resolution`.
+    pub fn effective_rules(
+        &self,
+        policies: &[&Policy],
+        resolver: &crate::services::PolicyConflictResolver,
+    ) -> Vec<(PolicyId, crate::entities::PolicyRule)> {
+        let rules = self
+            .policies
+            .iter()
+            .filter_map(|id| policies.iter().find(|p| p.id == *id))
+            .flat_map(|policy| policy.rules.iter().map(|rule| (policy.id, rule.clone())))
+            .collect();
+
+        resolver.resolve_rules(rules, self.conflict_resolution)
+    }
+
     /// Remove a policy from the set
     pub fn remove_policy(&mut self, policy_id: &PolicyId) {
         self.policies.retain(|id| id != policy_id);
@@ -310,6 +829,17 @@ pub struct PolicyExemption {
     pub scope: ExemptionScope,
     pub conditions: Vec<ExemptionCondition>,
     pub status: ExemptionStatus,
+    #[serde(default)]
+    pub renewals: Vec<ExemptionRenewal>,
+    /// Number of times the exemption may be applied, unlimited if `None`
+    #[serde(default)]
+    pub max_uses: Option<u32>,
+    /// Number of times the exemption has been applied, as last recorded
+    #[serde(default)]
+    pub uses_count: u32,
+    /// Stream sequence of the exemption's last event when it was loaded
+    #[serde(default)]
+    pub last_sequence: u64,
 }
 
 impl PolicyExemption {
@@ -335,9 +865,38 @@ impl PolicyExemption {
             scope: ExemptionScope::Global,
             conditions: Vec::new(),
             status: ExemptionStatus::Active,
+            renewals: Vec::new(),
+            max_uses: None,
+            uses_count: 0,
+            last_sequence: 0,
         }
     }
 
+    /// Handle a renewal command, producing the renewal event
+    pub fn renew(
+        &self,
+        command: &crate::commands::RenewExemption,
+        now: DateTime<Utc>,
+    ) -> Result<crate::events::PolicyEvent, crate::PolicyError> {
+        use crate::events::{PolicyEvent, PolicyExemptionRenewed};
+
+        let event = PolicyEvent::PolicyExemptionRenewed(PolicyExemptionRenewed {
+            event_id: Uuid::now_v7(),
+            identity: crate::sagas::create_caused_by(&command.identity),
+            exemption_id: self.id,
+            policy_id: self.policy_id,
+            renewed_by: command.renewed_by.clone(),
+            renewed_at: now,
+            previous_valid_until: self.valid_until,
+            new_valid_until: command.new_valid_until,
+            justification: command.justification.clone(),
+        });
+
+        // Validate the renewal against the current state
+        self.apply_event_pure(&event)?;
+        Ok(event)
+    }
+
     /// Apply an event to create a new exemption state (pure function)
     pub fn apply_event_pure(&self, event: &crate::events::PolicyEvent) -> Result<Self, crate::PolicyError> {
         use crate::events::PolicyEvent;
@@ -349,15 +908,23 @@ impl PolicyExemption {
                 new_exemption.id = e.exemption_id;
                 new_exemption.policy_id = e.policy_id;
                 new_exemption.reason = e.reason.clone();
-                new_exemption.justification = String::new(); // Set from command
+                new_exemption.justification = e.justification.clone();
                 new_exemption.risk_acceptance = e.risk_acceptance.clone();
                 new_exemption.approved_by = e.granted_by.clone();
                 new_exemption.approved_at = e.granted_at;
                 new_exemption.valid_from = e.granted_at;
                 new_exemption.valid_until = e.valid_until;
-                new_exemption.scope = ExemptionScope::Global;
-                new_exemption.conditions = Vec::new();
+                new_exemption.scope = e.scope.clone();
+                new_exemption.conditions = e.conditions.clone();
                 new_exemption.status = ExemptionStatus::Active;
+                new_exemption.max_uses = e.max_uses;
+                new_exemption.uses_count = 0;
+            }
+            PolicyEvent::PolicyExemptionUsed(e) => {
+                new_exemption.uses_count = e.uses_count;
+            }
+            PolicyEvent::PolicyExemptionExhausted(e) => {
+                new_exemption.uses_count = e.uses_count;
             }
             PolicyEvent::PolicyExemptionRevoked(e) => {
                 new_exemption.status = ExemptionStatus::Revoked {
@@ -369,21 +936,51 @@ impl PolicyExemption {
             PolicyEvent::PolicyExemptionExpired(_e) => {
                 new_exemption.status = ExemptionStatus::Expired;
             }
+            PolicyEvent::PolicyExemptionRenewed(e) => {
+                if matches!(new_exemption.status, ExemptionStatus::Revoked { .. }) {
+                    return Err(crate::PolicyError::ValidationError(format!(
+                        "Cannot renew revoked exemption {}",
+                        e.exemption_id.0
+                    )));
+                }
+                if e.new_valid_until <= new_exemption.valid_until {
+                    return Err(crate::PolicyError::ValidationError(
+                        "Renewal must extend the exemption's validity".to_string(),
+                    ));
+                }
+                new_exemption.valid_until = e.new_valid_until;
+                new_exemption.status = ExemptionStatus::Active;
+                new_exemption.renewals.push(ExemptionRenewal {
+                    renewed_by: e.renewed_by.clone(),
+                    renewed_at: e.renewed_at,
+                    previous_valid_until: e.previous_valid_until,
+                    justification: e.justification.clone(),
+                });
+            }
             // Other events don't modify PolicyExemption aggregate
             PolicyEvent::PolicyCreated(_) |
             PolicyEvent::PolicyUpdated(_) |
             PolicyEvent::PolicyApproved(_) |
+            PolicyEvent::PolicyApprovalRecorded(_) |
+            PolicyEvent::PolicyApprovalDelegated(_) |
+            PolicyEvent::PolicyExternalApprovalRequested(_) |
+            PolicyEvent::PolicyExternalApprovalVerified(_) |
+            PolicyEvent::PolicyExternalApprovalExpired(_) |
             PolicyEvent::PolicyActivated(_) |
             PolicyEvent::PolicySuspended(_) |
             PolicyEvent::PolicyRevoked(_) |
             PolicyEvent::PolicyArchived(_) |
+            PolicyEvent::PolicyExpiringSoon(_) |
             PolicyEvent::PolicyEvaluated(_) |
             PolicyEvent::PolicyViolationDetected(_) |
             PolicyEvent::PolicyCompliancePassed(_) |
             PolicyEvent::PolicySetCreated(_) |
             PolicyEvent::PolicyAddedToSet(_) |
             PolicyEvent::PolicyRemovedFromSet(_) |
-            PolicyEvent::PolicyConflictDetected(_) => {
+            PolicyEvent::PolicyConflictDetected(_) |
+            PolicyEvent::PolicyTemplateSaved(_) |
+            PolicyEvent::PolicyTemplateDeleted(_) |
+            PolicyEvent::PolicyInstantiatedFromTemplate(_) => {
                 // These don't modify PolicyExemption
             }
         }
@@ -391,30 +988,74 @@ impl PolicyExemption {
         Ok(new_exemption)
     }
 
-    /// Check if exemption is currently valid
-    pub fn is_valid(&self) -> bool {
-        if self.status != ExemptionStatus::Active {
+    /// Check if exemption is valid at the clock's current time
+    pub fn is_valid(&self, clock: &dyn crate::Clock) -> bool {
+        self.is_valid_at(clock.now())
+    }
+
+    /// Check if exemption is valid at the given point in time
+    pub fn is_valid_at(&self, now: DateTime<Utc>) -> bool {
+        if self.status != ExemptionStatus::Active || self.is_exhausted() {
             return false;
         }
 
-        let now = Utc::now();
         now >= self.valid_from && now <= self.valid_until
     }
 
-    /// Revoke the exemption
-    pub fn revoke(&mut self, revoked_by: impl Into<String>, reason: impl Into<String>) {
+    /// Whether a usage-limited exemption has been applied its maximum number of times
+    pub fn is_exhausted(&self) -> bool {
+        self.max_uses.is_some_and(|max_uses| self.uses_count >= max_uses)
+    }
+
+    /// Revoke the exemption at `now`
+    pub fn revoke(
+        &mut self,
+        revoked_by: impl Into<String>,
+        reason: impl Into<String>,
+        now: DateTime<Utc>,
+    ) {
         self.status = ExemptionStatus::Revoked {
             revoked_by: revoked_by.into(),
-            revoked_at: Utc::now(),
+            revoked_at: now,
             reason: reason.into(),
         };
     }
 }
 
-/// Scope of an exemption
+/// ClaimsPolicy - claims-based allow/deny policy for resources
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClaimsPolicy {
+    pub effect: PolicyEffect,
+    pub resources: Vec<ResourcePattern>,
+    pub condition: PolicyCondition,
+}
+
+impl ClaimsPolicy {
+    /// Create a new claims policy
+    pub fn new(effect: PolicyEffect, resources: Vec<ResourcePattern>, condition: PolicyCondition) -> Self {
+        Self {
+            effect,
+            resources,
+            condition,
+        }
+    }
+
+    /// Check if the policy covers the given resource
+    pub fn covers(&self, resource: &str) -> bool {
+        self.resources.iter().any(|pattern| pattern.matches(resource))
+    }
+
+    /// Check if the policy applies to the resource for the given claims
+    pub fn applies(&self, resource: &str, claims: &ClaimSet) -> bool {
+        self.covers(resource) && self.condition.evaluate(claims)
+    }
+}
+
+/// Scope of an exemption
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub enum ExemptionScope {
     /// Exemption applies globally
+    #[default]
     Global,
     /// Exemption applies to specific organization
     Organization(Uuid),
@@ -426,11 +1067,30 @@ pub enum ExemptionScope {
     Operation(OperationType),
 }
 
+impl ExemptionScope {
+    /// Check if the exemption scope covers everything the policy target applies to
+    pub fn covers(&self, target: &PolicyTarget) -> bool {
+        match (self, target) {
+            (ExemptionScope::Global, _) => true,
+            (_, PolicyTarget::Composite(targets)) => {
+                !targets.is_empty() && targets.iter().all(|t| self.covers(t))
+            }
+            (ExemptionScope::Organization(id1), PolicyTarget::Organization(id2)) => id1 == id2,
+            (ExemptionScope::Resource(name), PolicyTarget::Resource(resource)) => {
+                ResourceType::from_name(name) == *resource
+            }
+            (ExemptionScope::Operation(op1), PolicyTarget::Operation(op2)) => op1 == op2,
+            _ => false,
+        }
+    }
+}
+
 /// Conditions that must be met for exemption to apply
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ExemptionCondition {
     pub field: String,
     pub operator: ConditionOperator,
+    #[serde(with = "crate::value_objects::wire::value")]
     pub value: Value,
 }
 
@@ -443,6 +1103,17 @@ pub enum ConditionOperator {
     LessThan,
     Contains,
     NotContains,
+    /// Inclusive range; the condition value is a `[low, high]` list
+    Between,
+}
+
+/// Record of an exemption's validity being extended
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExemptionRenewal {
+    pub renewed_by: String,
+    pub renewed_at: DateTime<Utc>,
+    pub previous_valid_until: DateTime<Utc>,
+    pub justification: String,
 }
 
 /// Status of an exemption
@@ -474,6 +1145,132 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_redelivered_event_is_a_no_op() {
+        let mut policy = Policy::new("Key Size", "Minimum key size");
+        policy.status = PolicyStatus::Approved;
+        let activated = policy
+            .activate(
+                &crate::commands::ActivatePolicy {
+                    identity: create_message_identity(),
+                    policy_id: policy.id,
+                    activated_by: "ops".to_string(),
+                    effective_immediately: true,
+                    schedule_activation: None,
+                },
+                Utc::now(),
+            )
+            .unwrap();
+        let updated = PolicyEvent::PolicyUpdated(PolicyUpdated {
+            event_id: Uuid::now_v7(),
+            identity: create_message_identity(),
+            policy_id: policy.id,
+            version: 2,
+            changes: Vec::new(),
+            updated_by: "alice".to_string(),
+            updated_at: Utc::now(),
+        });
+
+        let once = policy.apply_sequenced(7, &activated).unwrap();
+        let twice = once.apply_sequenced(7, &activated).unwrap();
+        assert_eq!(twice.status, PolicyStatus::Active);
+        assert_eq!(twice.last_sequence, 7);
+        assert_eq!(twice.content_hash(), once.content_hash());
+
+        let v2 = twice.apply_sequenced(9, &updated).unwrap();
+        let v2_again = v2.apply_sequenced(9, &updated).unwrap();
+        assert_eq!(v2_again.version, 2);
+        assert_eq!(v2_again.content_hash(), v2.content_hash());
+
+        // Re-delivery of an earlier event leaves the state untouched
+        let redelivered = v2.apply_sequenced(7, &activated).unwrap();
+        assert_eq!(redelivered.last_sequence, 9);
+        assert_eq!(redelivered.content_hash(), v2.content_hash());
+    }
+
+    #[test]
+    fn test_every_event_applies_to_every_aggregate() {
+        use crate::events::fixtures::{event_of_every_type, EVENT_VARIANT_COUNT};
+
+        let policy = Policy::new("Key Size", "Minimum key size");
+        let set = PolicySet::new("Crypto", "Crypto policies");
+        let exemption = PolicyExemption::new(
+            policy.id,
+            "Legacy system",
+            "Scheduled for replacement",
+            "ciso",
+            Utc::now() + chrono::Duration::days(30),
+        );
+
+        // Errors are fine (e.g. a renewal that doesn't extend validity);
+        // reaching the end without a panic is what's being checked
+        let events = event_of_every_type(Uuid::now_v7());
+        assert_eq!(events.len(), EVENT_VARIANT_COUNT);
+        for event in &events {
+            let _ = policy.apply_event_pure(event);
+            let _ = set.apply_event_pure(event);
+            let _ = exemption.apply_event_pure(event);
+        }
+    }
+
+    #[test]
+    fn test_diff_between_versions() {
+        let mut v1 = Policy::new("Key Size", "Minimum key size");
+        v1.add_rule(PolicyRule::min_key_size(2048));
+        v1.metadata.tags = vec!["crypto".to_string()];
+
+        let mut v2 = v1.create_version();
+        assert!(v1.diff(&v2).is_empty());
+
+        let added = PolicyRule::max_validity_days(90);
+        v2.add_rule(added.clone());
+        v2.enforcement_level = EnforcementLevel::Hard;
+
+        let diff = v1.diff(&v2);
+        assert_eq!((diff.from_version, diff.to_version), (1, 2));
+        assert_eq!(diff.added_rules, vec![added.clone()]);
+        assert!(diff.removed_rules.is_empty());
+        assert!(diff.modified_rules.is_empty());
+        assert_eq!(
+            diff.enforcement_level,
+            Some(Change { before: EnforcementLevel::Advisory, after: EnforcementLevel::Hard })
+        );
+        assert!(diff.target.is_none());
+        assert!(diff.metadata.is_none());
+
+        let rendered = diff.to_string();
+        assert!(rendered.contains("Policy v1 -> v2"));
+        assert!(rendered.contains("enforcement level: Advisory -> Hard"));
+        assert!(rendered.contains(&format!("+ rule '{}'", added.name)));
+
+        // Editing a rule in place is a modification, not an add/remove pair
+        v2.rules[0].severity = Severity::Low;
+        let diff = v1.diff(&v2);
+        assert_eq!(diff.modified_rules.len(), 1);
+        assert_eq!(diff.modified_rules[0].after.severity, Severity::Low);
+    }
+
+    #[test]
+    fn test_content_hash_ignores_map_insertion_order() {
+        let mut first = Policy::new("Key Size", "Minimum key size");
+        let mut rule = PolicyRule::min_key_size(2048);
+        rule.parameters.insert("a".to_string(), Value::Float(-0.0));
+        rule.parameters.insert("b".to_string(), Value::Integer(1));
+        first.add_rule(rule);
+
+        let mut second = first.clone();
+        second.rules[0].parameters = [
+            ("b".to_string(), Value::Integer(1)),
+            ("a".to_string(), Value::Float(0.0)),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(first.content_hash(), second.content_hash());
+
+        second.enforcement_level = EnforcementLevel::Hard;
+        assert_ne!(first.content_hash(), second.content_hash());
+    }
+
     #[test]
     fn test_policy_created_event() {
         let policy = Policy::new("Test Policy", "Test Description");
@@ -488,6 +1285,7 @@ mod tests {
             policy_type: "Security".to_string(),
             created_by: "test-user".to_string(),
             created_at: Utc::now(),
+            approval_requirements: Default::default(),
         });
 
         let new_policy = policy.apply_event_pure(&event).unwrap();
@@ -679,17 +1477,86 @@ mod tests {
             granted_by: "admin".to_string(),
             granted_at: Utc::now(),
             reason: "Granted".to_string(),
+            justification: "Vendor fix pending".to_string(),
             valid_until: Utc::now() + chrono::Duration::days(30),
             risk_acceptance: Some("Acceptable risk".to_string()),
+            scope: ExemptionScope::Global,
+            conditions: Vec::new(),
+            max_uses: None,
         });
 
         let new_exemption = exemption.apply_event_pure(&event).unwrap();
         assert_eq!(new_exemption.id, exemption_id);
         assert_eq!(new_exemption.policy_id, policy_id);
         assert_eq!(new_exemption.reason, "Granted");
+        assert_eq!(new_exemption.justification, "Vendor fix pending");
         assert_eq!(new_exemption.status, ExemptionStatus::Active);
     }
 
+    #[test]
+    fn test_exemption_grant_details_survive_replay() {
+        let granted = PolicyExemptionGranted {
+            event_id: Uuid::now_v7(),
+            identity: create_message_identity(),
+            exemption_id: ExemptionId::new(),
+            policy_id: PolicyId::new(),
+            granted_by: "ciso".to_string(),
+            granted_at: Utc::now(),
+            reason: "Legacy HSM".to_string(),
+            justification: "Vendor firmware update due next quarter".to_string(),
+            valid_until: Utc::now() + chrono::Duration::days(90),
+            risk_acceptance: None,
+            scope: ExemptionScope::User("build-bot".to_string()),
+            conditions: vec![ExemptionCondition {
+                field: "environment".to_string(),
+                operator: ConditionOperator::Equals,
+                value: Value::String("staging".to_string()),
+            }],
+            max_uses: Some(5),
+        };
+
+        // Round-trip through the stored form, then rebuild from scratch
+        let stored = serde_json::to_value(PolicyEvent::PolicyExemptionGranted(granted.clone())).unwrap();
+        let replayed: PolicyEvent = serde_json::from_value(stored.clone()).unwrap();
+        let blank = PolicyExemption::new(granted.policy_id, "", "", "", granted.valid_until);
+        let rebuilt = blank.apply_event_pure(&replayed).unwrap();
+        assert_eq!(rebuilt.justification, "Vendor firmware update due next quarter");
+        assert_eq!(rebuilt.scope, granted.scope);
+        assert_eq!(rebuilt.conditions, granted.conditions);
+        assert_eq!(rebuilt.max_uses, Some(5));
+
+        // Events recorded before the field existed still load
+        let mut legacy = stored;
+        legacy.as_object_mut().unwrap().remove("justification");
+        legacy.as_object_mut().unwrap().remove("scope");
+        legacy.as_object_mut().unwrap().remove("conditions");
+        legacy.as_object_mut().unwrap().remove("max_uses");
+        let legacy: PolicyEvent = serde_json::from_value(legacy).unwrap();
+        let legacy = blank.apply_event_pure(&legacy).unwrap();
+        assert_eq!(legacy.justification, "");
+        assert_eq!(legacy.scope, ExemptionScope::Global);
+        assert!(legacy.conditions.is_empty());
+        assert_eq!(legacy.max_uses, None);
+    }
+
+    #[test]
+    fn test_exemption_scope_covers_matching_resource() {
+        let secrets = PolicyTarget::Resource(ResourceType::Secret);
+
+        assert!(ExemptionScope::Resource("secret".to_string()).covers(&secrets));
+        assert!(ExemptionScope::Resource("Secret".to_string()).covers(&secrets));
+        assert!(!ExemptionScope::Resource("key".to_string()).covers(&secrets));
+
+        let queues = PolicyTarget::Resource(ResourceType::Custom("queue".to_string()));
+        assert!(ExemptionScope::Resource("queue".to_string()).covers(&queues));
+        assert!(!ExemptionScope::Resource("Queue".to_string()).covers(&queues));
+
+        assert!(ExemptionScope::Operation(OperationType::KeyRotation)
+            .covers(&PolicyTarget::Operation(OperationType::KeyRotation)));
+        assert!(!ExemptionScope::Resource("secret".to_string())
+            .covers(&PolicyTarget::Composite(vec![secrets, queues])));
+    }
+
     #[test]
     fn test_exemption_revoked_event() {
         let mut exemption = PolicyExemption::new(
@@ -761,4 +1628,514 @@ mod tests {
         assert_eq!(original.name, original_name);
         assert_eq!(original.status, original_status);
     }
+
+    #[test]
+    fn test_policy_effectiveness_at_timestamp() {
+        let now = Utc::now();
+        let mut policy = Policy::new("Windowed", "Effective for a limited time");
+        policy.status = PolicyStatus::Active;
+        policy.effective_date = Some(now - chrono::Duration::days(10));
+        policy.expiry_date = Some(now - chrono::Duration::days(1));
+
+        assert!(policy.is_effective_at(now - chrono::Duration::days(5)));
+        assert!(!policy.is_effective_at(now - chrono::Duration::days(11)));
+        assert!(!policy.is_effective_at(now));
+        assert!(!policy.is_effective(&crate::MockClock::new(now)));
+        assert!(policy.is_effective(&crate::MockClock::new(now - chrono::Duration::days(5))));
+    }
+
+    #[test]
+    fn test_exemption_validity_at_timestamp() {
+        let now = Utc::now();
+        let mut exemption = PolicyExemption::new(
+            PolicyId::new(),
+            "Maintenance",
+            "Planned outage",
+            "admin",
+            now - chrono::Duration::days(1),
+        );
+        exemption.valid_from = now - chrono::Duration::days(3);
+
+        assert!(exemption.is_valid_at(now - chrono::Duration::days(2)));
+        assert!(!exemption.is_valid_at(now));
+        assert!(!exemption.is_valid(&crate::MockClock::new(now)));
+        assert!(exemption.is_valid(&crate::MockClock::new(now - chrono::Duration::days(2))));
+    }
+
+    fn approve_command(policy: &Policy, approver: &str, role: Option<&str>, external: bool) -> crate::commands::ApprovePolicy {
+        crate::commands::ApprovePolicy {
+            identity: create_message_identity(),
+            policy_id: policy.id,
+            approved_by: approver.to_string(),
+            approval_notes: None,
+            approver_role: role.map(str::to_string),
+            external,
+            approver_kind: None,
+        }
+    }
+
+    fn policy_requiring(requirements: ApprovalRequirements) -> Policy {
+        Policy::new("Gated", "Needs approvals").apply_event_pure(&PolicyEvent::PolicyCreated(PolicyCreated {
+            event_id: Uuid::now_v7(),
+            identity: create_message_identity(),
+            policy_id: PolicyId::new(),
+            name: "Gated".to_string(),
+            description: "Needs approvals".to_string(),
+            policy_type: "Security".to_string(),
+            created_by: "admin".to_string(),
+            created_at: Utc::now(),
+            approval_requirements: requirements,
+        })).unwrap()
+    }
+
+    fn approve(policy: Policy, approver: &str, role: Option<&str>, external: bool) -> (Policy, Vec<PolicyEvent>) {
+        let events = policy.approve(&approve_command(&policy, approver, role, external), Utc::now()).unwrap();
+        let policy = events.iter().fold(policy, |p, e| p.apply_event_pure(e).unwrap());
+        (policy, events)
+    }
+
+    #[test]
+    fn test_approval_pending_until_requirements_met() {
+        let policy = policy_requiring(ApprovalRequirements {
+            min_approvals: 2,
+            required_approvers: vec!["ciso".to_string()],
+            approval_roles: vec!["security".to_string()],
+            ..Default::default()
+        });
+
+        let (policy, events) = approve(policy, "alice", Some("security"), false);
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], PolicyEvent::PolicyApprovalRecorded(_)));
+        assert_eq!(policy.status, PolicyStatus::Draft);
+        assert_eq!(policy.approval_requirements.outstanding(&policy.approvals).len(), 2);
+
+        // Same approver can't count twice
+        assert!(policy.approve(&approve_command(&policy, "alice", None, false), Utc::now()).is_err());
+
+        let (policy, events) = approve(policy, "ciso", None, false);
+        assert!(matches!(events.last(), Some(PolicyEvent::PolicyApproved(_))));
+        assert_eq!(policy.status, PolicyStatus::Approved);
+    }
+
+    #[test]
+    fn test_weighted_quorum_counts_role_weights() {
+        let requirements = ApprovalRequirements {
+            role_weights: std::collections::HashMap::from([("director".to_string(), 3)]),
+            required_weight: 3,
+            ..Default::default()
+        };
+
+        // Two engineers weigh 1 each and fall short of the quorum
+        let policy = policy_requiring(requirements.clone());
+        let (policy, _) = approve(policy, "alice", Some("engineer"), false);
+        let (policy, _) = approve(policy, "bob", Some("engineer"), false);
+        assert_eq!(policy.status, PolicyStatus::Draft);
+        assert_eq!(
+            policy.approval_requirements.outstanding(&policy.approvals),
+            vec!["2 of 3 approval weight recorded".to_string()]
+        );
+
+        // One director meets it alone
+        let policy = policy_requiring(requirements.clone());
+        let (policy, events) = approve(policy, "dana", Some("director"), false);
+        assert!(matches!(events.last(), Some(PolicyEvent::PolicyApproved(_))));
+        assert_eq!(policy.status, PolicyStatus::Approved);
+
+        // Required approvers are still needed on top of the weight
+        let policy = policy_requiring(ApprovalRequirements {
+            required_approvers: vec!["ciso".to_string()],
+            ..requirements
+        });
+        let (policy, _) = approve(policy, "dana", Some("director"), false);
+        assert_eq!(policy.status, PolicyStatus::Draft);
+        let (policy, _) = approve(policy, "ciso", None, false);
+        assert_eq!(policy.status, PolicyStatus::Approved);
+    }
+
+    #[test]
+    fn test_delegate_approves_for_required_approver_within_window() {
+        let requirements = |valid_until| ApprovalRequirements {
+            required_approvers: vec!["ciso".to_string()],
+            delegations: vec![ApprovalDelegation {
+                from: "ciso".to_string(),
+                to: "deputy".to_string(),
+                role: None,
+                valid_until,
+            }],
+            ..Default::default()
+        };
+
+        let policy = policy_requiring(requirements(Utc::now() + chrono::Duration::days(7)));
+        let (policy, events) = approve(policy, "deputy", None, false);
+        assert!(matches!(events.last(), Some(PolicyEvent::PolicyApproved(_))));
+        assert_eq!(policy.status, PolicyStatus::Approved);
+
+        let policy = policy_requiring(requirements(Utc::now() - chrono::Duration::days(1)));
+        let (policy, _) = approve(policy, "deputy", None, false);
+        assert_eq!(policy.status, PolicyStatus::Draft);
+        assert_eq!(
+            policy.approval_requirements.outstanding(&policy.approvals),
+            vec!["approval from ciso".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_delegation_event_lets_delegate_cover_delegator_role() {
+        let policy = policy_requiring(ApprovalRequirements {
+            approval_roles: vec!["security".to_string()],
+            ..Default::default()
+        });
+        let delegate = |valid_until| crate::commands::DelegateApproval {
+            identity: create_message_identity(),
+            policy_id: policy.id,
+            from: "ciso".to_string(),
+            to: "deputy".to_string(),
+            role: Some("security".to_string()),
+            valid_until,
+        };
+        let now = Utc::now();
+
+        assert!(policy.delegate_approval(&delegate(now), now).is_err());
+
+        let event = policy
+            .delegate_approval(&delegate(now + chrono::Duration::days(7)), now)
+            .unwrap();
+        assert!(matches!(event, PolicyEvent::PolicyApprovalDelegated(_)));
+        let delegated = policy.apply_event_pure(&event).unwrap();
+        assert_eq!(delegated.approval_requirements.delegations.len(), 1);
+
+        // The deputy approves without a role of their own
+        let (approved, _) = approve(delegated, "deputy", None, false);
+        assert_eq!(approved.status, PolicyStatus::Approved);
+
+        // Once the delegation has ended the deputy no longer covers the role
+        let expired = policy
+            .apply_event_pure(&PolicyEvent::PolicyApprovalDelegated(PolicyApprovalDelegated {
+                event_id: Uuid::now_v7(),
+                identity: create_message_identity(),
+                policy_id: policy.id,
+                from: "ciso".to_string(),
+                to: "deputy".to_string(),
+                role: Some("security".to_string()),
+                valid_until: now - chrono::Duration::days(1),
+                delegated_at: now - chrono::Duration::days(8),
+            }))
+            .unwrap();
+        let (pending, _) = approve(expired, "deputy", None, false);
+        assert_eq!(pending.status, PolicyStatus::Draft);
+        assert_eq!(
+            pending.approval_requirements.outstanding(&pending.approvals),
+            vec!["approval from role security".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_default_requirements_approve_on_first_approval() {
+        let policy = policy_requiring(ApprovalRequirements::default());
+
+        let (policy, events) = approve(policy, "alice", None, false);
+        assert_eq!(events.len(), 2);
+        assert_eq!(policy.status, PolicyStatus::Approved);
+    }
+
+    #[test]
+    fn test_approval_follows_the_transition_table() {
+        for status in [PolicyStatus::Draft, PolicyStatus::UnderReview] {
+            let mut policy = policy_requiring(ApprovalRequirements::default());
+            policy.status = status;
+            let (policy, _) = approve(policy, "alice", None, false);
+            assert_eq!(policy.status, PolicyStatus::Approved);
+        }
+
+        for status in [
+            PolicyStatus::Approved,
+            PolicyStatus::Active,
+            PolicyStatus::Suspended,
+            PolicyStatus::Revoked,
+            PolicyStatus::Archived,
+        ] {
+            let mut policy = policy_requiring(ApprovalRequirements::default());
+            policy.status = status;
+            let command = approve_command(&policy, "alice", None, false);
+            assert!(policy.approve(&command, Utc::now()).is_err(), "{:?}", status);
+        }
+    }
+
+    #[test]
+    fn test_external_approval_requested_once() {
+        let policy = policy_requiring(ApprovalRequirements {
+            min_approvals: 1,
+            requires_external_approval: true,
+            ..Default::default()
+        });
+
+        let (policy, events) = approve(policy, "alice", None, false);
+        assert!(matches!(events.last(), Some(PolicyEvent::PolicyExternalApprovalRequested(_))));
+        assert_eq!(policy.pending_external_approvals.len(), 1);
+        assert_eq!(policy.status, PolicyStatus::Draft);
+
+        let (policy, events) = approve(policy, "bob", None, false);
+        assert_eq!(events.len(), 1);
+        assert_eq!(policy.status, PolicyStatus::Draft);
+
+        let (policy, events) = approve(policy, "auditor", None, true);
+        assert!(matches!(events.last(), Some(PolicyEvent::PolicyApproved(_))));
+        assert_eq!(policy.status, PolicyStatus::Approved);
+    }
+
+    #[test]
+    fn test_recorded_approval_is_an_event() {
+        let policy = policy_requiring(ApprovalRequirements::default());
+
+        let event = policy.record_approval("alice", Some("ciso".to_string()), Utc::now()).unwrap();
+        assert!(policy.approvals.is_empty());
+        let policy = policy.apply_event_pure(&event).unwrap();
+        assert_eq!(policy.approvals.len(), 1);
+        assert_eq!(policy.approvals[0].role.as_deref(), Some("ciso"));
+
+        assert!(policy.record_approval("alice", None, Utc::now()).is_err());
+    }
+
+    #[test]
+    fn test_yubikey_external_approval_cycle() {
+        let policy = policy_requiring(ApprovalRequirements {
+            requires_external_approval: true,
+            ..Default::default()
+        });
+        let (policy, _) = approve(policy, "alice", None, false);
+        let request_id = policy.pending_external_approvals[0].request_id;
+
+        let unknown = policy.record_external_verification(Uuid::now_v7(), ExternalVerification {
+            verifier: "auditor".to_string(),
+            method: "yubikey".to_string(),
+            verified_at: Utc::now(),
+        });
+        assert!(matches!(unknown, Err(crate::PolicyError::UnknownApprovalRequest(_))));
+
+        let event = policy.record_external_verification(request_id, ExternalVerification {
+            verifier: "auditor".to_string(),
+            method: "yubikey".to_string(),
+            verified_at: Utc::now(),
+        }).unwrap();
+        assert!(matches!(
+            &event,
+            PolicyEvent::PolicyExternalApprovalVerified(e) if e.request_id == request_id
+        ));
+        let policy = policy.apply_event_pure(&event).unwrap();
+
+        assert!(policy.pending_external_approvals.is_empty());
+        assert!(!policy.approval_requirements.needs_external_approval(&policy.approvals));
+        assert!(policy.approval_requirements.outstanding(&policy.approvals).is_empty());
+    }
+
+    #[test]
+    fn test_unanswered_external_approval_expires() {
+        let policy = policy_requiring(ApprovalRequirements {
+            requires_external_approval: true,
+            external_approval_timeout_hours: 24,
+            ..Default::default()
+        });
+        let (policy, _) = approve(policy, "alice", None, false);
+        let request_id = policy.pending_external_approvals[0].request_id;
+
+        assert!(policy.expire_stale_external_approvals(Utc::now()).is_empty());
+
+        let expired = policy.expire_stale_external_approvals(Utc::now() + chrono::Duration::hours(25));
+        assert!(matches!(
+            expired.as_slice(),
+            [PolicyEvent::PolicyExternalApprovalExpired(e)] if e.request_id == request_id
+        ));
+        let policy = expired
+            .iter()
+            .try_fold(policy, |policy, event| policy.apply_event_pure(event))
+            .unwrap();
+        assert!(policy.pending_external_approvals.is_empty());
+        assert!(policy.record_external_verification(request_id, ExternalVerification {
+            verifier: "auditor".to_string(),
+            method: "yubikey".to_string(),
+            verified_at: Utc::now(),
+        }).is_err());
+    }
+
+    fn renew_command(exemption: &PolicyExemption, new_valid_until: DateTime<Utc>) -> crate::commands::RenewExemption {
+        crate::commands::RenewExemption {
+            identity: create_message_identity(),
+            exemption_id: exemption.id,
+            new_valid_until,
+            justification: "Migration delayed".to_string(),
+            renewed_by: "security-admin".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_exemption_renewal_extends_window() {
+        let now = Utc::now();
+        let exemption = PolicyExemption::new(
+            PolicyId::new(),
+            "Legacy HSM",
+            "Replacement on order",
+            "security-admin",
+            now + chrono::Duration::days(5),
+        );
+        let approved_at = exemption.approved_at;
+        let new_valid_until = now + chrono::Duration::days(60);
+
+        let event = exemption.renew(&renew_command(&exemption, new_valid_until), now).unwrap();
+        let renewed = exemption.apply_event_pure(&event).unwrap();
+
+        assert_eq!(renewed.valid_until, new_valid_until);
+        assert_eq!(renewed.approved_at, approved_at);
+        assert_eq!(renewed.renewals.len(), 1);
+        assert_eq!(renewed.renewals[0].previous_valid_until, exemption.valid_until);
+        assert!(renewed.is_valid_at(now + chrono::Duration::days(30)));
+
+        // A renewal must extend the current window
+        assert!(renewed.renew(&renew_command(&renewed, now + chrono::Duration::days(10)), now).is_err());
+    }
+
+    #[test]
+    fn test_revoked_exemption_cannot_be_renewed() {
+        let mut exemption = PolicyExemption::new(
+            PolicyId::new(),
+            "Legacy HSM",
+            "Replacement on order",
+            "security-admin",
+            Utc::now() + chrono::Duration::days(5),
+        );
+        exemption.status = ExemptionStatus::Revoked {
+            revoked_by: "admin".to_string(),
+            revoked_at: Utc::now(),
+            reason: "Risk no longer accepted".to_string(),
+        };
+
+        let command = renew_command(&exemption, Utc::now() + chrono::Duration::days(60));
+        assert!(exemption.renew(&command, Utc::now()).is_err());
+    }
+
+    fn add_command(set: &PolicySet, policy: &Policy) -> crate::commands::AddPolicyToSet {
+        crate::commands::AddPolicyToSet {
+            identity: create_message_identity(),
+            policy_set_id: set.id,
+            policy_id: policy.id,
+            added_by: "admin".to_string(),
+        }
+    }
+
+    fn key_size_policy(name: &str, bits: i64) -> Policy {
+        let mut policy = Policy::new(name, "Key size requirements");
+        policy.status = PolicyStatus::Active;
+        policy.add_rule(crate::entities::PolicyRule::new(
+            "Key Size",
+            format!("Key size must be exactly {} bits", bits),
+            RuleExpression::Equal { field: "key_size".to_string(), value: Value::Integer(bits) },
+            Severity::High,
+        ));
+        policy
+    }
+
+    #[test]
+    fn test_add_to_set_rejects_archived_and_duplicate_policies() {
+        let mut set = PolicySet::new("Keys", "Key policies");
+        let mut archived = key_size_policy("Old", 1024);
+        archived.status = PolicyStatus::Archived;
+
+        assert!(set.add_policy_checked(&archived).is_err());
+        assert!(set.add_member(&add_command(&set, &archived), &archived, &[], Utc::now()).is_err());
+
+        let active = key_size_policy("Current", 2048);
+        set.add_policy_checked(&active).unwrap();
+        assert!(set.add_policy_checked(&active).is_err());
+        assert_eq!(set.policies, vec![active.id]);
+    }
+
+    #[test]
+    fn test_add_to_set_detects_conflict_with_members() {
+        let mut set = PolicySet::new("Keys", "Key policies");
+        let existing = key_size_policy("Current", 2048);
+        set.add_policy_checked(&existing).unwrap();
+
+        let conflicting = key_size_policy("Stricter", 4096);
+        let events = set
+            .add_member(&add_command(&set, &conflicting), &conflicting, std::slice::from_ref(&existing), Utc::now())
+            .unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert!(matches!(&events[0], PolicyEvent::PolicyAddedToSet(e) if e.policy_id == conflicting.id));
+        match &events[1] {
+            PolicyEvent::PolicyConflictDetected(e) => {
+                assert_eq!(e.policy_ids, vec![existing.id, conflicting.id]);
+                assert_eq!(e.conflict_type, "Contradiction");
+            }
+            other => panic!("expected conflict, got {:?}", other),
+        }
+
+        // A compatible policy joins without conflicts
+        let compatible = key_size_policy("Also current", 2048);
+        let events = set
+            .add_member(&add_command(&set, &compatible), &compatible, &[existing], Utc::now())
+            .unwrap();
+        assert_eq!(events.len(), 1);
+    }
+
+    #[test]
+    fn test_effective_rules_resolved_per_strategy_and_attributed() {
+        use crate::entities::PolicyRule;
+        use crate::services::PolicyConflictResolver;
+
+        let mut lenient = key_size_policy("Lenient", 2048);
+        lenient.rules[0].severity = Severity::Medium;
+        lenient.add_rule(PolicyRule::max_validity_days(365));
+        let strict = key_size_policy("Strict", 4096);
+
+        let mut set = PolicySet::new("Keys", "Key policies");
+        set.add_policy(lenient.id);
+        set.add_policy(strict.id);
+        let resolver = PolicyConflictResolver::new(ConflictResolution::FailOnConflict);
+        let key_size = |rules: &[(PolicyId, PolicyRule)]| {
+            rules.iter().filter(|(_, r)| r.name == "Key Size").cloned().collect::<Vec<_>>()
+        };
+
+        set.conflict_resolution = ConflictResolution::MostRestrictive;
+        let rules = set.effective_rules(&[&strict, &lenient], &resolver);
+        assert_eq!(rules.len(), 2);
+        let kept = key_size(&rules);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].0, strict.id);
+        assert_eq!(kept[0].1.severity, Severity::High);
+        assert!(rules.iter().any(|(id, r)| *id == lenient.id && r.name == "Maximum Validity Period"));
+
+        set.conflict_resolution = ConflictResolution::FirstWins;
+        let rules = set.effective_rules(&[&strict, &lenient], &resolver);
+        let kept = key_size(&rules);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].0, lenient.id);
+    }
+
+    #[test]
+    fn test_service_approvals_alone_do_not_satisfy_human_minimum() {
+        let policy = policy_requiring(ApprovalRequirements {
+            min_approvals: 1,
+            min_human_approvals: 1,
+            min_service_approvals: 1,
+            ..Default::default()
+        });
+        let approve_as = |policy: Policy, approver: &str, kind: ApproverKind| {
+            let mut command = approve_command(&policy, approver, None, false);
+            command.approver_kind = Some(kind);
+            let events = policy.approve(&command, Utc::now()).unwrap();
+            events.iter().fold(policy, |p, e| p.apply_event_pure(e).unwrap())
+        };
+
+        // Two CI systems sign off, but no human has
+        let policy = approve_as(policy, "ci", ApproverKind::Service(Uuid::now_v7()));
+        let policy = approve_as(policy, "scanner", ApproverKind::Service(Uuid::now_v7()));
+        assert_eq!(policy.status, PolicyStatus::Draft);
+        assert_eq!(
+            policy.approval_requirements.outstanding(&policy.approvals),
+            vec!["0 of 1 human approvals recorded".to_string()]
+        );
+
+        let policy = approve_as(policy, "alice", ApproverKind::Human(Uuid::now_v7()));
+        assert_eq!(policy.status, PolicyStatus::Approved);
+    }
 }
@@ -16,10 +16,12 @@
 //! - `policy.commands.create` - Create new policy
 //! - `policy.commands.update` - Update policy
 //! - `policy.commands.approve` - Approve policy
+//! - `policy.commands.delegate_approval` - Delegate an approver's approval
 //! - `policy.commands.activate` - Activate policy
 //! - `policy.commands.suspend` - Suspend policy
 //! - `policy.commands.revoke` - Revoke policy
 //! - `policy.commands.archive` - Archive policy
+//! - `policy.commands.batch` - Apply approve/activate/suspend to many policies
 //! - `policy.commands.create_set` - Create policy set
 //! - `policy.commands.add_to_set` - Add policy to set
 //! - `policy.commands.remove_from_set` - Remove policy from set
@@ -28,18 +30,56 @@
 //! - `policy.commands.evaluate` - Evaluate policy
 //! - `policy.commands.check_compliance` - Check compliance
 //!
+//! Queries (request/reply, answered from the read-model projection):
+//! - `policy.queries` - `{"query": "policy", "policy_id": ...}`,
+//!   `{"query": "policies_with_status", "status": ...}` or
+//!   `{"query": "active_exemptions", "policy_id": ...}`
+//!
+//! Health (request/reply):
+//! - `policy.health` - NATS connectivity and event store reachability
+//!
+//! Every minute, active exemptions past their `valid_until` are expired by
+//! recording `PolicyExemptionExpired`.
+//!
+//! Evaluations apply the policy's active exemptions. Each use of a
+//! usage-limited exemption is recorded as `PolicyExemptionUsed`, appended
+//! only if the exemption's stream hasn't moved since it was loaded, so its
+//! limit holds across restarts and concurrent service instances.
+//!
+//! On shutdown the service stops taking new commands, waits up to 30 seconds
+//! for in-flight commands to finish, then drains the NATS connection.
+//!
 //! Events (publish):
 //! - `events.policy.{policy_id}.{event_type}` - Policy domain events
 
 use cim_domain_policy::adapters::NatsEventPublisher;
+use cim_domain_policy::commands::{
+    ActivatePolicy, AddPolicyToSet, ApprovePolicy, BatchPolicyCommand, DelegateApproval,
+    EvaluatePolicy, SuspendPolicy,
+};
+use cim_domain_policy::value_objects::{ExemptionId, PolicyId, PolicySetId};
+use cim_domain_policy::{
+    ComplianceResult, EvaluationContext, Policy, PolicyError, PolicyEvaluation, PolicyEvent,
+    PolicyExemption, PolicyStatus,
+};
 use cim_domain_policy::infrastructure::{
-    ExemptionRepository, NatsEventStore, PolicyRepository, PolicySetRepository,
+    exemption_repository, policy_repository, policy_set_repository, ExemptionRepository, NatsError,
+    NatsEventStore, PolicyRepository, PolicySetRepository, ProjectionConsumer,
 };
 use cim_domain_policy::ports::EventPublisher;
+use cim_domain_policy::projections::PolicyProjection;
+use cim_domain_policy::services::{EvaluationError, ExemptionSweeper, PolicyEvaluator};
 use futures::StreamExt;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::env;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::signal;
+use tokio::sync::{broadcast, RwLock};
+use tokio::task::JoinSet;
 use tracing::{error, info, warn};
 
 #[tokio::main]
@@ -95,6 +135,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         ExemptionRepository::new(event_store.clone()).with_snapshot_frequency(snapshot_frequency),
     );
 
+    // Keep read-model projections up to date from the event stream
+    let projection_consumer = ProjectionConsumer::new(event_store.clone(), "policy-projections");
+    let projection = projection_consumer.projection();
+    tokio::spawn(async move {
+        if let Err(e) = projection_consumer.run().await {
+            error!("Projection consumer stopped: {}", e);
+        }
+    });
+
     // Create event publisher
     let publisher = Arc::new(NatsEventPublisher::new(jetstream.clone(), stream_name.clone()));
 
@@ -102,41 +151,43 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("Subscribing to command subjects...");
 
     // Policy commands
-    let mut create_sub = client.subscribe("policy.commands.create").await?;
-    let mut update_sub = client.subscribe("policy.commands.update").await?;
-    let mut approve_sub = client.subscribe("policy.commands.approve").await?;
-    let mut activate_sub = client.subscribe("policy.commands.activate").await?;
-    let mut suspend_sub = client.subscribe("policy.commands.suspend").await?;
-    let mut revoke_sub = client.subscribe("policy.commands.revoke").await?;
-    let mut archive_sub = client.subscribe("policy.commands.archive").await?;
+    let create_sub = client.subscribe("policy.commands.create").await?;
+    let update_sub = client.subscribe("policy.commands.update").await?;
+    let approve_sub = client.subscribe("policy.commands.approve").await?;
+    let delegate_sub = client.subscribe("policy.commands.delegate_approval").await?;
+    let activate_sub = client.subscribe("policy.commands.activate").await?;
+    let suspend_sub = client.subscribe("policy.commands.suspend").await?;
+    let revoke_sub = client.subscribe("policy.commands.revoke").await?;
+    let archive_sub = client.subscribe("policy.commands.archive").await?;
+    let batch_sub = client.subscribe("policy.commands.batch").await?;
 
     // PolicySet commands
-    let mut create_set_sub = client.subscribe("policy.commands.create_set").await?;
-    let mut add_to_set_sub = client.subscribe("policy.commands.add_to_set").await?;
-    let mut remove_from_set_sub = client.subscribe("policy.commands.remove_from_set").await?;
+    let create_set_sub = client.subscribe("policy.commands.create_set").await?;
+    let add_to_set_sub = client.subscribe("policy.commands.add_to_set").await?;
+    let remove_from_set_sub = client.subscribe("policy.commands.remove_from_set").await?;
 
     // Exemption commands
-    let mut grant_exemption_sub = client.subscribe("policy.commands.grant_exemption").await?;
-    let mut revoke_exemption_sub = client.subscribe("policy.commands.revoke_exemption").await?;
+    let grant_exemption_sub = client.subscribe("policy.commands.grant_exemption").await?;
+    let revoke_exemption_sub = client.subscribe("policy.commands.revoke_exemption").await?;
 
     // Evaluation commands
-    let mut evaluate_sub = client.subscribe("policy.commands.evaluate").await?;
-    let mut check_compliance_sub = client.subscribe("policy.commands.check_compliance").await?;
+    let evaluate_sub = client.subscribe("policy.commands.evaluate").await?;
+    let check_compliance_sub = client.subscribe("policy.commands.check_compliance").await?;
 
     info!("Subscribed to all command subjects");
 
     // Spawn handler tasks
     info!("Starting command handlers...");
+    let mut handlers = JoinSet::new();
+    let (shutdown, _) = broadcast::channel::<()>(1);
 
     // Policy command handlers
     {
         let repo = policy_repo.clone();
         let pub_ref = publisher.clone();
         let client_ref = client.clone();
-        tokio::spawn(async move {
-            while let Some(msg) = create_sub.next().await {
-                handle_create_policy(msg, repo.clone(), pub_ref.clone(), client_ref.clone()).await;
-            }
+        spawn_handler(&mut handlers, create_sub, shutdown.subscribe(), move |msg| {
+            handle_create_policy(msg, repo.clone(), pub_ref.clone(), client_ref.clone())
         });
     }
 
@@ -144,10 +195,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let repo = policy_repo.clone();
         let pub_ref = publisher.clone();
         let client_ref = client.clone();
-        tokio::spawn(async move {
-            while let Some(msg) = update_sub.next().await {
-                handle_update_policy(msg, repo.clone(), pub_ref.clone(), client_ref.clone()).await;
-            }
+        spawn_handler(&mut handlers, update_sub, shutdown.subscribe(), move |msg| {
+            handle_update_policy(msg, repo.clone(), pub_ref.clone(), client_ref.clone())
         });
     }
 
@@ -155,10 +204,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let repo = policy_repo.clone();
         let pub_ref = publisher.clone();
         let client_ref = client.clone();
-        tokio::spawn(async move {
-            while let Some(msg) = approve_sub.next().await {
-                handle_approve_policy(msg, repo.clone(), pub_ref.clone(), client_ref.clone()).await;
-            }
+        spawn_handler(&mut handlers, approve_sub, shutdown.subscribe(), move |msg| {
+            handle_approve_policy(msg, repo.clone(), pub_ref.clone(), client_ref.clone())
+        });
+    }
+
+    {
+        let repo = policy_repo.clone();
+        let client_ref = client.clone();
+        spawn_handler(&mut handlers, delegate_sub, shutdown.subscribe(), move |msg| {
+            handle_delegate_approval(msg, repo.clone(), client_ref.clone())
         });
     }
 
@@ -166,10 +221,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let repo = policy_repo.clone();
         let pub_ref = publisher.clone();
         let client_ref = client.clone();
-        tokio::spawn(async move {
-            while let Some(msg) = activate_sub.next().await {
-                handle_activate_policy(msg, repo.clone(), pub_ref.clone(), client_ref.clone()).await;
-            }
+        spawn_handler(&mut handlers, activate_sub, shutdown.subscribe(), move |msg| {
+            handle_activate_policy(msg, repo.clone(), pub_ref.clone(), client_ref.clone())
         });
     }
 
@@ -177,10 +230,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let repo = policy_repo.clone();
         let pub_ref = publisher.clone();
         let client_ref = client.clone();
-        tokio::spawn(async move {
-            while let Some(msg) = suspend_sub.next().await {
-                handle_suspend_policy(msg, repo.clone(), pub_ref.clone(), client_ref.clone()).await;
-            }
+        spawn_handler(&mut handlers, suspend_sub, shutdown.subscribe(), move |msg| {
+            handle_suspend_policy(msg, repo.clone(), pub_ref.clone(), client_ref.clone())
         });
     }
 
@@ -188,10 +239,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let repo = policy_repo.clone();
         let pub_ref = publisher.clone();
         let client_ref = client.clone();
-        tokio::spawn(async move {
-            while let Some(msg) = revoke_sub.next().await {
-                handle_revoke_policy(msg, repo.clone(), pub_ref.clone(), client_ref.clone()).await;
-            }
+        spawn_handler(&mut handlers, revoke_sub, shutdown.subscribe(), move |msg| {
+            handle_revoke_policy(msg, repo.clone(), pub_ref.clone(), client_ref.clone())
         });
     }
 
@@ -199,10 +248,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let repo = policy_repo.clone();
         let pub_ref = publisher.clone();
         let client_ref = client.clone();
-        tokio::spawn(async move {
-            while let Some(msg) = archive_sub.next().await {
-                handle_archive_policy(msg, repo.clone(), pub_ref.clone(), client_ref.clone()).await;
-            }
+        spawn_handler(&mut handlers, archive_sub, shutdown.subscribe(), move |msg| {
+            handle_archive_policy(msg, repo.clone(), pub_ref.clone(), client_ref.clone())
+        });
+    }
+
+    {
+        let repo = policy_repo.clone();
+        let client_ref = client.clone();
+        spawn_handler(&mut handlers, batch_sub, shutdown.subscribe(), move |msg| {
+            handle_batch(msg, repo.clone(), client_ref.clone())
         });
     }
 
@@ -211,21 +266,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let repo = policy_set_repo.clone();
         let pub_ref = publisher.clone();
         let client_ref = client.clone();
-        tokio::spawn(async move {
-            while let Some(msg) = create_set_sub.next().await {
-                handle_create_set(msg, repo.clone(), pub_ref.clone(), client_ref.clone()).await;
-            }
+        spawn_handler(&mut handlers, create_set_sub, shutdown.subscribe(), move |msg| {
+            handle_create_set(msg, repo.clone(), pub_ref.clone(), client_ref.clone())
         });
     }
 
     {
         let repo = policy_set_repo.clone();
+        let policies = policy_repo.clone();
         let pub_ref = publisher.clone();
         let client_ref = client.clone();
-        tokio::spawn(async move {
-            while let Some(msg) = add_to_set_sub.next().await {
-                handle_add_to_set(msg, repo.clone(), pub_ref.clone(), client_ref.clone()).await;
-            }
+        spawn_handler(&mut handlers, add_to_set_sub, shutdown.subscribe(), move |msg| {
+            handle_add_to_set(msg, repo.clone(), policies.clone(), pub_ref.clone(), client_ref.clone())
         });
     }
 
@@ -233,10 +285,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let repo = policy_set_repo.clone();
         let pub_ref = publisher.clone();
         let client_ref = client.clone();
-        tokio::spawn(async move {
-            while let Some(msg) = remove_from_set_sub.next().await {
-                handle_remove_from_set(msg, repo.clone(), pub_ref.clone(), client_ref.clone()).await;
-            }
+        spawn_handler(&mut handlers, remove_from_set_sub, shutdown.subscribe(), move |msg| {
+            handle_remove_from_set(msg, repo.clone(), pub_ref.clone(), client_ref.clone())
         });
     }
 
@@ -245,10 +295,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let repo = exemption_repo.clone();
         let pub_ref = publisher.clone();
         let client_ref = client.clone();
-        tokio::spawn(async move {
-            while let Some(msg) = grant_exemption_sub.next().await {
-                handle_grant_exemption(msg, repo.clone(), pub_ref.clone(), client_ref.clone()).await;
-            }
+        spawn_handler(&mut handlers, grant_exemption_sub, shutdown.subscribe(), move |msg| {
+            handle_grant_exemption(msg, repo.clone(), pub_ref.clone(), client_ref.clone())
         });
     }
 
@@ -256,32 +304,83 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let repo = exemption_repo.clone();
         let pub_ref = publisher.clone();
         let client_ref = client.clone();
-        tokio::spawn(async move {
-            while let Some(msg) = revoke_exemption_sub.next().await {
-                handle_revoke_exemption(msg, repo.clone(), pub_ref.clone(), client_ref.clone()).await;
-            }
+        spawn_handler(&mut handlers, revoke_exemption_sub, shutdown.subscribe(), move |msg| {
+            handle_revoke_exemption(msg, repo.clone(), pub_ref.clone(), client_ref.clone())
         });
     }
 
     // Evaluation command handlers
+    {
+        let repo = policy_repo.clone();
+        let exemptions = exemption_repo.clone();
+        let projection = projection.clone();
+        let client_ref = client.clone();
+        spawn_handler(&mut handlers, evaluate_sub, shutdown.subscribe(), move |msg| {
+            handle_evaluate(
+                msg,
+                repo.clone(),
+                exemptions.clone(),
+                projection.clone(),
+                client_ref.clone(),
+            )
+        });
+    }
+
     {
         let repo = policy_repo.clone();
         let pub_ref = publisher.clone();
         let client_ref = client.clone();
+        spawn_handler(&mut handlers, check_compliance_sub, shutdown.subscribe(), move |msg| {
+            handle_check_compliance(msg, repo.clone(), pub_ref.clone(), client_ref.clone())
+        });
+    }
+
+    // Projection queries
+    {
+        let mut query_sub = client.subscribe("policy.queries").await?;
+        let client_ref = client.clone();
+        let projection = projection.clone();
         tokio::spawn(async move {
-            while let Some(msg) = evaluate_sub.next().await {
-                handle_evaluate(msg, repo.clone(), pub_ref.clone(), client_ref.clone()).await;
+            while let Some(msg) = query_sub.next().await {
+                let response = {
+                    let projection = projection.read().await;
+                    query_projection(&msg.payload, &projection).unwrap_or_else(|e| e.to_reply())
+                };
+                if let Some(reply) = msg.reply {
+                    respond(&client_ref, reply, &response).await;
+                }
             }
         });
     }
 
+    // Health checks
     {
-        let repo = policy_repo.clone();
-        let pub_ref = publisher.clone();
+        let mut health_sub = client.subscribe("policy.health").await?;
         let client_ref = client.clone();
+        let store = event_store.clone();
         tokio::spawn(async move {
-            while let Some(msg) = check_compliance_sub.next().await {
-                handle_check_compliance(msg, repo.clone(), pub_ref.clone(), client_ref.clone()).await;
+            while let Some(msg) = health_sub.next().await {
+                let store_check = store.check_reachable().await.map_err(|e| e.to_string());
+                let report = health_report(client_ref.connection_state(), store_check);
+                if let Some(reply) = msg.reply {
+                    respond(&client_ref, reply, &report).await;
+                }
+            }
+        });
+    }
+
+    // Exemption expiry sweeps
+    {
+        let projection = projection.clone();
+        let repo = exemption_repo.clone();
+        let mut shutdown_rx = shutdown.subscribe();
+        handlers.spawn(async move {
+            let mut interval = tokio::time::interval(EXEMPTION_SWEEP_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = shutdown_rx.recv() => break,
+                    _ = interval.tick() => sweep_exemptions(&projection, &repo).await,
+                }
             }
         });
     }
@@ -292,9 +391,330 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     signal::ctrl_c().await?;
     info!("Shutting down Policy Service...");
 
+    let drain_client = client.clone();
+    let completed = shutdown_gracefully(shutdown, handlers, SHUTDOWN_TIMEOUT, async move {
+        if let Err(e) = drain_client.drain().await {
+            warn!("Failed to drain NATS connection: {}", e);
+        }
+    })
+    .await;
+    if !completed {
+        warn!("Handlers did not finish within {:?}; aborted", SHUTDOWN_TIMEOUT);
+    }
+    info!("Policy Service stopped");
+
     Ok(())
 }
 
+/// How long shutdown waits for in-flight commands before aborting them
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often lapsed exemptions are swept
+const EXEMPTION_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Record expiry for active exemptions past their validity
+///
+/// Candidates come from the projection; each is reloaded from its event
+/// stream so the sweep decides on the authoritative state.
+async fn sweep_exemptions(projection: &RwLock<PolicyProjection>, repository: &ExemptionRepository) {
+    let now = chrono::Utc::now();
+    let candidates: Vec<_> = projection
+        .read()
+        .await
+        .lapsed_exemptions(now)
+        .into_iter()
+        .map(|view| view.exemption_id)
+        .collect();
+
+    let mut exemptions = Vec::new();
+    for exemption_id in candidates {
+        match repository.load(exemption_id).await {
+            Ok(Some(exemption)) => exemptions.push(exemption),
+            Ok(None) => {}
+            Err(e) => warn!("Failed to load exemption {}: {}", exemption_id.0, e),
+        }
+    }
+
+    for event in ExemptionSweeper::new().sweep(&exemptions, now) {
+        let exemption_id = event.exemption_id;
+        match repository.save(vec![PolicyEvent::PolicyExemptionExpired(event)]).await {
+            Ok(()) => info!("Expired exemption {}", exemption_id.0),
+            Err(e) => warn!("Failed to expire exemption {}: {}", exemption_id.0, e),
+        }
+    }
+}
+
+/// Run a handler for every message on a subscription until shutdown
+///
+/// A message being handled when shutdown is signalled is finished first;
+/// afterwards the subscription is dropped so no new messages are accepted.
+fn spawn_handler<F, Fut>(
+    handlers: &mut JoinSet<()>,
+    mut subscription: async_nats::Subscriber,
+    mut shutdown: broadcast::Receiver<()>,
+    handler: F,
+) where
+    F: Fn(async_nats::Message) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    handlers.spawn(async move {
+        loop {
+            tokio::select! {
+                _ = shutdown.recv() => break,
+                msg = subscription.next() => match msg {
+                    Some(msg) => handler(msg).await,
+                    None => break,
+                },
+            }
+        }
+        if let Err(e) = subscription.unsubscribe().await {
+            warn!("Failed to unsubscribe: {}", e);
+        }
+    });
+}
+
+/// Stop accepting messages, wait for in-flight handlers, then drain
+///
+/// Handlers still running after `timeout` are aborted. `drain` runs last in
+/// either case. Returns whether every handler finished in time.
+async fn shutdown_gracefully(
+    shutdown: broadcast::Sender<()>,
+    mut handlers: JoinSet<()>,
+    timeout: Duration,
+    drain: impl Future<Output = ()>,
+) -> bool {
+    let _ = shutdown.send(());
+
+    let completed = tokio::time::timeout(timeout, async {
+        while handlers.join_next().await.is_some() {}
+    })
+    .await
+    .is_ok();
+    if !completed {
+        handlers.shutdown().await;
+    }
+
+    drain.await;
+    completed
+}
+
+/// Query accepted on the `policy.queries` subject
+#[derive(Debug, Deserialize)]
+#[serde(tag = "query", rename_all = "snake_case")]
+enum ProjectionQuery {
+    Policy { policy_id: PolicyId },
+    PoliciesWithStatus { status: PolicyStatus },
+    ActiveExemptions { policy_id: PolicyId },
+}
+
+/// Answer a query from the read-model projection
+fn query_projection(
+    payload: &[u8],
+    projection: &PolicyProjection,
+) -> Result<serde_json::Value, PolicyServiceError> {
+    let query: ProjectionQuery = parse_command(payload)?;
+
+    let response = match query {
+        ProjectionQuery::Policy { policy_id } => serde_json::to_value(
+            projection
+                .policy(&policy_id)
+                .ok_or(PolicyError::PolicyNotFound(policy_id.0))?,
+        ),
+        ProjectionQuery::PoliciesWithStatus { status } => {
+            serde_json::to_value(projection.policies_with_status(status))
+        }
+        ProjectionQuery::ActiveExemptions { policy_id } => {
+            serde_json::to_value(projection.active_exemptions_for(&policy_id))
+        }
+    };
+
+    response.map_err(|e| PolicyServiceError::internal(e.to_string()))
+}
+
+/// Health report for the `policy.health` subject
+fn health_report(
+    nats: async_nats::connection::State,
+    event_store: Result<(), String>,
+) -> serde_json::Value {
+    let nats_connected = nats == async_nats::connection::State::Connected;
+    let healthy = nats_connected && event_store.is_ok();
+
+    serde_json::json!({
+        "status": if healthy { "ok" } else { "unavailable" },
+        "nats_connected": nats_connected,
+        "event_store_reachable": event_store.is_ok(),
+        "event_store_error": event_store.err(),
+    })
+}
+
+/// Send a JSON reply, logging instead of failing if it cannot be sent
+async fn respond(client: &async_nats::Client, reply: async_nats::Subject, response: &serde_json::Value) {
+    match serde_json::to_vec(response) {
+        Ok(payload) => {
+            if let Err(e) = client.publish(reply, payload.into()).await {
+                warn!("Failed to send reply: {}", e);
+            }
+        }
+        Err(e) => error!("Failed to serialize reply: {}", e),
+    }
+}
+
+// ============================================================================
+// Error Replies
+// ============================================================================
+
+/// Error envelope sent in reply to a command that could not be handled
+///
+/// `code` is stable and intended for clients to match on; `message` is for
+/// humans; `details` carries structured context where there is some.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct PolicyServiceError {
+    code: &'static str,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<serde_json::Value>,
+}
+
+impl PolicyServiceError {
+    fn new(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    fn internal(message: impl Into<String>) -> Self {
+        Self::new("INTERNAL_ERROR", message)
+    }
+
+    fn unsupported_command(command_type: &str) -> Self {
+        Self::new(
+            "UNSUPPORTED_COMMAND",
+            format!("Unsupported batch command type: {}", command_type),
+        )
+    }
+
+    fn policy_set_not_found(policy_set_id: PolicySetId) -> Self {
+        Self::new(
+            "POLICY_SET_NOT_FOUND",
+            format!("Policy set not found: {}", policy_set_id.0),
+        )
+        .with_details(serde_json::json!({ "policy_set_id": policy_set_id }))
+    }
+
+    /// Reply body for this error
+    ///
+    /// `status` and `message` stay at the top level for clients that predate
+    /// the envelope.
+    fn to_reply(&self) -> serde_json::Value {
+        serde_json::json!({
+            "status": "error",
+            "message": self.message,
+            "error": self,
+        })
+    }
+}
+
+impl std::fmt::Display for PolicyServiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for PolicyServiceError {}
+
+impl From<serde_json::Error> for PolicyServiceError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::new("INVALID_PAYLOAD", format!("Invalid command payload: {}", err)).with_details(
+            serde_json::json!({ "line": err.line(), "column": err.column() }),
+        )
+    }
+}
+
+impl From<PolicyError> for PolicyServiceError {
+    fn from(err: PolicyError) -> Self {
+        let error = Self::new(err.code(), err.to_string());
+        match err {
+            PolicyError::PolicyNotFound(id) | PolicyError::UnknownApprovalRequest(id) => {
+                error.with_details(serde_json::json!({ "id": id }))
+            }
+            _ => error,
+        }
+    }
+}
+
+impl From<NatsError> for PolicyServiceError {
+    fn from(err: NatsError) -> Self {
+        Self::new("EVENT_STORE_UNAVAILABLE", err.to_string())
+    }
+}
+
+impl From<policy_repository::RepositoryError> for PolicyServiceError {
+    fn from(err: policy_repository::RepositoryError) -> Self {
+        use policy_repository::RepositoryError;
+        match err {
+            RepositoryError::Nats(e) => e.into(),
+            RepositoryError::Policy(e) => e.into(),
+            RepositoryError::InvalidSequence(message) => Self::new("CORRUPT_EVENT_STREAM", message),
+            RepositoryError::ConcurrencyConflict { expected, actual } => Self::new(
+                "CONCURRENCY_CONFLICT",
+                "Policy was modified concurrently; reload and retry",
+            )
+            .with_details(serde_json::json!({ "expected": expected, "actual": actual })),
+        }
+    }
+}
+
+impl From<exemption_repository::RepositoryError> for PolicyServiceError {
+    fn from(err: exemption_repository::RepositoryError) -> Self {
+        use exemption_repository::RepositoryError;
+        match err {
+            RepositoryError::Nats(e) => e.into(),
+            RepositoryError::Policy(e) => e.into(),
+            RepositoryError::InvalidSequence(message) => Self::new("CORRUPT_EVENT_STREAM", message),
+            RepositoryError::ConcurrencyConflict { expected, actual } => Self::new(
+                "CONCURRENCY_CONFLICT",
+                "Exemption was modified concurrently; reload and retry",
+            )
+            .with_details(serde_json::json!({ "expected": expected, "actual": actual })),
+        }
+    }
+}
+
+impl From<EvaluationError> for PolicyServiceError {
+    fn from(err: EvaluationError) -> Self {
+        match err {
+            EvaluationError::PolicyNotActive(policy_id) => {
+                Self::new("POLICY_NOT_ACTIVE", err.to_string())
+                    .with_details(serde_json::json!({ "policy_id": policy_id }))
+            }
+            other => Self::new("EVALUATION_FAILED", other.to_string()),
+        }
+    }
+}
+
+impl From<policy_set_repository::RepositoryError> for PolicyServiceError {
+    fn from(err: policy_set_repository::RepositoryError) -> Self {
+        use policy_set_repository::RepositoryError;
+        match err {
+            RepositoryError::Nats(e) => e.into(),
+            RepositoryError::Policy(e) => e.into(),
+            RepositoryError::InvalidSequence(message) => Self::new("CORRUPT_EVENT_STREAM", message),
+        }
+    }
+}
+
+/// Deserialize a command payload
+fn parse_command<T: DeserializeOwned>(payload: &[u8]) -> Result<T, PolicyServiceError> {
+    Ok(serde_json::from_slice(payload)?)
+}
+
 // ============================================================================
 // Command Handlers (Skeleton Implementations)
 // ============================================================================
@@ -318,8 +738,7 @@ async fn handle_create_policy(
             "status": "accepted",
             "message": "Policy creation command received (implementation pending)"
         });
-        let payload = serde_json::to_vec(&response).unwrap();
-        let _ = client.publish(reply, payload.into()).await;
+        respond(&client, reply, &response).await;
     }
 }
 
@@ -336,62 +755,247 @@ async fn handle_update_policy(
             "status": "accepted",
             "message": "Policy update command received (implementation pending)"
         });
-        let payload = serde_json::to_vec(&response).unwrap();
-        let _ = client.publish(reply, payload.into()).await;
+        respond(&client, reply, &response).await;
     }
 }
 
 async fn handle_approve_policy(
     msg: async_nats::Message,
-    _repository: Arc<PolicyRepository>,
+    repository: Arc<PolicyRepository>,
     _publisher: Arc<NatsEventPublisher>,
     client: async_nats::Client,
 ) {
     info!("Received approve policy command");
 
+    let response = match approve_policy(&msg.payload, &repository).await {
+        Ok(response) => response,
+        Err(e) => {
+            error!("Failed to approve policy: {}", e);
+            e.to_reply()
+        }
+    };
+
     if let Some(reply) = msg.reply {
-        let response = serde_json::json!({
-            "status": "accepted",
-            "message": "Policy approval command received (implementation pending)"
-        });
-        let payload = serde_json::to_vec(&response).unwrap();
-        let _ = client.publish(reply, payload.into()).await;
+        respond(&client, reply, &response).await;
+    }
+}
+
+/// Record an approval and persist the resulting events
+///
+/// Saving through the repository appends to the event stream, which is
+/// where subscribers receive policy events from.
+async fn approve_policy(
+    payload: &[u8],
+    repository: &PolicyRepository,
+) -> Result<serde_json::Value, PolicyServiceError> {
+    let command: ApprovePolicy = parse_command(payload)?;
+    let (policy, version) = repository
+        .load_versioned(command.policy_id)
+        .await?
+        .ok_or(PolicyError::PolicyNotFound(command.policy_id.0))?;
+
+    let events = policy.approve(&command, chrono::Utc::now())?;
+    repository.save(command.policy_id, events.clone(), version).await?;
+
+    let policy = events
+        .iter()
+        .try_fold(policy, |policy, event| policy.apply_event_pure(event))?;
+
+    let response = if policy.status == PolicyStatus::Approved {
+        serde_json::json!({
+            "status": "approved",
+            "policy_id": command.policy_id
+        })
+    } else if events
+        .iter()
+        .any(|e| matches!(e, PolicyEvent::PolicyExternalApprovalRequested(_)))
+    {
+        serde_json::json!({
+            "status": "external_approval_requested",
+            "policy_id": command.policy_id
+        })
+    } else {
+        serde_json::json!({
+            "status": "pending",
+            "policy_id": command.policy_id,
+            "outstanding": policy.approval_requirements.outstanding(&policy.approvals),
+            "external_approval_pending": policy
+                .approval_requirements
+                .needs_external_approval(&policy.approvals)
+        })
+    };
+
+    Ok(response)
+}
+
+async fn handle_delegate_approval(
+    msg: async_nats::Message,
+    repository: Arc<PolicyRepository>,
+    client: async_nats::Client,
+) {
+    info!("Received delegate approval command");
+
+    let response = match delegate_approval(&msg.payload, &repository).await {
+        Ok(response) => response,
+        Err(e) => {
+            error!("Failed to delegate approval: {}", e);
+            e.to_reply()
+        }
+    };
+
+    if let Some(reply) = msg.reply {
+        respond(&client, reply, &response).await;
     }
 }
 
+/// Record an approval delegation and persist the resulting event
+async fn delegate_approval(
+    payload: &[u8],
+    repository: &PolicyRepository,
+) -> Result<serde_json::Value, PolicyServiceError> {
+    let command: DelegateApproval = parse_command(payload)?;
+    let (policy, version) = repository
+        .load_versioned(command.policy_id)
+        .await?
+        .ok_or(PolicyError::PolicyNotFound(command.policy_id.0))?;
+
+    let event = policy.delegate_approval(&command, chrono::Utc::now())?;
+    repository.save(command.policy_id, vec![event], version).await?;
+
+    Ok(serde_json::json!({
+        "status": "delegated",
+        "policy_id": command.policy_id,
+        "from": command.from,
+        "to": command.to,
+        "valid_until": command.valid_until
+    }))
+}
+
 async fn handle_activate_policy(
     msg: async_nats::Message,
-    _repository: Arc<PolicyRepository>,
+    repository: Arc<PolicyRepository>,
     _publisher: Arc<NatsEventPublisher>,
     client: async_nats::Client,
 ) {
     info!("Received activate policy command");
 
+    let response = match activate_policy(&msg.payload, &repository).await {
+        Ok(response) => response,
+        Err(e) => {
+            error!("Failed to activate policy: {}", e);
+            e.to_reply()
+        }
+    };
+
     if let Some(reply) = msg.reply {
-        let response = serde_json::json!({
-            "status": "accepted",
-            "message": "Policy activation command received (implementation pending)"
-        });
-        let payload = serde_json::to_vec(&response).unwrap();
-        let _ = client.publish(reply, payload.into()).await;
+        respond(&client, reply, &response).await;
     }
 }
 
+/// Activate a policy and persist the resulting event
+async fn activate_policy(
+    payload: &[u8],
+    repository: &PolicyRepository,
+) -> Result<serde_json::Value, PolicyServiceError> {
+    let command: ActivatePolicy = parse_command(payload)?;
+    let (policy, version) = repository
+        .load_versioned(command.policy_id)
+        .await?
+        .ok_or(PolicyError::PolicyNotFound(command.policy_id.0))?;
+
+    let event = policy.activate(&command, chrono::Utc::now())?;
+    repository.save(command.policy_id, vec![event], version).await?;
+
+    Ok(serde_json::json!({
+        "status": "activated",
+        "policy_id": command.policy_id
+    }))
+}
+
 async fn handle_suspend_policy(
     msg: async_nats::Message,
-    _repository: Arc<PolicyRepository>,
+    repository: Arc<PolicyRepository>,
     _publisher: Arc<NatsEventPublisher>,
     client: async_nats::Client,
 ) {
     info!("Received suspend policy command");
 
+    let response = match suspend_policy(&msg.payload, &repository).await {
+        Ok(response) => response,
+        Err(e) => {
+            error!("Failed to suspend policy: {}", e);
+            e.to_reply()
+        }
+    };
+
     if let Some(reply) = msg.reply {
-        let response = serde_json::json!({
-            "status": "accepted",
-            "message": "Policy suspension command received (implementation pending)"
-        });
-        let payload = serde_json::to_vec(&response).unwrap();
-        let _ = client.publish(reply, payload.into()).await;
+        respond(&client, reply, &response).await;
+    }
+}
+
+/// Suspend a policy and persist the resulting event
+async fn suspend_policy(
+    payload: &[u8],
+    repository: &PolicyRepository,
+) -> Result<serde_json::Value, PolicyServiceError> {
+    let command: SuspendPolicy = parse_command(payload)?;
+    let (policy, version) = repository
+        .load_versioned(command.policy_id)
+        .await?
+        .ok_or(PolicyError::PolicyNotFound(command.policy_id.0))?;
+
+    let event = policy.suspend(&command, chrono::Utc::now())?;
+    repository.save(command.policy_id, vec![event], version).await?;
+
+    Ok(serde_json::json!({
+        "status": "suspended",
+        "policy_id": command.policy_id
+    }))
+}
+
+async fn handle_batch(
+    msg: async_nats::Message,
+    repository: Arc<PolicyRepository>,
+    client: async_nats::Client,
+) {
+    info!("Received batch command");
+
+    let response = match parse_command::<BatchPolicyCommand>(&msg.payload) {
+        Ok(batch) => {
+            let repository = repository.as_ref();
+            let command_type = batch.command_type.clone();
+            let result = batch
+                .execute(|_, payload| {
+                    let command_type = command_type.clone();
+                    async move {
+                        let payload = serde_json::to_vec(&payload)
+                            .map_err(|e| PolicyServiceError::internal(e.to_string()))?;
+                        match command_type.as_str() {
+                            "approve" => approve_policy(&payload, repository).await,
+                            "activate" => activate_policy(&payload, repository).await,
+                            "suspend" => suspend_policy(&payload, repository).await,
+                            other => Err(PolicyServiceError::unsupported_command(other)),
+                        }
+                    }
+                })
+                .await;
+            info!(
+                "Batch {} completed: {} succeeded, {} failed",
+                batch.command_type, result.succeeded, result.failed
+            );
+            serde_json::to_value(&result).unwrap_or_else(|e| {
+                PolicyServiceError::internal(format!("Failed to serialize batch result: {}", e))
+                    .to_reply()
+            })
+        }
+        Err(e) => {
+            error!("Invalid batch command: {}", e);
+            e.to_reply()
+        }
+    };
+
+    if let Some(reply) = msg.reply {
+        respond(&client, reply, &response).await;
     }
 }
 
@@ -408,8 +1012,7 @@ async fn handle_revoke_policy(
             "status": "accepted",
             "message": "Policy revocation command received (implementation pending)"
         });
-        let payload = serde_json::to_vec(&response).unwrap();
-        let _ = client.publish(reply, payload.into()).await;
+        respond(&client, reply, &response).await;
     }
 }
 
@@ -426,8 +1029,7 @@ async fn handle_archive_policy(
             "status": "accepted",
             "message": "Policy archival command received (implementation pending)"
         });
-        let payload = serde_json::to_vec(&response).unwrap();
-        let _ = client.publish(reply, payload.into()).await;
+        respond(&client, reply, &response).await;
     }
 }
 
@@ -444,29 +1046,70 @@ async fn handle_create_set(
             "status": "accepted",
             "message": "PolicySet creation command received (implementation pending)"
         });
-        let payload = serde_json::to_vec(&response).unwrap();
-        let _ = client.publish(reply, payload.into()).await;
+        respond(&client, reply, &response).await;
     }
 }
 
 async fn handle_add_to_set(
     msg: async_nats::Message,
-    _repository: Arc<PolicySetRepository>,
+    repository: Arc<PolicySetRepository>,
+    policy_repository: Arc<PolicyRepository>,
     _publisher: Arc<NatsEventPublisher>,
     client: async_nats::Client,
 ) {
     info!("Received add to set command");
 
+    let response = match add_to_set(&msg.payload, &repository, &policy_repository).await {
+        Ok(response) => response,
+        Err(e) => {
+            error!("Failed to add policy to set: {}", e);
+            e.to_reply()
+        }
+    };
+
     if let Some(reply) = msg.reply {
-        let response = serde_json::json!({
-            "status": "accepted",
-            "message": "Add to set command received (implementation pending)"
-        });
-        let payload = serde_json::to_vec(&response).unwrap();
-        let _ = client.publish(reply, payload.into()).await;
+        respond(&client, reply, &response).await;
     }
 }
 
+/// Add a policy to a set, recording conflicts with the existing members
+async fn add_to_set(
+    payload: &[u8],
+    repository: &PolicySetRepository,
+    policy_repository: &PolicyRepository,
+) -> Result<serde_json::Value, PolicyServiceError> {
+    let command: AddPolicyToSet = parse_command(payload)?;
+    let set = repository
+        .load(command.policy_set_id)
+        .await?
+        .ok_or_else(|| PolicyServiceError::policy_set_not_found(command.policy_set_id))?;
+    let policy = policy_repository
+        .load(command.policy_id)
+        .await?
+        .ok_or(PolicyError::PolicyNotFound(command.policy_id.0))?;
+
+    let mut members = Vec::with_capacity(set.policies.len());
+    for member_id in &set.policies {
+        if let Some(member) = policy_repository.load(*member_id).await? {
+            members.push(member);
+        }
+    }
+
+    let events = set.add_member(&command, &policy, &members, chrono::Utc::now())?;
+    let conflicts = events
+        .iter()
+        .filter(|e| matches!(e, PolicyEvent::PolicyConflictDetected(_)))
+        .count();
+    repository.save(events).await?;
+
+    Ok(serde_json::json!({
+        "status": "added",
+        "policy_set_id": command.policy_set_id,
+        "policy_id": command.policy_id,
+        "conflicts": conflicts
+    }))
+}
+
 async fn handle_remove_from_set(
     msg: async_nats::Message,
     _repository: Arc<PolicySetRepository>,
@@ -480,8 +1123,7 @@ async fn handle_remove_from_set(
             "status": "accepted",
             "message": "Remove from set command received (implementation pending)"
         });
-        let payload = serde_json::to_vec(&response).unwrap();
-        let _ = client.publish(reply, payload.into()).await;
+        respond(&client, reply, &response).await;
     }
 }
 
@@ -498,8 +1140,7 @@ async fn handle_grant_exemption(
             "status": "accepted",
             "message": "Exemption grant command received (implementation pending)"
         });
-        let payload = serde_json::to_vec(&response).unwrap();
-        let _ = client.publish(reply, payload.into()).await;
+        respond(&client, reply, &response).await;
     }
 }
 
@@ -516,26 +1157,133 @@ async fn handle_revoke_exemption(
             "status": "accepted",
             "message": "Exemption revocation command received (implementation pending)"
         });
-        let payload = serde_json::to_vec(&response).unwrap();
-        let _ = client.publish(reply, payload.into()).await;
+        respond(&client, reply, &response).await;
     }
 }
 
 async fn handle_evaluate(
     msg: async_nats::Message,
-    _repository: Arc<PolicyRepository>,
-    _publisher: Arc<NatsEventPublisher>,
+    repository: Arc<PolicyRepository>,
+    exemptions: Arc<ExemptionRepository>,
+    projection: Arc<RwLock<PolicyProjection>>,
     client: async_nats::Client,
 ) {
     info!("Received policy evaluation command");
 
+    let response = match evaluate_policy(&msg.payload, &repository, &exemptions, &projection).await {
+        Ok(response) => response,
+        Err(e) => {
+            error!("Failed to evaluate policy: {}", e);
+            e.to_reply()
+        }
+    };
+
     if let Some(reply) = msg.reply {
-        let response = serde_json::json!({
-            "status": "accepted",
-            "message": "Policy evaluation command received (implementation pending)"
-        });
-        let payload = serde_json::to_vec(&response).unwrap();
-        let _ = client.publish(reply, payload.into()).await;
+        respond(&client, reply, &response).await;
+    }
+}
+
+/// Evaluate a policy with its active exemptions, recording exemption uses
+///
+/// Exemptions are found through the projection and reloaded from their
+/// event streams, so usage counts are the persisted ones.
+async fn evaluate_policy(
+    payload: &[u8],
+    repository: &PolicyRepository,
+    exemptions: &ExemptionRepository,
+    projection: &RwLock<PolicyProjection>,
+) -> Result<serde_json::Value, PolicyServiceError> {
+    let command: EvaluatePolicy = parse_command(payload)?;
+    let policy = repository
+        .load(command.policy_id)
+        .await?
+        .ok_or(PolicyError::PolicyNotFound(command.policy_id.0))?;
+
+    let policy_id = command.policy_id;
+    let load_exemptions = move || async move {
+        let exemption_ids: Vec<_> = projection
+            .read()
+            .await
+            .active_exemptions_for(&policy_id)
+            .into_iter()
+            .map(|view| view.exemption_id)
+            .collect();
+        let mut active = Vec::with_capacity(exemption_ids.len());
+        for exemption_id in exemption_ids {
+            if let Some(exemption) = exemptions.load(exemption_id).await? {
+                active.push(exemption);
+            }
+        }
+        Ok::<_, exemption_repository::RepositoryError>(active)
+    };
+    let evaluation = evaluate_recording_exemption_uses(
+        &policy,
+        &command.context,
+        load_exemptions,
+        |exemption_id, events, expected_sequence| {
+            exemptions.save_at(exemption_id, events, expected_sequence)
+        },
+    )
+    .await?;
+
+    Ok(serde_json::json!({
+        "status": "evaluated",
+        "policy_id": command.policy_id,
+        "compliant": evaluation.is_compliant(),
+        "result": evaluation.overall_result,
+    }))
+}
+
+/// Attempts at recording a usage-limited exemption's use before giving up
+const EXEMPTION_USE_ATTEMPTS: usize = 3;
+
+/// Evaluate a policy with the exemptions `load` returns, saving any exemption use
+///
+/// A use is saved with `save_at` against the exemption's `last_sequence`, so
+/// of several evaluations racing for an exemption's last use only one
+/// records it. The others reload the exemptions and evaluate again, and
+/// fail with a concurrency conflict after `EXEMPTION_USE_ATTEMPTS` attempts.
+async fn evaluate_recording_exemption_uses<L, LoadFut, S, SaveFut>(
+    policy: &Policy,
+    context: &EvaluationContext,
+    mut load: L,
+    mut save_at: S,
+) -> Result<PolicyEvaluation, PolicyServiceError>
+where
+    L: FnMut() -> LoadFut,
+    LoadFut: Future<Output = Result<Vec<PolicyExemption>, exemption_repository::RepositoryError>>,
+    S: FnMut(ExemptionId, Vec<PolicyEvent>, u64) -> SaveFut,
+    SaveFut: Future<Output = Result<(), exemption_repository::RepositoryError>>,
+{
+    let mut attempt = 1;
+    loop {
+        let active = load().await?;
+        let sequences: HashMap<_, _> = active
+            .iter()
+            .map(|exemption| (exemption.id, exemption.last_sequence))
+            .collect();
+        let mut evaluator = PolicyEvaluator::new();
+        evaluator.register_exemptions(active);
+
+        let evaluation = evaluator.evaluate(policy, context)?;
+        let usage = evaluator.take_exemption_events();
+        let ComplianceResult::CompliantWithExemption { exemption_id } = evaluation.overall_result
+        else {
+            return Ok(evaluation);
+        };
+        if usage.is_empty() {
+            return Ok(evaluation);
+        }
+
+        match save_at(exemption_id, usage, sequences[&exemption_id]).await {
+            Ok(()) => return Ok(evaluation),
+            Err(exemption_repository::RepositoryError::ConcurrencyConflict { .. })
+                if attempt < EXEMPTION_USE_ATTEMPTS =>
+            {
+                attempt += 1;
+            }
+            Err(e) => return Err(e.into()),
+        }
     }
 }
 
@@ -552,7 +1300,217 @@ async fn handle_check_compliance(
             "status": "accepted",
             "message": "Compliance check command received (implementation pending)"
         });
-        let payload = serde_json::to_vec(&response).unwrap();
-        let _ = client.publish(reply, payload.into()).await;
+        respond(&client, reply, &response).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn test_health_report() {
+        let healthy = health_report(async_nats::connection::State::Connected, Ok(()));
+        assert_eq!(healthy["status"], "ok");
+        assert_eq!(healthy["event_store_reachable"], true);
+
+        let no_store = health_report(
+            async_nats::connection::State::Connected,
+            Err("stream not found".to_string()),
+        );
+        assert_eq!(no_store["status"], "unavailable");
+        assert_eq!(no_store["event_store_error"], "stream not found");
+
+        let disconnected = health_report(async_nats::connection::State::Disconnected, Ok(()));
+        assert_eq!(disconnected["status"], "unavailable");
+        assert_eq!(disconnected["nats_connected"], false);
+    }
+
+    #[test]
+    fn test_projection_queries() {
+        use cim_domain_policy::events::PolicyCreated;
+
+        let policy_id = PolicyId::new();
+        let mut projection = PolicyProjection::new();
+        projection.apply(
+            1,
+            &PolicyEvent::PolicyCreated(PolicyCreated {
+                event_id: uuid::Uuid::now_v7(),
+                identity: cim_domain_policy::sagas::create_root_command(),
+                policy_id,
+                name: "Key Policy".to_string(),
+                description: "Key size requirements".to_string(),
+                policy_type: "Security".to_string(),
+                created_by: "alice".to_string(),
+                created_at: chrono::Utc::now(),
+                approval_requirements: Default::default(),
+            }),
+        );
+
+        let query = serde_json::json!({ "query": "policy", "policy_id": policy_id });
+        let view = query_projection(query.to_string().as_bytes(), &projection).unwrap();
+        assert_eq!(view["name"], "Key Policy");
+
+        let query = serde_json::json!({ "query": "policies_with_status", "status": "Draft" });
+        let views = query_projection(query.to_string().as_bytes(), &projection).unwrap();
+        assert_eq!(views.as_array().unwrap().len(), 1);
+
+        let query = serde_json::json!({ "query": "policy", "policy_id": PolicyId::new() });
+        let err = query_projection(query.to_string().as_bytes(), &projection).unwrap_err();
+        assert_eq!(err.code, "POLICY_NOT_FOUND");
+    }
+
+    #[test]
+    fn test_invalid_payload_gets_structured_error() {
+        let err = parse_command::<ApprovePolicy>(b"{not json").unwrap_err();
+        assert_eq!(err.code, "INVALID_PAYLOAD");
+        assert_eq!(err.details.as_ref().unwrap()["line"], 1);
+
+        // Well-formed JSON of the wrong shape is rejected the same way
+        let err = parse_command::<BatchPolicyCommand>(br#"{"policy_ids": "all"}"#).unwrap_err();
+        assert_eq!(err.code, "INVALID_PAYLOAD");
+
+        let reply = err.to_reply();
+        assert_eq!(reply["status"], "error");
+        assert_eq!(reply["error"]["code"], "INVALID_PAYLOAD");
+        assert_eq!(reply["error"]["message"], reply["message"]);
+    }
+
+    #[test]
+    fn test_domain_errors_map_to_stable_codes() {
+        let id = uuid::Uuid::now_v7();
+        let err = PolicyServiceError::from(PolicyError::PolicyNotFound(id));
+        assert_eq!(err.code, "POLICY_NOT_FOUND");
+        assert_eq!(err.details, Some(serde_json::json!({ "id": id })));
+
+        let err = PolicyServiceError::from(PolicyError::ValidationError("no rules".into()));
+        assert_eq!(err.code, "VALIDATION_FAILED");
+        assert_eq!(err.details, None);
+        assert!(err.to_reply()["error"].get("details").is_none());
+
+        let err = PolicyServiceError::from(policy_repository::RepositoryError::ConcurrencyConflict {
+            expected: 3,
+            actual: 4,
+        });
+        assert_eq!(err.code, "CONCURRENCY_CONFLICT");
+        assert_eq!(err.details.unwrap()["actual"], 4);
+
+        let err = PolicyServiceError::from(policy_set_repository::RepositoryError::Policy(
+            PolicyError::UnauthorizedOperation("no".into()),
+        ));
+        assert_eq!(err.code, "UNAUTHORIZED");
+
+        let policy_id = cim_domain_policy::PolicyId::new();
+        let err = PolicyServiceError::from(EvaluationError::PolicyNotActive(policy_id));
+        assert_eq!(err.code, "POLICY_NOT_ACTIVE");
+        let err = PolicyServiceError::from(EvaluationError::MissingContextField("key_size".into()));
+        assert_eq!(err.code, "EVALUATION_FAILED");
+
+        assert_eq!(
+            PolicyServiceError::unsupported_command("explode").code,
+            "UNSUPPORTED_COMMAND"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_racing_evaluations_grant_last_exemption_use_once() {
+        use cim_domain_policy::entities::PolicyRule;
+        use std::sync::Mutex;
+
+        let mut policy = Policy::new("Validity", "Certificate validity");
+        policy.status = PolicyStatus::Active;
+        policy.add_rule(PolicyRule::max_validity_days(90));
+        let mut exemption = PolicyExemption::new(
+            policy.id,
+            "Break glass",
+            "One emergency renewal left",
+            "admin",
+            chrono::Utc::now() + chrono::Duration::days(30),
+        );
+        exemption.max_uses = Some(2);
+        exemption.uses_count = 1;
+        exemption.last_sequence = 5;
+
+        // A stream holding the one exemption, appending only at its last sequence
+        let stream = Mutex::new(exemption);
+        let load = || async {
+            let loaded = stream.lock().unwrap().clone();
+            // Let the other evaluation load the same state before either saves
+            tokio::task::yield_now().await;
+            Ok(vec![loaded])
+        };
+        let save_at = |_: ExemptionId, events: Vec<PolicyEvent>, expected: u64| {
+            let mut current = stream.lock().unwrap();
+            let result = if current.last_sequence == expected {
+                for event in &events {
+                    *current = current.apply_event_pure(event).unwrap();
+                    current.last_sequence += 1;
+                }
+                Ok(())
+            } else {
+                Err(exemption_repository::RepositoryError::ConcurrencyConflict {
+                    expected,
+                    actual: current.last_sequence,
+                })
+            };
+            async move { result }
+        };
+
+        let context = EvaluationContext::new().with_field("validity_days", 365);
+        let (first, second) = tokio::join!(
+            evaluate_recording_exemption_uses(&policy, &context, load, save_at),
+            evaluate_recording_exemption_uses(&policy, &context, load, save_at),
+        );
+
+        let granted = [first.unwrap(), second.unwrap()]
+            .iter()
+            .filter(|evaluation| evaluation.is_compliant())
+            .count();
+        assert_eq!(granted, 1);
+        assert_eq!(stream.lock().unwrap().uses_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_waits_for_handlers_before_draining() {
+        let (shutdown, _) = broadcast::channel::<()>(1);
+        let mut handlers = JoinSet::new();
+        let finished = Arc::new(AtomicBool::new(false));
+
+        // An in-flight command that finishes after shutdown is signalled
+        let handler_finished = finished.clone();
+        let mut receiver = shutdown.subscribe();
+        handlers.spawn(async move {
+            let _ = receiver.recv().await;
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            handler_finished.store(true, Ordering::SeqCst);
+        });
+
+        let drained_after_handlers = Arc::new(AtomicBool::new(false));
+        let drained = drained_after_handlers.clone();
+        let completed = shutdown_gracefully(shutdown, handlers, Duration::from_secs(5), async move {
+            drained.store(finished.load(Ordering::SeqCst), Ordering::SeqCst);
+        })
+        .await;
+
+        assert!(completed);
+        assert!(drained_after_handlers.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_aborts_stuck_handlers_after_timeout() {
+        let (shutdown, _) = broadcast::channel::<()>(1);
+        let mut handlers = JoinSet::new();
+        handlers.spawn(std::future::pending::<()>());
+
+        let drained = Arc::new(AtomicBool::new(false));
+        let drain_flag = drained.clone();
+        let completed = shutdown_gracefully(shutdown, handlers, Duration::from_millis(20), async move {
+            drain_flag.store(true, Ordering::SeqCst);
+        })
+        .await;
+
+        assert!(!completed);
+        assert!(drained.load(Ordering::SeqCst));
     }
 }
@@ -0,0 +1,155 @@
+//! Source of the current time
+//!
+//! Aggregates take the current time as a `now` argument; services and sagas
+//! that need it read it from a `Clock`, so tests can substitute a
+//! `MockClock` instead of racing the wall clock.
+
+use chrono::{DateTime, Duration, Utc};
+use std::sync::{Arc, Mutex};
+
+/// Provides the current time
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The system's wall clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock that only moves when told to
+///
+/// Clones share the same time, so a test can keep one handle and give
+/// another to the code under test.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    now: Arc<Mutex<DateTime<Utc>>>,
+}
+
+impl MockClock {
+    /// Create a clock stopped at `now`
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Arc::new(Mutex::new(now)),
+        }
+    }
+
+    /// Move the clock to `now`
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap() = now;
+    }
+
+    /// Move the clock forward by `duration`
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+}
+
+/// The clock used when none is configured
+pub(crate) fn system_clock() -> Arc<dyn Clock> {
+    Arc::new(SystemClock)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::{ExemptionStatus, Policy, PolicyExemption};
+    use crate::events::PolicyEvent;
+    use crate::sagas::approval_saga::{ApprovalLevel, PolicyApprovalSaga};
+    use crate::sagas::{ExemptionWorkflowSaga, PolicySaga, SagaState};
+    use crate::services::ExemptionSweeper;
+    use crate::value_objects::{PolicyId, PolicyStatus};
+
+    fn start() -> DateTime<Utc> {
+        "2025-06-01T09:00:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn test_mock_clock_controls_policy_effectiveness() {
+        let clock = MockClock::new(start());
+        let mut policy = Policy::new("Quarterly", "In force for the third quarter");
+        policy.status = PolicyStatus::Active;
+        policy.effective_date = Some(start() + Duration::days(30));
+        policy.expiry_date = Some(start() + Duration::days(120));
+
+        assert!(!policy.is_effective_at(clock.now()));
+        clock.advance(Duration::days(30));
+        assert!(policy.is_effective_at(clock.now()));
+        clock.set(start() + Duration::days(120));
+        assert!(policy.is_effective_at(clock.now()));
+        clock.advance(Duration::seconds(1));
+        assert!(!policy.is_effective_at(clock.now()));
+    }
+
+    #[test]
+    fn test_mock_clock_controls_exemption_expiry() {
+        let clock = MockClock::new(start());
+        let mut exemption = PolicyExemption::new(
+            PolicyId::new(),
+            "Migration",
+            "Legacy keys",
+            "ciso",
+            start() + Duration::hours(1),
+        );
+        exemption.valid_from = start();
+        let sweeper = ExemptionSweeper::new();
+
+        clock.advance(Duration::hours(1));
+        assert!(exemption.is_valid_at(clock.now()));
+        assert!(sweeper
+            .sweep(std::slice::from_ref(&exemption), clock.now())
+            .is_empty());
+
+        clock.advance(Duration::seconds(1));
+        assert!(!exemption.is_valid_at(clock.now()));
+        let expired = sweeper.sweep(std::slice::from_ref(&exemption), clock.now());
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].expired_at, clock.now());
+        let exemption = exemption
+            .apply_event_pure(&PolicyEvent::PolicyExemptionExpired(expired[0].clone()))
+            .unwrap();
+        assert_eq!(exemption.status, ExemptionStatus::Expired);
+    }
+
+    #[test]
+    fn test_exemption_saga_expires_on_mock_clock() {
+        let clock = MockClock::new(start());
+        let mut saga = ExemptionWorkflowSaga::new(PolicyId::new(), "alice".to_string())
+            .with_clock(Arc::new(clock.clone()));
+        saga.grant_exemption(Vec::new(), Duration::days(7));
+
+        clock.advance(Duration::days(7));
+        saga.check_expiry();
+        assert_eq!(saga.current_state(), SagaState::ExemptionGranted);
+
+        clock.advance(Duration::seconds(1));
+        saga.check_expiry();
+        assert_eq!(saga.current_state(), SagaState::ExemptionExpired);
+    }
+
+    #[test]
+    fn test_saga_metadata_reads_mock_clock() {
+        let clock = MockClock::new(start());
+        let mut saga = PolicyApprovalSaga::new(PolicyId::new(), "alice".to_string())
+            .with_clock(Arc::new(clock.clone()));
+        assert_eq!(saga.metadata().initiated_at, start());
+        assert_eq!(saga.metadata().last_updated, start());
+
+        clock.advance(Duration::hours(3));
+        saga.add_approval("bob".to_string(), ApprovalLevel::Manager);
+        assert_eq!(saga.metadata().initiated_at, start());
+        assert_eq!(saga.metadata().last_updated, start() + Duration::hours(3));
+        assert_eq!(saga.metadata().version, 2);
+    }
+}
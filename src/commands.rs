@@ -17,6 +17,7 @@ pub enum PolicyCommand {
     CreatePolicy(CreatePolicy),
     UpdatePolicy(UpdatePolicy),
     ApprovePolicy(ApprovePolicy),
+    DelegateApproval(DelegateApproval),
     ActivatePolicy(ActivatePolicy),
     SuspendPolicy(SuspendPolicy),
     RevokePolicy(RevokePolicy),
@@ -25,11 +26,13 @@ pub enum PolicyCommand {
     // Evaluation commands
     EvaluatePolicy(EvaluatePolicy),
     EnforcePolicy(EnforcePolicy),
+    RemediateViolation(RemediateViolation),
 
     // Exemption commands
     RequestExemption(RequestExemption),
     GrantExemption(GrantExemption),
     RevokeExemption(RevokeExemption),
+    RenewExemption(RenewExemption),
 
     // PolicySet commands
     CreatePolicySet(CreatePolicySet),
@@ -46,15 +49,18 @@ impl Command for PolicyCommand {
             PolicyCommand::CreatePolicy(_) => None, // New aggregate
             PolicyCommand::UpdatePolicy(cmd) => Some(EntityId::from_uuid(cmd.policy_id.0)),
             PolicyCommand::ApprovePolicy(cmd) => Some(EntityId::from_uuid(cmd.policy_id.0)),
+            PolicyCommand::DelegateApproval(cmd) => Some(EntityId::from_uuid(cmd.policy_id.0)),
             PolicyCommand::ActivatePolicy(cmd) => Some(EntityId::from_uuid(cmd.policy_id.0)),
             PolicyCommand::SuspendPolicy(cmd) => Some(EntityId::from_uuid(cmd.policy_id.0)),
             PolicyCommand::RevokePolicy(cmd) => Some(EntityId::from_uuid(cmd.policy_id.0)),
             PolicyCommand::ArchivePolicy(cmd) => Some(EntityId::from_uuid(cmd.policy_id.0)),
             PolicyCommand::EvaluatePolicy(cmd) => Some(EntityId::from_uuid(cmd.policy_id.0)),
             PolicyCommand::EnforcePolicy(cmd) => Some(EntityId::from_uuid(cmd.policy_id.0)),
+            PolicyCommand::RemediateViolation(cmd) => Some(EntityId::from_uuid(cmd.policy_id.0)),
             PolicyCommand::RequestExemption(cmd) => Some(EntityId::from_uuid(cmd.policy_id.0)),
             PolicyCommand::GrantExemption(cmd) => Some(EntityId::from_uuid(cmd.policy_id.0)),
             PolicyCommand::RevokeExemption(_cmd) => None, // Exemption aggregate
+            PolicyCommand::RenewExemption(_cmd) => None, // Exemption aggregate
             PolicyCommand::CreatePolicySet(_) => None, // PolicySet aggregate
             PolicyCommand::AddPolicyToSet(_) => None, // PolicySet aggregate
             PolicyCommand::RemovePolicyFromSet(_) => None, // PolicySet aggregate
@@ -115,6 +121,15 @@ pub struct ApprovePolicy {
     pub policy_id: PolicyId,
     pub approved_by: String,
     pub approval_notes: Option<String>,
+    /// Role the approver is acting in
+    #[serde(default)]
+    pub approver_role: Option<String>,
+    /// Whether the approver is an external party
+    #[serde(default)]
+    pub external: bool,
+    /// Whether the approver is a person or an automated service
+    #[serde(default)]
+    pub approver_kind: Option<ApproverKind>,
 }
 
 impl Command for ApprovePolicy {
@@ -125,6 +140,27 @@ impl Command for ApprovePolicy {
     }
 }
 
+/// Let `to` approve in place of `from` until `valid_until`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DelegateApproval {
+    pub identity: MessageIdentity,
+    pub policy_id: PolicyId,
+    pub from: String,
+    pub to: String,
+    /// Role `from` approves in, which the delegate then stands in for
+    #[serde(default)]
+    pub role: Option<String>,
+    pub valid_until: DateTime<Utc>,
+}
+
+impl Command for DelegateApproval {
+    type Aggregate = Policy;
+
+    fn aggregate_id(&self) -> Option<EntityId<Self::Aggregate>> {
+        Some(EntityId::from_uuid(self.policy_id.0))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActivatePolicy {
     pub identity: MessageIdentity,
@@ -197,6 +233,101 @@ pub struct EnforcePolicy {
     pub enforcement_action: EnforcementAction,
 }
 
+// Batch Commands
+
+/// Applies one single-policy command to many policies
+///
+/// `params` holds the fields of the single-policy command except
+/// `policy_id`, which is filled in per policy.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchPolicyCommand {
+    pub command_type: String,
+    pub policy_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub params: serde_json::Value,
+}
+
+impl BatchPolicyCommand {
+    /// Single-policy command payload for one policy of the batch
+    ///
+    /// A fresh root identity is supplied when `params` has none.
+    pub fn payload_for(&self, policy_id: Uuid) -> serde_json::Value {
+        let mut payload = match &self.params {
+            serde_json::Value::Object(params) => params.clone(),
+            _ => serde_json::Map::new(),
+        };
+        payload.insert("policy_id".to_string(), serde_json::json!(policy_id));
+        if !payload.contains_key("identity") {
+            payload.insert(
+                "identity".to_string(),
+                serde_json::to_value(crate::sagas::create_root_command()).unwrap_or_default(),
+            );
+        }
+        serde_json::Value::Object(payload)
+    }
+
+    /// Run `handler` for every policy, continuing past individual failures
+    pub async fn execute<F, Fut, E>(&self, mut handler: F) -> BatchResult
+    where
+        F: FnMut(Uuid, serde_json::Value) -> Fut,
+        Fut: std::future::Future<Output = Result<serde_json::Value, E>>,
+        E: std::fmt::Display,
+    {
+        let mut results = Vec::with_capacity(self.policy_ids.len());
+        for policy_id in &self.policy_ids {
+            let result = match handler(*policy_id, self.payload_for(*policy_id)).await {
+                Ok(response) => BatchItemResult {
+                    policy_id: *policy_id,
+                    success: true,
+                    response: Some(response),
+                    error: None,
+                },
+                Err(e) => BatchItemResult {
+                    policy_id: *policy_id,
+                    success: false,
+                    response: None,
+                    error: Some(e.to_string()),
+                },
+            };
+            results.push(result);
+        }
+
+        let succeeded = results.iter().filter(|r| r.success).count();
+        BatchResult {
+            succeeded,
+            failed: results.len() - succeeded,
+            results,
+        }
+    }
+}
+
+/// Outcome of a batch command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchResult {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<BatchItemResult>,
+}
+
+/// Outcome of a batch command for a single policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchItemResult {
+    pub policy_id: Uuid,
+    pub success: bool,
+    pub response: Option<serde_json::Value>,
+    pub error: Option<String>,
+}
+
+/// Marks the violation of a rule as remediated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemediateViolation {
+    pub identity: MessageIdentity,
+    pub policy_id: PolicyId,
+    pub rule_id: Uuid,
+    pub remediated_by: String,
+    pub notes: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum EnforcementAction {
     Block,
@@ -242,6 +373,15 @@ pub struct RevokeExemption {
     pub reason: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenewExemption {
+    pub identity: MessageIdentity,
+    pub exemption_id: ExemptionId,
+    pub new_valid_until: DateTime<Utc>,
+    pub justification: String,
+    pub renewed_by: String,
+}
+
 // PolicySet Commands
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -359,4 +499,62 @@ impl Command for RevokePolicyAssignmentCommand {
     fn aggregate_id(&self) -> Option<EntityId<Self::Aggregate>> {
         Some(EntityId::from_uuid(self.policy_id.0))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::PolicyEvent;
+    use crate::PolicyError;
+
+    fn active_policy() -> Policy {
+        let mut policy = Policy::new("Key Policy", "Key size requirements");
+        policy.status = PolicyStatus::Active;
+        policy
+    }
+
+    #[tokio::test]
+    async fn test_batch_suspend_reports_partial_success() {
+        let policies: HashMap<Uuid, Policy> = (0..2)
+            .map(|_| active_policy())
+            .map(|policy| (policy.id.0, policy))
+            .collect();
+        let unknown = Uuid::now_v7();
+        let mut policy_ids: Vec<Uuid> = policies.keys().copied().collect();
+        policy_ids.insert(1, unknown);
+
+        let batch = BatchPolicyCommand {
+            command_type: "suspend".to_string(),
+            policy_ids,
+            params: serde_json::json!({
+                "suspended_by": "operator",
+                "reason": "Incident response",
+                "expected_resume_date": null
+            }),
+        };
+
+        let result = batch
+            .execute(|policy_id, payload| {
+                let policy = policies.get(&policy_id).cloned();
+                async move {
+                    let command: SuspendPolicy = serde_json::from_value(payload)
+                        .map_err(|e| PolicyError::ValidationError(e.to_string()))?;
+                    let policy = policy.ok_or(PolicyError::PolicyNotFound(policy_id))?;
+                    let event = policy.suspend(&command, Utc::now())?;
+                    assert!(matches!(event, PolicyEvent::PolicySuspended(_)));
+                    Ok::<_, PolicyError>(serde_json::json!({ "status": "suspended" }))
+                }
+            })
+            .await;
+
+        assert_eq!(result.succeeded, 2);
+        assert_eq!(result.failed, 1);
+        assert_eq!(result.results.len(), 3);
+
+        let failure = &result.results[1];
+        assert_eq!(failure.policy_id, unknown);
+        assert!(!failure.success);
+        assert!(failure.error.as_deref().unwrap().contains(&unknown.to_string()));
+        assert!(result.results[2].success);
+    }
+}
@@ -0,0 +1,510 @@
+//! Policy-as-code documents
+//!
+//! YAML representations of policies and policy sets for managing them in Git.
+//! Rule expressions are written with YAML tags, e.g. `expression: !Exists {field: mfa}`.
+//! Documents carry only what an author writes — definitions, not lifecycle
+//! state — so status, approvals and audit metadata are left out and a
+//! reviewed change reads as a small diff.
+
+use crate::aggregate::{CompositionRule, ConflictResolution, Policy, PolicySet};
+use crate::entities::{PolicyRule, RuleType};
+use crate::value_objects::*;
+use crate::PolicyError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// YAML form of a [`Policy`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyDocument {
+    /// Omitted for a new policy; an id is generated on import
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Uuid>,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default = "global_target")]
+    pub target: PolicyTarget,
+    pub enforcement_level: EnforcementLevel,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_date: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiry_date: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_policy_id: Option<Uuid>,
+    pub rules: Vec<RuleDocument>,
+}
+
+/// YAML form of a [`PolicyRule`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleDocument {
+    /// Omitted for a new rule; an id is generated on import
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Uuid>,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub rule_type: RuleType,
+    pub expression: RuleExpression,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub parameters: HashMap<String, Value>,
+    pub severity: Severity,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remediation_hint: Option<String>,
+}
+
+/// YAML form of a [`PolicySet`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicySetDocument {
+    /// Omitted for a new set; an id is generated on import
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Uuid>,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub policies: Vec<Uuid>,
+    pub composition_rule: CompositionRule,
+    pub conflict_resolution: ConflictResolution,
+}
+
+fn global_target() -> PolicyTarget {
+    PolicyTarget::Global
+}
+
+impl From<&PolicyRule> for RuleDocument {
+    fn from(rule: &PolicyRule) -> Self {
+        Self {
+            id: Some(rule.id),
+            name: rule.name.clone(),
+            description: rule.description.clone(),
+            rule_type: rule.rule_type,
+            expression: rule.expression.clone(),
+            parameters: rule.parameters.clone(),
+            severity: rule.severity,
+            error_message: rule.error_message.clone(),
+            remediation_hint: rule.remediation_hint.clone(),
+        }
+    }
+}
+
+impl From<&Policy> for PolicyDocument {
+    fn from(policy: &Policy) -> Self {
+        Self {
+            id: Some(policy.id.0),
+            name: policy.name.clone(),
+            description: policy.description.clone(),
+            target: policy.target.clone(),
+            enforcement_level: policy.enforcement_level,
+            effective_date: policy.effective_date,
+            expiry_date: policy.expiry_date,
+            parent_policy_id: policy.parent_policy_id.map(|id| id.0),
+            rules: policy.rules.iter().map(RuleDocument::from).collect(),
+        }
+    }
+}
+
+impl From<&PolicySet> for PolicySetDocument {
+    fn from(set: &PolicySet) -> Self {
+        Self {
+            id: Some(set.id.0),
+            name: set.name.clone(),
+            description: set.description.clone(),
+            policies: set.policies.iter().map(|id| id.0).collect(),
+            composition_rule: set.composition_rule,
+            conflict_resolution: set.conflict_resolution,
+        }
+    }
+}
+
+impl PolicyDocument {
+    /// Check the document and build a draft policy from it
+    pub fn into_policy(self) -> Result<Policy, PolicyError> {
+        require_name("Policy", &self.name)?;
+        if let (Some(effective), Some(expiry)) = (self.effective_date, self.expiry_date) {
+            if expiry <= effective {
+                return Err(PolicyError::ValidationError(format!(
+                    "Policy '{}' expires before it takes effect",
+                    self.name
+                )));
+            }
+        }
+
+        let mut rule_ids = HashSet::new();
+        let mut rules = Vec::with_capacity(self.rules.len());
+        for rule in self.rules {
+            let rule = rule.into_rule()?;
+            if !rule_ids.insert(rule.id) {
+                return Err(PolicyError::ValidationError(format!(
+                    "Duplicate rule id {} in policy '{}'",
+                    rule.id, self.name
+                )));
+            }
+            rules.push(rule);
+        }
+
+        let mut policy = Policy::new(self.name, self.description);
+        if let Some(id) = self.id {
+            policy.id = PolicyId(id);
+        }
+        policy.target = self.target;
+        policy.enforcement_level = self.enforcement_level;
+        policy.effective_date = self.effective_date;
+        policy.expiry_date = self.expiry_date;
+        policy.parent_policy_id = self.parent_policy_id.map(PolicyId);
+        policy.rules = rules;
+        Ok(policy)
+    }
+}
+
+impl RuleDocument {
+    /// Check the document and build a rule from it
+    pub fn into_rule(self) -> Result<PolicyRule, PolicyError> {
+        require_name("Rule", &self.name)?;
+        validate_expression(&self.expression)?;
+
+        let mut rule = PolicyRule::new(self.name, self.description, self.expression, self.severity);
+        if let Some(id) = self.id {
+            rule.id = id;
+        }
+        rule.rule_type = self.rule_type;
+        rule.parameters = self.parameters;
+        rule.error_message = self.error_message;
+        rule.remediation_hint = self.remediation_hint;
+        Ok(rule)
+    }
+}
+
+impl PolicySetDocument {
+    /// Check the document and build a draft policy set from it
+    pub fn into_policy_set(self) -> Result<PolicySet, PolicyError> {
+        require_name("Policy set", &self.name)?;
+        if let CompositionRule::AtLeast(n) = self.composition_rule {
+            if n > self.policies.len() {
+                return Err(PolicyError::ValidationError(format!(
+                    "Policy set '{}' requires {} policies but lists {}",
+                    self.name,
+                    n,
+                    self.policies.len()
+                )));
+            }
+        }
+
+        let mut set = PolicySet::new(self.name, self.description);
+        if let Some(id) = self.id {
+            set.id = PolicySetId(id);
+        }
+        for policy_id in self.policies {
+            set.add_policy(PolicyId(policy_id));
+        }
+        set.composition_rule = self.composition_rule;
+        set.conflict_resolution = self.conflict_resolution;
+        Ok(set)
+    }
+}
+
+/// A policy file that could not be imported
+#[derive(Debug)]
+pub struct ImportError {
+    pub path: PathBuf,
+    pub error: PolicyError,
+}
+
+/// Outcome of a bulk import
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    /// Policies imported as drafts, in file name order
+    pub policies: Vec<Policy>,
+    /// Files that were rejected, in file name order
+    pub errors: Vec<ImportError>,
+}
+
+/// Imports policy documents in bulk, e.g. to bootstrap from an existing rule set
+#[derive(Debug, Clone, Default)]
+pub struct PolicyImporter;
+
+impl PolicyImporter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Import every `.yaml`, `.yml` and `.json` policy document in `dir`
+    ///
+    /// Each file is parsed and validated on its own; besides the checks of
+    /// [`PolicyDocument::into_policy`], every rule must be satisfiable and
+    /// policy ids and names must be unique across the directory (the first
+    /// file by name wins). Bad files are reported in the result rather than aborting
+    /// the import. Only failing to read the directory itself is an error.
+    pub fn import_dir(&self, dir: impl AsRef<Path>) -> Result<ImportReport, PolicyError> {
+        let read_error = |e: std::io::Error| {
+            PolicyError::ValidationError(format!("Cannot read {}: {}", dir.as_ref().display(), e))
+        };
+        let mut paths = Vec::new();
+        for entry in std::fs::read_dir(dir.as_ref()).map_err(read_error)? {
+            let path = entry.map_err(read_error)?.path();
+            let is_document = matches!(
+                path.extension().and_then(|ext| ext.to_str()),
+                Some("yaml" | "yml" | "json")
+            );
+            if is_document && path.is_file() {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        let mut report = ImportReport::default();
+        let mut ids = HashSet::new();
+        let mut names = HashSet::new();
+        for path in paths {
+            let imported = self.import_file(&path).and_then(|policy| {
+                if ids.contains(&policy.id) {
+                    Err(PolicyError::ValidationError(format!(
+                        "Policy id {} is already used by another file",
+                        policy.id
+                    )))
+                } else if names.contains(&policy.name) {
+                    Err(PolicyError::ValidationError(format!(
+                        "Policy name '{}' is already used by another file",
+                        policy.name
+                    )))
+                } else {
+                    ids.insert(policy.id);
+                    names.insert(policy.name.clone());
+                    Ok(policy)
+                }
+            });
+            match imported {
+                Ok(policy) => report.policies.push(policy),
+                Err(error) => report.errors.push(ImportError { path, error }),
+            }
+        }
+        Ok(report)
+    }
+
+    fn import_file(&self, path: &Path) -> Result<Policy, PolicyError> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| PolicyError::ValidationError(format!("Cannot read file: {}", e)))?;
+        let document: PolicyDocument = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(&contents).map_err(|e| {
+                PolicyError::ValidationError(format!("Invalid policy document: {}", e))
+            })?
+        } else {
+            from_yaml(&contents)?
+        };
+
+        let policy = document.into_policy()?;
+        if let Some(rule) = policy
+            .rules
+            .iter()
+            .find(|rule| !crate::services::is_satisfiable(&rule.expression))
+        {
+            return Err(PolicyError::ValidationError(format!(
+                "Rule '{}' in policy '{}' can never be satisfied",
+                rule.name, policy.name
+            )));
+        }
+        Ok(policy)
+    }
+}
+
+/// Serialize a document to YAML
+pub(crate) fn to_yaml<T: Serialize>(document: &T) -> Result<String, PolicyError> {
+    serde_norway::to_string(document).map_err(|e| PolicyError::Serialization(e.to_string()))
+}
+
+/// Parse a YAML document, reporting where it is malformed
+pub(crate) fn from_yaml<T: serde::de::DeserializeOwned>(yaml: &str) -> Result<T, PolicyError> {
+    serde_norway::from_str(yaml).map_err(|e| {
+        PolicyError::ValidationError(format!("Invalid policy document: {}", e))
+    })
+}
+
+fn require_name(kind: &str, name: &str) -> Result<(), PolicyError> {
+    if name.trim().is_empty() {
+        return Err(PolicyError::ValidationError(format!("{} name must not be empty", kind)));
+    }
+    Ok(())
+}
+
+/// Reject expressions that could never evaluate, e.g. an invalid regex
+fn validate_expression(expression: &RuleExpression) -> Result<(), PolicyError> {
+    match expression {
+        RuleExpression::And(children) | RuleExpression::Or(children) => {
+            if children.is_empty() {
+                return Err(PolicyError::ValidationError(
+                    "And/Or must have at least one operand".to_string(),
+                ));
+            }
+            children.iter().try_for_each(validate_expression)
+        }
+        RuleExpression::Not(inner) => validate_expression(inner),
+        RuleExpression::MatchesRegex { pattern, .. } => regex::Regex::new(pattern)
+            .map(|_| ())
+            .map_err(|e| PolicyError::ValidationError(format!("Invalid pattern: {}", e))),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_size_policy() -> Policy {
+        let mut policy = Policy::new("Key Size", "Minimum key sizes for certificates");
+        policy.enforcement_level = EnforcementLevel::Hard;
+        policy.target = PolicyTarget::Role("pki-admin".to_string());
+        policy.expiry_date = Some(Utc::now() + chrono::Duration::days(365));
+        policy.add_rule(PolicyRule::min_key_size(2048));
+        policy
+    }
+
+    #[test]
+    fn test_policy_round_trips_through_yaml() {
+        let policy = key_size_policy();
+        let yaml = policy.to_yaml().unwrap();
+        assert!(yaml.contains("name: Key Size"));
+        assert!(!yaml.contains("status"));
+
+        let imported = Policy::from_yaml(&yaml).unwrap();
+        assert_eq!(imported.id, policy.id);
+        assert_eq!(imported.status, PolicyStatus::Draft);
+        assert!(policy.diff(&imported).is_empty(), "{}", policy.diff(&imported));
+    }
+
+    #[test]
+    fn test_policy_set_round_trips_through_yaml() {
+        let mut set = PolicySet::new("Crypto", "Cryptography baseline");
+        set.add_policy(PolicyId::new());
+        set.add_policy(PolicyId::new());
+        set.composition_rule = CompositionRule::AtLeast(2);
+
+        let imported = PolicySet::from_yaml(&set.to_yaml().unwrap()).unwrap();
+        assert_eq!(PolicySetDocument::from(&imported), PolicySetDocument::from(&set));
+    }
+
+    #[test]
+    fn test_import_hand_written_policy() {
+        let yaml = r#"
+name: Production access
+description: Only on-call engineers may touch production
+enforcement_level: Hard
+rules:
+  - name: On-call role
+    rule_type: Authorization
+    severity: High
+    expression: !And
+      - !Equal
+        field: environment
+        value: production
+      - !In
+        field: role
+        values: [oncall, sre]
+    error_message: Production access requires an on-call role
+"#;
+        let policy = Policy::from_yaml(yaml).unwrap();
+        assert_eq!(policy.name, "Production access");
+        assert_eq!(policy.target, PolicyTarget::Global);
+        assert_eq!(policy.rules.len(), 1);
+
+        let context = EvaluationContext::new()
+            .with_field("environment", "production")
+            .with_field("role", "sre");
+        let active = Policy {
+            status: PolicyStatus::Active,
+            ..policy
+        };
+        let evaluation = crate::PolicyEvaluator::new().evaluate(&active, &context).unwrap();
+        assert!(evaluation.is_compliant());
+    }
+
+    #[test]
+    fn test_malformed_documents_are_rejected() {
+        let cases = [
+            // Not YAML for a policy at all
+            "- just\n- a list\n",
+            // Unknown field, e.g. a typo
+            "name: P\nenforcment_level: Hard\nrules: []\n",
+            // Empty name
+            "name: ''\nenforcement_level: Hard\nrules: []\n",
+            // Empty conjunction
+            "name: P\nenforcement_level: Hard\nrules:\n  - name: R\n    rule_type: Validation\n    severity: Low\n    expression: !And []\n",
+            // Invalid regex
+            "name: P\nenforcement_level: Hard\nrules:\n  - name: R\n    rule_type: Validation\n    severity: Low\n    expression: !MatchesRegex {field: cn, pattern: '('}\n",
+        ];
+        for yaml in cases {
+            assert!(
+                matches!(Policy::from_yaml(yaml), Err(PolicyError::ValidationError(_))),
+                "{}",
+                yaml
+            );
+        }
+
+        let mut policy = key_size_policy();
+        policy.rules.push(policy.rules[0].clone());
+        assert!(matches!(
+            Policy::from_yaml(&policy.to_yaml().unwrap()),
+            Err(PolicyError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_import_dir_reports_bad_files_without_aborting() {
+        let dir = std::env::temp_dir().join(format!("policy-import-{}", Uuid::now_v7()));
+        std::fs::create_dir(&dir).unwrap();
+
+        std::fs::write(dir.join("a-key-size.yaml"), key_size_policy().to_yaml().unwrap()).unwrap();
+        let mut mfa = Policy::new("MFA", "Multi-factor authentication");
+        mfa.add_rule(PolicyRule::new(
+            "MFA enabled",
+            "MFA must be enabled",
+            RuleExpression::Equal {
+                field: "mfa".to_string(),
+                value: Value::Bool(true),
+            },
+            Severity::High,
+        ));
+        let json = serde_json::to_string(&PolicyDocument::from(&mfa)).unwrap();
+        std::fs::write(dir.join("b-mfa.json"), json).unwrap();
+        std::fs::write(dir.join("c-broken.yaml"), "name: Broken\nenforcement_level: [\n").unwrap();
+        std::fs::write(
+            dir.join("d-impossible.yml"),
+            "name: Impossible\nenforcement_level: Hard\nrules:\n  - name: R\n    rule_type: Validation\n    severity: Low\n    expression: !And [!GreaterThan {field: x, value: 10}, !LessThan {field: x, value: 5}]\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("e-duplicate.yaml"), key_size_policy().to_yaml().unwrap()).unwrap();
+        let mut same_id = key_size_policy();
+        same_id.id = mfa.id;
+        same_id.name = "Renamed MFA".to_string();
+        std::fs::write(dir.join("f-same-id.yaml"), same_id.to_yaml().unwrap()).unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a policy").unwrap();
+
+        let report = PolicyImporter::new().import_dir(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let names: Vec<_> = report.policies.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["Key Size", "MFA"]);
+        let failed: Vec<_> = report
+            .errors
+            .iter()
+            .map(|e| e.path.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(
+            failed,
+            vec!["c-broken.yaml", "d-impossible.yml", "e-duplicate.yaml", "f-same-id.yaml"]
+        );
+        assert!(report.errors[1].error.to_string().contains("never be satisfied"));
+        assert!(report.errors[2].error.to_string().contains("name 'Key Size' is already used"));
+        assert!(report.errors[3]
+            .error
+            .to_string()
+            .contains(&format!("id {} is already used", mfa.id)));
+
+        assert!(PolicyImporter::new().import_dir(&dir).is_err());
+    }
+}
@@ -8,7 +8,7 @@ use std::collections::HashMap;
 use uuid::Uuid;
 
 /// A single rule within a policy
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PolicyRule {
     pub id: Uuid,
     pub name: String,
@@ -18,6 +18,8 @@ pub struct PolicyRule {
     pub parameters: HashMap<String, Value>,
     pub severity: Severity,
     pub error_message: Option<String>,
+    /// Remediation shown when the rule fails; `{field}` and `{value}` are
+    /// replaced with the expression's field and expected value
     pub remediation_hint: Option<String>,
 }
 
@@ -80,6 +82,71 @@ impl PolicyRule {
             Severity::High,
         )
     }
+
+    /// How to fix a failure of this rule
+    ///
+    /// Uses `remediation_hint` as a template when set, otherwise describes
+    /// what the expression requires, e.g. "set 'amount' to at most 10000".
+    /// Returns `None` when neither is available.
+    pub fn remediation(&self) -> Option<String> {
+        let (field, value) = match &self.expression {
+            RuleExpression::Equal { field, value }
+            | RuleExpression::NotEqual { field, value }
+            | RuleExpression::GreaterThan { field, value }
+            | RuleExpression::GreaterThanOrEqual { field, value }
+            | RuleExpression::LessThan { field, value }
+            | RuleExpression::LessThanOrEqual { field, value }
+            | RuleExpression::Contains { field, value } => (Some(field), Some(describe_value(value))),
+            RuleExpression::In { field, values } | RuleExpression::NotIn { field, values } => {
+                (Some(field), Some(describe_values(values)))
+            }
+            RuleExpression::Exists { field } | RuleExpression::NotExists { field } => (Some(field), None),
+            _ => (None, None),
+        };
+
+        if let Some(template) = &self.remediation_hint {
+            return Some(
+                template
+                    .replace("{field}", field.map(String::as_str).unwrap_or_default())
+                    .replace("{value}", value.as_deref().unwrap_or_default()),
+            );
+        }
+
+        let (field, value) = (field?, value.unwrap_or_default());
+        let suggestion = match &self.expression {
+            RuleExpression::Equal { .. } => format!("set '{}' to {}", field, value),
+            RuleExpression::NotEqual { .. } => format!("change '{}' to something other than {}", field, value),
+            RuleExpression::GreaterThan { .. } => format!("set '{}' to more than {}", field, value),
+            RuleExpression::GreaterThanOrEqual { .. } => format!("set '{}' to at least {}", field, value),
+            RuleExpression::LessThan { .. } => format!("set '{}' to less than {}", field, value),
+            RuleExpression::LessThanOrEqual { .. } => format!("set '{}' to at most {}", field, value),
+            RuleExpression::Contains { .. } => format!("make '{}' contain {}", field, value),
+            RuleExpression::In { .. } => format!("set '{}' to one of {}", field, value),
+            RuleExpression::NotIn { .. } => format!("set '{}' to a value other than {}", field, value),
+            RuleExpression::Exists { .. } => format!("provide '{}'", field),
+            RuleExpression::NotExists { .. } => format!("remove '{}'", field),
+            _ => return None,
+        };
+        Some(suggestion)
+    }
+}
+
+/// Render a value for a remediation message
+fn describe_value(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Integer(i) => i.to_string(),
+        Value::Float(f) => f.to_string(),
+        Value::String(s) => format!("'{}'", s),
+        Value::DateTime(dt) => dt.to_rfc3339(),
+        Value::List(values) => format!("[{}]", describe_values(values)),
+        Value::Map(_) => "the expected map".to_string(),
+    }
+}
+
+fn describe_values(values: &[Value]) -> String {
+    values.iter().map(describe_value).collect::<Vec<_>>().join(", ")
 }
 
 /// Type of policy rule
@@ -149,6 +216,24 @@ impl PolicyEvaluation {
             .map(|r| r.to_violation())
             .collect()
     }
+
+    /// The `n` rules that took longest to evaluate, slowest first
+    pub fn slowest_rules(&self, n: usize) -> Vec<&RuleResult> {
+        let mut rules: Vec<&RuleResult> = self.rule_results.iter().collect();
+        rules.sort_by_key(|r| std::cmp::Reverse(r.execution_time_us));
+        rules.truncate(n);
+        rules
+    }
+}
+
+/// Result of evaluating a set of policies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetEvaluation {
+    pub result: ComplianceResult,
+    /// Number of policies that were evaluated
+    pub evaluated: usize,
+    /// Number of policies skipped because they were not in force
+    pub skipped: usize,
 }
 
 /// Result of evaluating a single rule
@@ -161,6 +246,12 @@ pub struct RuleResult {
     pub severity: Severity,
     pub actual_value: Option<Value>,
     pub expected_value: Option<Value>,
+    /// Time spent evaluating this rule, in microseconds
+    #[serde(default)]
+    pub execution_time_us: u64,
+    /// How to fix the failure, for failed rules
+    #[serde(default)]
+    pub suggested_remediation: Option<String>,
 }
 
 impl RuleResult {
@@ -171,11 +262,46 @@ impl RuleResult {
             rule_description: self.rule_name.clone(),
             severity: self.severity,
             details: self.message.clone(),
-            suggested_remediation: None,
+            suggested_remediation: self.suggested_remediation.clone(),
         }
     }
 }
 
+/// Step-by-step account of how a policy's rules evaluated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvaluationTrace {
+    pub policy_id: PolicyId,
+    pub rules: Vec<RuleTrace>,
+}
+
+impl EvaluationTrace {
+    /// Whether every rule passed
+    pub fn passed(&self) -> bool {
+        self.rules.iter().all(|rule| rule.expression.result == Some(true))
+    }
+}
+
+/// Trace of a single rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleTrace {
+    pub rule_id: Uuid,
+    pub rule_name: String,
+    pub expression: ExpressionTrace,
+}
+
+/// Trace of one expression node, mirroring the expression tree
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExpressionTrace {
+    /// Expression variant, e.g. `And` or `GreaterThanOrEqual`
+    pub operator: String,
+    /// Context values read by this node, `None` for absent fields
+    pub fields: Vec<(String, Option<Value>)>,
+    /// Outcome, or `None` if evaluation failed
+    pub result: Option<bool>,
+    pub error: Option<String>,
+    pub children: Vec<ExpressionTrace>,
+}
+
 /// Template for creating policies
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PolicyTemplate {
@@ -219,6 +345,15 @@ pub struct TemplateParameter {
     pub default_value: Option<Value>,
     pub required: bool,
     pub validation: Option<RuleExpression>,
+    /// Minimum length for string parameters
+    #[serde(default)]
+    pub min_length: Option<usize>,
+    /// Maximum length for string parameters
+    #[serde(default)]
+    pub max_length: Option<usize>,
+    /// Exhaustive set of accepted values
+    #[serde(default)]
+    pub allowed_values: Option<Vec<Value>>,
 }
 
 /// Type of template parameter
@@ -256,4 +391,106 @@ pub enum ConflictType {
     Impossible,
     /// Rules create ambiguity
     Ambiguous,
-}
\ No newline at end of file
+    /// Overlapping policies are enforced at incompatible levels
+    EnforcementMismatch,
+}
+
+impl ConflictType {
+    /// Whether the policies' rules cannot both be satisfied
+    ///
+    /// Only such conflicts justify dropping one of the policies; overlaps and
+    /// enforcement mismatches leave both enforceable side by side.
+    pub fn is_rule_contradiction(&self) -> bool {
+        matches!(self, ConflictType::Contradiction | ConflictType::Impossible)
+    }
+}
+/// A value that differs between two policy versions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Change<T> {
+    pub before: T,
+    pub after: T,
+}
+
+impl<T: PartialEq> Change<T> {
+    /// `Some` only when the two values differ
+    pub fn between(before: T, after: T) -> Option<Self> {
+        (before != after).then_some(Self { before, after })
+    }
+}
+
+/// Differences between two versions of a policy, e.g. for attaching to an approval request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyDiff {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub added_rules: Vec<PolicyRule>,
+    pub removed_rules: Vec<PolicyRule>,
+    /// Rules present in both versions (matched by id) whose content changed
+    pub modified_rules: Vec<Change<PolicyRule>>,
+    pub name: Option<Change<String>>,
+    pub description: Option<Change<String>>,
+    pub target: Option<Change<PolicyTarget>>,
+    pub enforcement_level: Option<Change<EnforcementLevel>>,
+    /// Metadata changes, ignoring `created_at` which every version resets
+    pub metadata: Option<Change<PolicyMetadata>>,
+    pub effective_date: Option<Change<Option<DateTime<Utc>>>>,
+    pub expiry_date: Option<Change<Option<DateTime<Utc>>>>,
+}
+
+impl PolicyDiff {
+    /// Whether the two versions are equivalent
+    pub fn is_empty(&self) -> bool {
+        self.added_rules.is_empty()
+            && self.removed_rules.is_empty()
+            && self.modified_rules.is_empty()
+            && self.name.is_none()
+            && self.description.is_none()
+            && self.target.is_none()
+            && self.enforcement_level.is_none()
+            && self.metadata.is_none()
+            && self.effective_date.is_none()
+            && self.expiry_date.is_none()
+    }
+}
+
+impl std::fmt::Display for PolicyDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let date = |d: &Option<DateTime<Utc>>| d.map_or("none".to_string(), |d| d.to_rfc3339());
+
+        writeln!(f, "Policy v{} -> v{}", self.from_version, self.to_version)?;
+        if self.is_empty() {
+            return writeln!(f, "  no changes");
+        }
+        if let Some(change) = &self.name {
+            writeln!(f, "  name: '{}' -> '{}'", change.before, change.after)?;
+        }
+        if let Some(change) = &self.description {
+            writeln!(f, "  description: '{}' -> '{}'", change.before, change.after)?;
+        }
+        if let Some(change) = &self.target {
+            writeln!(f, "  target: {:?} -> {:?}", change.before, change.after)?;
+        }
+        if let Some(change) = &self.enforcement_level {
+            writeln!(f, "  enforcement level: {:?} -> {:?}", change.before, change.after)?;
+        }
+        if let Some(change) = &self.effective_date {
+            writeln!(f, "  effective date: {} -> {}", date(&change.before), date(&change.after))?;
+        }
+        if let Some(change) = &self.expiry_date {
+            writeln!(f, "  expiry date: {} -> {}", date(&change.before), date(&change.after))?;
+        }
+        if self.metadata.is_some() {
+            writeln!(f, "  metadata changed")?;
+        }
+        for rule in &self.added_rules {
+            writeln!(f, "  + rule '{}' ({})", rule.name, rule.id)?;
+        }
+        for rule in &self.removed_rules {
+            writeln!(f, "  - rule '{}' ({})", rule.name, rule.id)?;
+        }
+        for change in &self.modified_rules {
+            writeln!(f, "  ~ rule '{}' ({})", change.after.name, change.after.id)?;
+        }
+        Ok(())
+    }
+}
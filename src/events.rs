@@ -1,11 +1,44 @@
 //! Events in the policy domain
 
+use crate::entities::{PolicyEvaluation, PolicyTemplate};
 use crate::value_objects::*;
 use chrono::{DateTime, Utc};
 use cim_domain::{DomainEvent, MessageIdentity};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
+/// Schema version written for newly persisted events
+pub const EVENT_SCHEMA_VERSION: u16 = 1;
+
+/// Persisted form of an event with its envelope metadata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventEnvelope<E> {
+    pub event: E,
+    pub message_identity: MessageIdentity,
+    pub recorded_at: DateTime<Utc>,
+    pub schema_version: u16,
+}
+
+impl<E> EventEnvelope<E> {
+    /// Wrap an event recorded now at the current schema version
+    pub fn new(event: E, message_identity: MessageIdentity) -> Self {
+        Self {
+            event,
+            message_identity,
+            recorded_at: Utc::now(),
+            schema_version: EVENT_SCHEMA_VERSION,
+        }
+    }
+}
+
+impl From<PolicyEvent> for EventEnvelope<PolicyEvent> {
+    fn from(event: PolicyEvent) -> Self {
+        let identity = event.identity().clone();
+        Self::new(event, identity)
+    }
+}
+
 /// Base event type for all policy events
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "event_type")]
@@ -14,10 +47,16 @@ pub enum PolicyEvent {
     PolicyCreated(PolicyCreated),
     PolicyUpdated(PolicyUpdated),
     PolicyApproved(PolicyApproved),
+    PolicyApprovalRecorded(PolicyApprovalRecorded),
+    PolicyApprovalDelegated(PolicyApprovalDelegated),
+    PolicyExternalApprovalRequested(PolicyExternalApprovalRequested),
+    PolicyExternalApprovalVerified(PolicyExternalApprovalVerified),
+    PolicyExternalApprovalExpired(PolicyExternalApprovalExpired),
     PolicyActivated(PolicyActivated),
     PolicySuspended(PolicySuspended),
     PolicyRevoked(PolicyRevoked),
     PolicyArchived(PolicyArchived),
+    PolicyExpiringSoon(PolicyExpiringSoon),
 
     // Evaluation events
     PolicyEvaluated(PolicyEvaluated),
@@ -28,12 +67,92 @@ pub enum PolicyEvent {
     PolicyExemptionGranted(PolicyExemptionGranted),
     PolicyExemptionRevoked(PolicyExemptionRevoked),
     PolicyExemptionExpired(PolicyExemptionExpired),
+    PolicyExemptionRenewed(PolicyExemptionRenewed),
+    PolicyExemptionUsed(PolicyExemptionUsed),
+    PolicyExemptionExhausted(PolicyExemptionExhausted),
 
     // PolicySet events
     PolicySetCreated(PolicySetCreated),
     PolicyAddedToSet(PolicyAddedToSet),
     PolicyRemovedFromSet(PolicyRemovedFromSet),
     PolicyConflictDetected(PolicyConflictDetected),
+
+    // Template events
+    PolicyTemplateSaved(PolicyTemplateSaved),
+    PolicyTemplateDeleted(PolicyTemplateDeleted),
+    PolicyInstantiatedFromTemplate(PolicyInstantiatedFromTemplate),
+}
+
+impl PolicyEvent {
+    /// Unique id of the event
+    pub fn event_id(&self) -> Uuid {
+        match self {
+            PolicyEvent::PolicyCreated(e) => e.event_id,
+            PolicyEvent::PolicyUpdated(e) => e.event_id,
+            PolicyEvent::PolicyApproved(e) => e.event_id,
+            PolicyEvent::PolicyApprovalRecorded(e) => e.event_id,
+            PolicyEvent::PolicyApprovalDelegated(e) => e.event_id,
+            PolicyEvent::PolicyExternalApprovalRequested(e) => e.event_id,
+            PolicyEvent::PolicyExternalApprovalVerified(e) => e.event_id,
+            PolicyEvent::PolicyExternalApprovalExpired(e) => e.event_id,
+            PolicyEvent::PolicyActivated(e) => e.event_id,
+            PolicyEvent::PolicySuspended(e) => e.event_id,
+            PolicyEvent::PolicyRevoked(e) => e.event_id,
+            PolicyEvent::PolicyArchived(e) => e.event_id,
+            PolicyEvent::PolicyExpiringSoon(e) => e.event_id,
+            PolicyEvent::PolicyEvaluated(e) => e.event_id,
+            PolicyEvent::PolicyViolationDetected(e) => e.event_id,
+            PolicyEvent::PolicyCompliancePassed(e) => e.event_id,
+            PolicyEvent::PolicyExemptionGranted(e) => e.event_id,
+            PolicyEvent::PolicyExemptionRevoked(e) => e.event_id,
+            PolicyEvent::PolicyExemptionExpired(e) => e.event_id,
+            PolicyEvent::PolicyExemptionRenewed(e) => e.event_id,
+            PolicyEvent::PolicyExemptionUsed(e) => e.event_id,
+            PolicyEvent::PolicyExemptionExhausted(e) => e.event_id,
+            PolicyEvent::PolicySetCreated(e) => e.event_id,
+            PolicyEvent::PolicyAddedToSet(e) => e.event_id,
+            PolicyEvent::PolicyRemovedFromSet(e) => e.event_id,
+            PolicyEvent::PolicyConflictDetected(e) => e.event_id,
+            PolicyEvent::PolicyTemplateSaved(e) => e.event_id,
+            PolicyEvent::PolicyTemplateDeleted(e) => e.event_id,
+            PolicyEvent::PolicyInstantiatedFromTemplate(e) => e.event_id,
+        }
+    }
+
+    /// Message identity (correlation/causation) carried by the event
+    pub fn identity(&self) -> &MessageIdentity {
+        match self {
+            PolicyEvent::PolicyCreated(e) => &e.identity,
+            PolicyEvent::PolicyUpdated(e) => &e.identity,
+            PolicyEvent::PolicyApproved(e) => &e.identity,
+            PolicyEvent::PolicyApprovalRecorded(e) => &e.identity,
+            PolicyEvent::PolicyApprovalDelegated(e) => &e.identity,
+            PolicyEvent::PolicyExternalApprovalRequested(e) => &e.identity,
+            PolicyEvent::PolicyExternalApprovalVerified(e) => &e.identity,
+            PolicyEvent::PolicyExternalApprovalExpired(e) => &e.identity,
+            PolicyEvent::PolicyActivated(e) => &e.identity,
+            PolicyEvent::PolicySuspended(e) => &e.identity,
+            PolicyEvent::PolicyRevoked(e) => &e.identity,
+            PolicyEvent::PolicyArchived(e) => &e.identity,
+            PolicyEvent::PolicyExpiringSoon(e) => &e.identity,
+            PolicyEvent::PolicyEvaluated(e) => &e.identity,
+            PolicyEvent::PolicyViolationDetected(e) => &e.identity,
+            PolicyEvent::PolicyCompliancePassed(e) => &e.identity,
+            PolicyEvent::PolicyExemptionGranted(e) => &e.identity,
+            PolicyEvent::PolicyExemptionRevoked(e) => &e.identity,
+            PolicyEvent::PolicyExemptionExpired(e) => &e.identity,
+            PolicyEvent::PolicyExemptionRenewed(e) => &e.identity,
+            PolicyEvent::PolicyExemptionUsed(e) => &e.identity,
+            PolicyEvent::PolicyExemptionExhausted(e) => &e.identity,
+            PolicyEvent::PolicySetCreated(e) => &e.identity,
+            PolicyEvent::PolicyAddedToSet(e) => &e.identity,
+            PolicyEvent::PolicyRemovedFromSet(e) => &e.identity,
+            PolicyEvent::PolicyConflictDetected(e) => &e.identity,
+            PolicyEvent::PolicyTemplateSaved(e) => &e.identity,
+            PolicyEvent::PolicyTemplateDeleted(e) => &e.identity,
+            PolicyEvent::PolicyInstantiatedFromTemplate(e) => &e.identity,
+        }
+    }
 }
 
 impl DomainEvent for PolicyEvent {
@@ -42,20 +161,32 @@ impl DomainEvent for PolicyEvent {
             PolicyEvent::PolicyCreated(_) => "PolicyCreated",
             PolicyEvent::PolicyUpdated(_) => "PolicyUpdated",
             PolicyEvent::PolicyApproved(_) => "PolicyApproved",
+            PolicyEvent::PolicyApprovalRecorded(_) => "PolicyApprovalRecorded",
+            PolicyEvent::PolicyApprovalDelegated(_) => "PolicyApprovalDelegated",
+            PolicyEvent::PolicyExternalApprovalRequested(_) => "PolicyExternalApprovalRequested",
+            PolicyEvent::PolicyExternalApprovalVerified(_) => "PolicyExternalApprovalVerified",
+            PolicyEvent::PolicyExternalApprovalExpired(_) => "PolicyExternalApprovalExpired",
             PolicyEvent::PolicyActivated(_) => "PolicyActivated",
             PolicyEvent::PolicySuspended(_) => "PolicySuspended",
             PolicyEvent::PolicyRevoked(_) => "PolicyRevoked",
             PolicyEvent::PolicyArchived(_) => "PolicyArchived",
+            PolicyEvent::PolicyExpiringSoon(_) => "PolicyExpiringSoon",
             PolicyEvent::PolicyEvaluated(_) => "PolicyEvaluated",
             PolicyEvent::PolicyViolationDetected(_) => "PolicyViolationDetected",
             PolicyEvent::PolicyCompliancePassed(_) => "PolicyCompliancePassed",
             PolicyEvent::PolicyExemptionGranted(_) => "PolicyExemptionGranted",
             PolicyEvent::PolicyExemptionRevoked(_) => "PolicyExemptionRevoked",
             PolicyEvent::PolicyExemptionExpired(_) => "PolicyExemptionExpired",
+            PolicyEvent::PolicyExemptionRenewed(_) => "PolicyExemptionRenewed",
+            PolicyEvent::PolicyExemptionUsed(_) => "PolicyExemptionUsed",
+            PolicyEvent::PolicyExemptionExhausted(_) => "PolicyExemptionExhausted",
             PolicyEvent::PolicySetCreated(_) => "PolicySetCreated",
             PolicyEvent::PolicyAddedToSet(_) => "PolicyAddedToSet",
             PolicyEvent::PolicyRemovedFromSet(_) => "PolicyRemovedFromSet",
             PolicyEvent::PolicyConflictDetected(_) => "PolicyConflictDetected",
+            PolicyEvent::PolicyTemplateSaved(_) => "PolicyTemplateSaved",
+            PolicyEvent::PolicyTemplateDeleted(_) => "PolicyTemplateDeleted",
+            PolicyEvent::PolicyInstantiatedFromTemplate(_) => "PolicyInstantiatedFromTemplate",
         }
     }
 
@@ -64,20 +195,32 @@ impl DomainEvent for PolicyEvent {
             PolicyEvent::PolicyCreated(e) => e.policy_id.0,
             PolicyEvent::PolicyUpdated(e) => e.policy_id.0,
             PolicyEvent::PolicyApproved(e) => e.policy_id.0,
+            PolicyEvent::PolicyApprovalRecorded(e) => e.policy_id.0,
+            PolicyEvent::PolicyApprovalDelegated(e) => e.policy_id.0,
+            PolicyEvent::PolicyExternalApprovalRequested(e) => e.policy_id.0,
+            PolicyEvent::PolicyExternalApprovalVerified(e) => e.policy_id.0,
+            PolicyEvent::PolicyExternalApprovalExpired(e) => e.policy_id.0,
             PolicyEvent::PolicyActivated(e) => e.policy_id.0,
             PolicyEvent::PolicySuspended(e) => e.policy_id.0,
             PolicyEvent::PolicyRevoked(e) => e.policy_id.0,
             PolicyEvent::PolicyArchived(e) => e.policy_id.0,
+            PolicyEvent::PolicyExpiringSoon(e) => e.policy_id.0,
             PolicyEvent::PolicyEvaluated(e) => e.policy_id.0,
             PolicyEvent::PolicyViolationDetected(e) => e.policy_id.0,
             PolicyEvent::PolicyCompliancePassed(e) => e.policy_id.0,
             PolicyEvent::PolicyExemptionGranted(e) => e.policy_id.0,
             PolicyEvent::PolicyExemptionRevoked(e) => e.exemption_id.0,
             PolicyEvent::PolicyExemptionExpired(e) => e.exemption_id.0,
+            PolicyEvent::PolicyExemptionRenewed(e) => e.exemption_id.0,
+            PolicyEvent::PolicyExemptionUsed(e) => e.exemption_id.0,
+            PolicyEvent::PolicyExemptionExhausted(e) => e.exemption_id.0,
             PolicyEvent::PolicySetCreated(e) => e.policy_set_id.0,
             PolicyEvent::PolicyAddedToSet(e) => e.policy_set_id.0,
             PolicyEvent::PolicyRemovedFromSet(e) => e.policy_set_id.0,
             PolicyEvent::PolicyConflictDetected(e) => e.conflict_id,
+            PolicyEvent::PolicyTemplateSaved(e) => e.template.id,
+            PolicyEvent::PolicyTemplateDeleted(e) => e.template_id,
+            PolicyEvent::PolicyInstantiatedFromTemplate(e) => e.policy_id.0,
         }
     }
 }
@@ -94,6 +237,8 @@ pub struct PolicyCreated {
     pub policy_type: String,
     pub created_by: String,
     pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub approval_requirements: ApprovalRequirements,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -124,6 +269,66 @@ pub struct PolicyApproved {
     pub approval_notes: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyApprovalRecorded {
+    pub event_id: Uuid,
+    pub identity: MessageIdentity,
+    pub policy_id: PolicyId,
+    pub approver: String,
+    pub role: Option<String>,
+    pub external: bool,
+    pub recorded_at: DateTime<Utc>,
+    #[serde(default)]
+    pub approver_kind: Option<ApproverKind>,
+}
+
+/// A required approver delegated their approval to someone else
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyApprovalDelegated {
+    pub event_id: Uuid,
+    pub identity: MessageIdentity,
+    pub policy_id: PolicyId,
+    pub from: String,
+    pub to: String,
+    pub role: Option<String>,
+    pub valid_until: DateTime<Utc>,
+    pub delegated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyExternalApprovalRequested {
+    pub event_id: Uuid,
+    pub identity: MessageIdentity,
+    pub policy_id: PolicyId,
+    pub requested_by: String,
+    pub requested_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// An external approver answered a pending approval request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyExternalApprovalVerified {
+    pub event_id: Uuid,
+    pub identity: MessageIdentity,
+    pub policy_id: PolicyId,
+    /// Event id of the answered `PolicyExternalApprovalRequested`
+    pub request_id: Uuid,
+    pub verifier: String,
+    pub method: String,
+    pub verified_at: DateTime<Utc>,
+}
+
+/// A pending external approval request expired without an answer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyExternalApprovalExpired {
+    pub event_id: Uuid,
+    pub identity: MessageIdentity,
+    pub policy_id: PolicyId,
+    /// Event id of the expired `PolicyExternalApprovalRequested`
+    pub request_id: Uuid,
+    pub expired_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PolicyActivated {
     pub event_id: Uuid,
@@ -167,6 +372,17 @@ pub struct PolicyArchived {
     pub retention_period_days: Option<u32>,
 }
 
+/// Event: A policy will stop being effective soon
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyExpiringSoon {
+    pub event_id: Uuid,
+    pub identity: MessageIdentity,
+    pub policy_id: PolicyId,
+    pub expires_at: DateTime<Utc>,
+    pub days_remaining: i64,
+    pub detected_at: DateTime<Utc>,
+}
+
 // Evaluation Events
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -178,9 +394,33 @@ pub struct PolicyEvaluated {
     pub evaluated_at: DateTime<Utc>,
     pub context_hash: String,
     pub result: ComplianceResult,
+    #[serde(default)]
+    pub violation_count: usize,
     pub execution_time_ms: u64,
 }
 
+impl PolicyEvaluated {
+    /// Record an evaluation, keeping only a fingerprint of its context
+    pub fn from_evaluation(evaluation: &PolicyEvaluation, identity: MessageIdentity) -> Self {
+        Self {
+            event_id: Uuid::now_v7(),
+            identity,
+            policy_id: evaluation.policy_id,
+            evaluation_id: evaluation.id,
+            evaluated_at: evaluation.evaluated_at,
+            context_hash: evaluation.context.fingerprint(),
+            result: evaluation.overall_result.clone(),
+            violation_count: evaluation.rule_results.iter().filter(|r| !r.passed).count(),
+            execution_time_ms: evaluation.execution_time_ms,
+        }
+    }
+
+    /// NATS subject for this event
+    pub fn subject(&self) -> &'static str {
+        "policies.policy.evaluated.v1"
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PolicyViolationDetected {
     pub event_id: Uuid,
@@ -214,8 +454,19 @@ pub struct PolicyExemptionGranted {
     pub granted_by: String,
     pub granted_at: DateTime<Utc>,
     pub reason: String,
+    /// Absent in events recorded before justifications were persisted
+    #[serde(default)]
+    pub justification: String,
     pub valid_until: DateTime<Utc>,
     pub risk_acceptance: Option<String>,
+    /// Absent (and therefore global) in events recorded before scopes were persisted
+    #[serde(default)]
+    pub scope: crate::aggregate::ExemptionScope,
+    #[serde(default)]
+    pub conditions: Vec<crate::aggregate::ExemptionCondition>,
+    /// Number of times the exemption may be applied, unlimited if absent
+    #[serde(default)]
+    pub max_uses: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -238,6 +489,42 @@ pub struct PolicyExemptionExpired {
     pub expired_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyExemptionRenewed {
+    pub event_id: Uuid,
+    pub identity: MessageIdentity,
+    pub exemption_id: ExemptionId,
+    pub policy_id: PolicyId,
+    pub renewed_by: String,
+    pub renewed_at: DateTime<Utc>,
+    pub previous_valid_until: DateTime<Utc>,
+    pub new_valid_until: DateTime<Utc>,
+    pub justification: String,
+}
+
+/// A usage-limited exemption turned a failing evaluation into a pass
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyExemptionUsed {
+    pub event_id: Uuid,
+    pub identity: MessageIdentity,
+    pub exemption_id: ExemptionId,
+    pub policy_id: PolicyId,
+    /// Uses recorded including this one
+    pub uses_count: u32,
+    pub used_at: DateTime<Utc>,
+}
+
+/// A usage-limited exemption has been applied its maximum number of times
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyExemptionExhausted {
+    pub event_id: Uuid,
+    pub identity: MessageIdentity,
+    pub exemption_id: ExemptionId,
+    pub policy_id: PolicyId,
+    pub uses_count: u32,
+    pub exhausted_at: DateTime<Utc>,
+}
+
 // PolicySet Events
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -284,6 +571,58 @@ pub struct PolicyConflictDetected {
     pub severity: Severity,
 }
 
+// Template Events
+
+/// Event: A policy template was created or updated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyTemplateSaved {
+    pub event_id: Uuid,
+    pub identity: MessageIdentity,
+    pub template: PolicyTemplate,
+    pub saved_by: String,
+    pub saved_at: DateTime<Utc>,
+}
+
+/// Event: A policy template was deleted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyTemplateDeleted {
+    pub event_id: Uuid,
+    pub identity: MessageIdentity,
+    pub template_id: Uuid,
+    pub deleted_by: String,
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// Event: A policy was instantiated from a template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyInstantiatedFromTemplate {
+    pub event_id: Uuid,
+    pub identity: MessageIdentity,
+    pub policy_id: PolicyId,
+    pub template_id: Uuid,
+    pub template_name: String,
+    /// Resolved parameters, including defaults
+    pub parameters: HashMap<String, Value>,
+    pub instantiated_at: DateTime<Utc>,
+}
+
+impl PolicyInstantiatedFromTemplate {
+    /// NATS subject for this event
+    pub fn subject(&self) -> &'static str {
+        "policies.policy.instantiated_from_template.v1"
+    }
+}
+
+impl DomainEvent for PolicyInstantiatedFromTemplate {
+    fn event_type(&self) -> &'static str {
+        "PolicyInstantiatedFromTemplate"
+    }
+
+    fn aggregate_id(&self) -> Uuid {
+        self.policy_id.0
+    }
+}
+
 // ============= Events from GitHub version =============
 
 /// Event: Policy was assigned to an entity
@@ -360,4 +699,335 @@ pub struct ClaimsRevoked {
     pub revoked_by: String,
     pub reason: String,
     pub revoked_at: DateTime<Utc>,
-}
\ No newline at end of file
+}
+
+/// Test fixtures covering every `PolicyEvent` variant
+///
+/// `variant_index` matches exhaustively, so adding an event fails to compile
+/// until it is listed there; the tests then fail until `event_of_every_type`
+/// produces it, which in turn feeds it through every aggregate's
+/// `apply_event_pure`.
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::*;
+    use crate::sagas::create_root_command;
+
+    /// Number of `PolicyEvent` variants
+    pub(crate) const EVENT_VARIANT_COUNT: usize = 29;
+
+    /// Distinct index for each variant, below `EVENT_VARIANT_COUNT`
+    pub(crate) fn variant_index(event: &PolicyEvent) -> usize {
+        match event {
+            PolicyEvent::PolicyCreated(_) => 0,
+            PolicyEvent::PolicyUpdated(_) => 1,
+            PolicyEvent::PolicyApproved(_) => 2,
+            PolicyEvent::PolicyApprovalRecorded(_) => 3,
+            PolicyEvent::PolicyExternalApprovalRequested(_) => 4,
+            PolicyEvent::PolicyActivated(_) => 5,
+            PolicyEvent::PolicySuspended(_) => 6,
+            PolicyEvent::PolicyRevoked(_) => 7,
+            PolicyEvent::PolicyArchived(_) => 8,
+            PolicyEvent::PolicyExpiringSoon(_) => 9,
+            PolicyEvent::PolicyEvaluated(_) => 10,
+            PolicyEvent::PolicyViolationDetected(_) => 11,
+            PolicyEvent::PolicyCompliancePassed(_) => 12,
+            PolicyEvent::PolicyExemptionGranted(_) => 13,
+            PolicyEvent::PolicyExemptionRevoked(_) => 14,
+            PolicyEvent::PolicyExemptionExpired(_) => 15,
+            PolicyEvent::PolicyExemptionRenewed(_) => 16,
+            PolicyEvent::PolicySetCreated(_) => 17,
+            PolicyEvent::PolicyAddedToSet(_) => 18,
+            PolicyEvent::PolicyRemovedFromSet(_) => 19,
+            PolicyEvent::PolicyConflictDetected(_) => 20,
+            PolicyEvent::PolicyTemplateSaved(_) => 21,
+            PolicyEvent::PolicyTemplateDeleted(_) => 22,
+            PolicyEvent::PolicyInstantiatedFromTemplate(_) => 23,
+            PolicyEvent::PolicyExemptionExhausted(_) => 24,
+            PolicyEvent::PolicyExemptionUsed(_) => 25,
+            PolicyEvent::PolicyExternalApprovalVerified(_) => 26,
+            PolicyEvent::PolicyExternalApprovalExpired(_) => 27,
+            PolicyEvent::PolicyApprovalDelegated(_) => 28,
+        }
+    }
+
+    /// One event of every type, each with `id` as its aggregate id
+    pub(crate) fn event_of_every_type(id: Uuid) -> Vec<PolicyEvent> {
+        let now = Utc::now();
+        let identity = create_root_command;
+        let mut template = PolicyTemplate::new("Template", "A template");
+        template.id = id;
+
+        vec![
+            PolicyEvent::PolicyCreated(PolicyCreated {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                policy_id: PolicyId(id),
+                name: "Policy".to_string(),
+                description: String::new(),
+                policy_type: "security".to_string(),
+                created_by: "alice".to_string(),
+                created_at: now,
+                approval_requirements: ApprovalRequirements::default(),
+            }),
+            PolicyEvent::PolicyUpdated(PolicyUpdated {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                policy_id: PolicyId(id),
+                version: 2,
+                changes: Vec::new(),
+                updated_by: "alice".to_string(),
+                updated_at: now,
+            }),
+            PolicyEvent::PolicyApproved(PolicyApproved {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                policy_id: PolicyId(id),
+                approved_by: "bob".to_string(),
+                approved_at: now,
+                approval_notes: None,
+            }),
+            PolicyEvent::PolicyApprovalRecorded(PolicyApprovalRecorded {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                policy_id: PolicyId(id),
+                approver: "bob".to_string(),
+                role: None,
+                external: false,
+                recorded_at: now,
+                approver_kind: None,
+            }),
+            PolicyEvent::PolicyApprovalDelegated(PolicyApprovalDelegated {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                policy_id: PolicyId(id),
+                from: "ciso".to_string(),
+                to: "deputy".to_string(),
+                role: None,
+                valid_until: now,
+                delegated_at: now,
+            }),
+            PolicyEvent::PolicyExternalApprovalRequested(PolicyExternalApprovalRequested {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                policy_id: PolicyId(id),
+                requested_by: "bob".to_string(),
+                requested_at: now,
+                expires_at: now,
+            }),
+            PolicyEvent::PolicyExternalApprovalVerified(PolicyExternalApprovalVerified {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                policy_id: PolicyId(id),
+                request_id: Uuid::now_v7(),
+                verifier: "auditor".to_string(),
+                method: "yubikey".to_string(),
+                verified_at: now,
+            }),
+            PolicyEvent::PolicyExternalApprovalExpired(PolicyExternalApprovalExpired {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                policy_id: PolicyId(id),
+                request_id: Uuid::now_v7(),
+                expired_at: now,
+            }),
+            PolicyEvent::PolicyActivated(PolicyActivated {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                policy_id: PolicyId(id),
+                activated_by: "ops".to_string(),
+                activated_at: now,
+                effective_from: now,
+                effective_until: None,
+            }),
+            PolicyEvent::PolicySuspended(PolicySuspended {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                policy_id: PolicyId(id),
+                suspended_by: "ops".to_string(),
+                suspended_at: now,
+                reason: "incident".to_string(),
+                expected_resume_date: None,
+            }),
+            PolicyEvent::PolicyRevoked(PolicyRevoked {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                policy_id: PolicyId(id),
+                revoked_by: "ops".to_string(),
+                revoked_at: now,
+                reason: "superseded".to_string(),
+                immediate: true,
+            }),
+            PolicyEvent::PolicyArchived(PolicyArchived {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                policy_id: PolicyId(id),
+                archived_by: "ops".to_string(),
+                archived_at: now,
+                retention_period_days: None,
+            }),
+            PolicyEvent::PolicyExpiringSoon(PolicyExpiringSoon {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                policy_id: PolicyId(id),
+                expires_at: now,
+                days_remaining: 0,
+                detected_at: now,
+            }),
+            PolicyEvent::PolicyEvaluated(PolicyEvaluated {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                policy_id: PolicyId(id),
+                evaluation_id: Uuid::now_v7(),
+                evaluated_at: now,
+                context_hash: String::new(),
+                result: ComplianceResult::Compliant,
+                violation_count: 0,
+                execution_time_ms: 0,
+            }),
+            PolicyEvent::PolicyViolationDetected(PolicyViolationDetected {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                policy_id: PolicyId(id),
+                violation_id: Uuid::now_v7(),
+                detected_at: now,
+                violations: Vec::new(),
+                severity: Severity::Low,
+                enforcement_action: None,
+            }),
+            PolicyEvent::PolicyCompliancePassed(PolicyCompliancePassed {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                policy_id: PolicyId(id),
+                evaluation_id: Uuid::now_v7(),
+                passed_at: now,
+                rules_evaluated: 1,
+            }),
+            PolicyEvent::PolicyExemptionGranted(PolicyExemptionGranted {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                exemption_id: ExemptionId::new(),
+                policy_id: PolicyId(id),
+                granted_by: "ciso".to_string(),
+                granted_at: now,
+                reason: "legacy".to_string(),
+                justification: "replacement scheduled".to_string(),
+                valid_until: now,
+                risk_acceptance: None,
+                scope: crate::aggregate::ExemptionScope::Global,
+                conditions: Vec::new(),
+                max_uses: None,
+            }),
+            PolicyEvent::PolicyExemptionRevoked(PolicyExemptionRevoked {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                exemption_id: ExemptionId(id),
+                policy_id: PolicyId::new(),
+                revoked_by: "ciso".to_string(),
+                revoked_at: now,
+                reason: "fixed".to_string(),
+            }),
+            PolicyEvent::PolicyExemptionExpired(PolicyExemptionExpired {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                exemption_id: ExemptionId(id),
+                policy_id: PolicyId::new(),
+                expired_at: now,
+            }),
+            PolicyEvent::PolicyExemptionRenewed(PolicyExemptionRenewed {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                exemption_id: ExemptionId(id),
+                policy_id: PolicyId::new(),
+                renewed_by: "ciso".to_string(),
+                renewed_at: now,
+                previous_valid_until: now,
+                new_valid_until: now,
+                justification: "still needed".to_string(),
+            }),
+            PolicyEvent::PolicyExemptionUsed(PolicyExemptionUsed {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                exemption_id: ExemptionId(id),
+                policy_id: PolicyId::new(),
+                uses_count: 2,
+                used_at: now,
+            }),
+            PolicyEvent::PolicyExemptionExhausted(PolicyExemptionExhausted {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                exemption_id: ExemptionId(id),
+                policy_id: PolicyId::new(),
+                uses_count: 3,
+                exhausted_at: now,
+            }),
+            PolicyEvent::PolicySetCreated(PolicySetCreated {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                policy_set_id: PolicySetId(id),
+                name: "Set".to_string(),
+                description: String::new(),
+                created_by: "alice".to_string(),
+                created_at: now,
+            }),
+            PolicyEvent::PolicyAddedToSet(PolicyAddedToSet {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                policy_set_id: PolicySetId(id),
+                policy_id: PolicyId::new(),
+                added_at: now,
+                added_by: "alice".to_string(),
+            }),
+            PolicyEvent::PolicyRemovedFromSet(PolicyRemovedFromSet {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                policy_set_id: PolicySetId(id),
+                policy_id: PolicyId::new(),
+                removed_at: now,
+                removed_by: "alice".to_string(),
+                reason: None,
+            }),
+            PolicyEvent::PolicyConflictDetected(PolicyConflictDetected {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                conflict_id: id,
+                policy_ids: Vec::new(),
+                conflict_type: "contradiction".to_string(),
+                description: String::new(),
+                detected_at: now,
+                severity: Severity::High,
+            }),
+            PolicyEvent::PolicyTemplateSaved(PolicyTemplateSaved {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                template,
+                saved_by: "alice".to_string(),
+                saved_at: now,
+            }),
+            PolicyEvent::PolicyTemplateDeleted(PolicyTemplateDeleted {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                template_id: id,
+                deleted_by: "alice".to_string(),
+                deleted_at: now,
+            }),
+            PolicyEvent::PolicyInstantiatedFromTemplate(PolicyInstantiatedFromTemplate {
+                event_id: Uuid::now_v7(),
+                identity: identity(),
+                policy_id: PolicyId(id),
+                template_id: Uuid::now_v7(),
+                template_name: "Template".to_string(),
+                parameters: HashMap::new(),
+                instantiated_at: now,
+            }),
+        ]
+    }
+
+    #[test]
+    fn test_fixture_covers_every_variant() {
+        let mut seen = [false; EVENT_VARIANT_COUNT];
+        for event in event_of_every_type(Uuid::now_v7()) {
+            seen[variant_index(&event)] = true;
+        }
+        assert!(seen.iter().all(|&covered| covered), "missing variants: {:?}", seen);
+    }
+}
@@ -2,8 +2,9 @@
 
 use crate::aggregate::PolicyExemption;
 use crate::events::{PolicyEvent, PolicyExemptionGranted};
-use crate::infrastructure::nats_integration::{NatsError, NatsEventStore};
+use crate::infrastructure::nats_integration::{AggregateSnapshot, NatsError, NatsEventStore};
 use crate::value_objects::ExemptionId;
+use chrono::Utc;
 use cim_domain::DomainEvent;
 use std::sync::Arc;
 use thiserror::Error;
@@ -19,12 +20,16 @@ pub enum RepositoryError {
 
     #[error("Invalid event sequence: {0}")]
     InvalidSequence(String),
+
+    #[error("Concurrency conflict: expected version {expected}, found {actual}")]
+    ConcurrencyConflict { expected: u64, actual: u64 },
 }
 
 /// Repository for policy exemption aggregates using event sourcing
 pub struct ExemptionRepository {
     event_store: Arc<NatsEventStore>,
     snapshot_frequency: u64,
+    max_snapshots: u64,
 }
 
 impl ExemptionRepository {
@@ -33,6 +38,7 @@ impl ExemptionRepository {
         Self {
             event_store,
             snapshot_frequency: 100,
+            max_snapshots: 3,
         }
     }
 
@@ -42,32 +48,33 @@ impl ExemptionRepository {
         self
     }
 
+    /// Set how many snapshots are retained (at least one is always kept)
+    pub fn with_max_snapshots(mut self, max_snapshots: u64) -> Self {
+        self.max_snapshots = max_snapshots.max(1);
+        self
+    }
+
     /// Load an exemption by reconstructing it from its event history
+    ///
+    /// The exemption's `last_sequence` is set to the stream sequence of its
+    /// last event, ready to pass back to `save_at`.
     pub async fn load(&self, exemption_id: ExemptionId) -> Result<Option<PolicyExemption>, RepositoryError> {
-        let uuid_id = exemption_id.0;
-        let events = self.event_store.load_events(uuid_id).await?;
+        let replay = self
+            .event_store
+            .load_since_snapshot::<PolicyExemption>(exemption_id.0)
+            .await?;
+        let event_count = replay.event_count();
 
-        if events.is_empty() {
-            return Ok(None);
-        }
-
-        let mut exemption = None;
+        let exemption = replay_exemption(replay.snapshot.map(|s| s.state), &replay.events)?
+            .map(|exemption| PolicyExemption {
+                last_sequence: replay.last_sequence,
+                ..exemption
+            });
 
-        for event in events {
-            match &exemption {
-                None => {
-                    if let PolicyEvent::PolicyExemptionGranted(granted) = &event {
-                        exemption = Some(self.create_from_granted_event(granted)?);
-                    } else {
-                        return Err(RepositoryError::InvalidSequence(format!(
-                            "First event must be PolicyExemptionGranted, got: {}",
-                            event.event_type()
-                        )));
-                    }
-                }
-                Some(ex) => {
-                    exemption = Some(ex.apply_event_pure(&event)?);
-                }
+        if let Some(exemption) = &exemption {
+            if replay.events.len() as u64 >= self.snapshot_frequency {
+                self.take_snapshot(exemption, event_count, replay.last_sequence)
+                    .await?;
             }
         }
 
@@ -82,17 +89,93 @@ impl ExemptionRepository {
         Ok(())
     }
 
-    /// Create an exemption from a PolicyExemptionGranted event
-    fn create_from_granted_event(&self, event: &PolicyExemptionGranted) -> Result<PolicyExemption, RepositoryError> {
-        let exemption = PolicyExemption::new(
-            event.policy_id,
-            &event.reason,
-            &event.reason, // Use reason as justification for now
-            &event.granted_by,
-            event.valid_until,
-        );
-
-        let policy_event = PolicyEvent::PolicyExemptionGranted(event.clone());
-        Ok(exemption.apply_event_pure(&policy_event)?)
+    /// Save events for an exemption only if it is still at `expected_sequence`
+    ///
+    /// If another writer has appended since, `ConcurrencyConflict` is
+    /// returned and nothing is written; callers should reload and retry.
+    pub async fn save_at(
+        &self,
+        exemption_id: ExemptionId,
+        events: Vec<PolicyEvent>,
+        expected_sequence: u64,
+    ) -> Result<(), RepositoryError> {
+        match self
+            .event_store
+            .append_events_at(exemption_id.0, events, expected_sequence)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(NatsError::WrongSequence { .. }) => Err(RepositoryError::ConcurrencyConflict {
+                expected: expected_sequence,
+                actual: self.event_store.last_sequence(exemption_id.0).await?,
+            }),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Store a snapshot and drop snapshots outside the retained window
+    async fn take_snapshot(
+        &self,
+        exemption: &PolicyExemption,
+        event_count: u64,
+        stream_sequence: u64,
+    ) -> Result<(), RepositoryError> {
+        self.event_store
+            .save_snapshot(&AggregateSnapshot {
+                aggregate_id: exemption.id.0,
+                event_count,
+                stream_sequence,
+                state: exemption.clone(),
+                taken_at: Utc::now(),
+            })
+            .await?;
+        self.event_store
+            .compact_snapshots(exemption.id.0, self.max_snapshots)
+            .await?;
+        Ok(())
     }
 }
+
+/// Apply events on top of an optional starting state (pure function)
+///
+/// Without a starting state the first event must be PolicyExemptionGranted.
+pub fn replay_exemption(
+    base: Option<PolicyExemption>,
+    events: &[PolicyEvent],
+) -> Result<Option<PolicyExemption>, RepositoryError> {
+    let mut exemption = base;
+
+    for event in events {
+        match &exemption {
+            None => {
+                if let PolicyEvent::PolicyExemptionGranted(granted) = event {
+                    exemption = Some(create_from_granted_event(granted)?);
+                } else {
+                    return Err(RepositoryError::InvalidSequence(format!(
+                        "First event must be PolicyExemptionGranted, got: {}",
+                        event.event_type()
+                    )));
+                }
+            }
+            Some(ex) => {
+                exemption = Some(ex.apply_event_pure(event)?);
+            }
+        }
+    }
+
+    Ok(exemption)
+}
+
+/// Create an exemption from a PolicyExemptionGranted event
+fn create_from_granted_event(event: &PolicyExemptionGranted) -> Result<PolicyExemption, RepositoryError> {
+    let exemption = PolicyExemption::new(
+        event.policy_id,
+        &event.reason,
+        &event.justification,
+        &event.granted_by,
+        event.valid_until,
+    );
+
+    let policy_event = PolicyEvent::PolicyExemptionGranted(event.clone());
+    Ok(exemption.apply_event_pure(&policy_event)?)
+}
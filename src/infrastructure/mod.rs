@@ -4,8 +4,17 @@ pub mod nats_integration;
 pub mod policy_repository;
 pub mod policy_set_repository;
 pub mod exemption_repository;
+pub mod projection_consumer;
+pub mod template_repository;
+pub mod upcasting;
 
-pub use nats_integration::{NatsError, NatsEventStore};
+pub use nats_integration::{
+    decode_stored_event, AggregateSnapshot, EventCodec, NatsError, NatsEventStore,
+    SnapshotReplay,
+};
 pub use policy_repository::PolicyRepository;
 pub use policy_set_repository::PolicySetRepository;
 pub use exemption_repository::ExemptionRepository;
+pub use projection_consumer::ProjectionConsumer;
+pub use template_repository::TemplateRepository;
+pub use upcasting::{EventUpcaster, UpcasterRegistry, DefaultFieldUpcaster, EVENT_SCHEMA_VERSION};
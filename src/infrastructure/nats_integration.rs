@@ -1,9 +1,12 @@
 //! NATS JetStream integration for policy event sourcing
 
-use crate::events::PolicyEvent;
+use crate::events::{EventEnvelope, PolicyEvent};
+use crate::infrastructure::upcasting::UpcasterRegistry;
 use async_nats::jetstream::{self, stream::Stream};
+use chrono::{DateTime, Utc};
 use cim_domain::DomainEvent;
 use futures::StreamExt;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -21,6 +24,12 @@ pub enum NatsError {
 
     #[error("Event not found: {0}")]
     EventNotFound(String),
+
+    #[error("Codec error: {0}")]
+    Codec(String),
+
+    #[error("Aggregate {aggregate_id} is no longer at stream sequence {expected}")]
+    WrongSequence { aggregate_id: Uuid, expected: u64 },
 }
 
 impl From<async_nats::Error> for NatsError {
@@ -47,11 +56,113 @@ impl From<async_nats::jetstream::stream::ConsumerError> for NatsError {
     }
 }
 
+/// Encoding used for event payloads in the stream
+///
+/// The codec is recorded in each message's `codec` header, so a store can
+/// read events written with any codec. Messages without the header are JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EventCodec {
+    #[default]
+    Json,
+    Cbor,
+    MessagePack,
+}
+
+impl EventCodec {
+    /// Value of the `codec` message header
+    pub fn header_value(&self) -> &'static str {
+        match self {
+            EventCodec::Json => "json",
+            EventCodec::Cbor => "cbor",
+            EventCodec::MessagePack => "msgpack",
+        }
+    }
+
+    /// Parse a `codec` message header
+    pub fn from_header(value: &str) -> Option<Self> {
+        match value {
+            "json" => Some(EventCodec::Json),
+            "cbor" => Some(EventCodec::Cbor),
+            "msgpack" => Some(EventCodec::MessagePack),
+            _ => None,
+        }
+    }
+
+    /// Encode a value
+    ///
+    /// Values go through their JSON representation first, so identifiers and
+    /// timestamps keep the same textual form under every codec and decoding
+    /// can feed the JSON-based upcasters.
+    pub fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, NatsError> {
+        let json = serde_json::to_value(value)?;
+        match self {
+            EventCodec::Json => Ok(serde_json::to_vec(&json)?),
+            EventCodec::Cbor => {
+                let mut payload = Vec::new();
+                ciborium::into_writer(&json, &mut payload)
+                    .map_err(|e| NatsError::Codec(e.to_string()))?;
+                Ok(payload)
+            }
+            EventCodec::MessagePack => {
+                rmp_serde::to_vec(&json).map_err(|e| NatsError::Codec(e.to_string()))
+            }
+        }
+    }
+
+    /// Decode a payload into its JSON representation
+    pub fn decode(&self, payload: &[u8]) -> Result<serde_json::Value, NatsError> {
+        match self {
+            EventCodec::Json => Ok(serde_json::from_slice(payload)?),
+            EventCodec::Cbor => {
+                ciborium::from_reader(payload).map_err(|e| NatsError::Codec(e.to_string()))
+            }
+            EventCodec::MessagePack => {
+                rmp_serde::from_slice(payload).map_err(|e| NatsError::Codec(e.to_string()))
+            }
+        }
+    }
+}
+
+/// Point-in-time state of an aggregate, used to shorten event replay
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregateSnapshot<T> {
+    pub aggregate_id: Uuid,
+    /// Number of the aggregate's events already applied to `state`
+    pub event_count: u64,
+    /// Stream sequence of the last event applied to `state`
+    ///
+    /// Replay resumes after it. Snapshots written before this was recorded
+    /// read as 0 and are not used.
+    #[serde(default)]
+    pub stream_sequence: u64,
+    pub state: T,
+    pub taken_at: DateTime<Utc>,
+}
+
+/// An aggregate's latest usable snapshot and the events stored after it
+#[derive(Debug, Clone)]
+pub struct SnapshotReplay<T> {
+    pub snapshot: Option<AggregateSnapshot<T>>,
+    pub events: Vec<PolicyEvent>,
+    /// Stream sequence of the aggregate's last event (0 when it has none)
+    pub last_sequence: u64,
+}
+
+impl<T> SnapshotReplay<T> {
+    /// Number of the aggregate's events once `events` are applied
+    pub fn event_count(&self) -> u64 {
+        self.snapshot.as_ref().map_or(0, |s| s.event_count) + self.events.len() as u64
+    }
+}
+
 /// NATS JetStream event store for policy events
 pub struct NatsEventStore {
     jetstream: jetstream::Context,
     stream: Stream,
+    snapshot_stream: Stream,
     stream_name: String,
+    upcasters: UpcasterRegistry,
+    codec: EventCodec,
 }
 
 impl NatsEventStore {
@@ -68,21 +179,50 @@ impl NatsEventStore {
             })
             .await?;
 
+        let snapshot_stream = jetstream
+            .get_or_create_stream(jetstream::stream::Config {
+                name: format!("{}_SNAPSHOTS", stream_name),
+                subjects: vec!["snapshots.policy.>".to_string()],
+                storage: jetstream::stream::StorageType::File,
+                num_replicas: 1,
+                ..Default::default()
+            })
+            .await?;
+
         Ok(Self {
             jetstream,
             stream,
+            snapshot_stream,
             stream_name,
+            upcasters: UpcasterRegistry::new(),
+            codec: EventCodec::default(),
         })
     }
 
+    /// Check that the event stream can be reached through JetStream
+    pub async fn check_reachable(&self) -> Result<(), NatsError> {
+        self.jetstream
+            .get_stream(&self.stream_name)
+            .await
+            .map(|_| ())
+            .map_err(|e| NatsError::JetStream(e.to_string()))
+    }
+
+    /// Set the codec used for newly appended events (default: JSON)
+    pub fn with_codec(mut self, codec: EventCodec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// Set the upcasters applied to stored events of older schema versions
+    pub fn with_upcasters(mut self, upcasters: UpcasterRegistry) -> Self {
+        self.upcasters = upcasters;
+        self
+    }
+
     /// Append an event to the event store
     pub async fn append_event(&self, event: PolicyEvent) -> Result<(), NatsError> {
-        let subject = self.event_subject(&event);
-        let payload = serde_json::to_vec(&event)?;
-
-        let mut headers = async_nats::HeaderMap::new();
-        headers.insert("event-type", event.event_type());
-        headers.insert("aggregate-id", event.aggregate_id().to_string().as_str());
+        let (subject, headers, payload) = self.encode_event(event)?;
 
         self.jetstream
             .publish_with_headers(subject, headers, payload.into())
@@ -91,17 +231,168 @@ impl NatsEventStore {
         Ok(())
     }
 
+    /// Append events to an aggregate only if its stream is still at `expected_sequence`
+    ///
+    /// `expected_sequence` is the stream sequence of the aggregate's last
+    /// event (0 for a new aggregate). JetStream rejects the first event with
+    /// `WrongSequence` when another writer has appended since, and every
+    /// later event is conditioned on the one published before it. Returns
+    /// the aggregate's new last sequence.
+    ///
+    /// The check spans all of the aggregate's event subjects, which needs
+    /// NATS server 2.11 or later.
+    pub async fn append_events_at(
+        &self,
+        aggregate_id: Uuid,
+        events: Vec<PolicyEvent>,
+        expected_sequence: u64,
+    ) -> Result<u64, NatsError> {
+        let mut last_sequence = expected_sequence;
+
+        for event in events {
+            let (subject, mut headers, payload) = self.encode_event(event)?;
+            headers.insert(
+                async_nats::header::NATS_EXPECTED_LAST_SUBJECT_SEQUENCE,
+                last_sequence.to_string().as_str(),
+            );
+            headers.insert(
+                EXPECTED_LAST_SUBJECT_SEQUENCE_SUBJECT,
+                aggregate_subjects(aggregate_id).as_str(),
+            );
+
+            let ack = self
+                .jetstream
+                .publish_with_headers(subject, headers, payload.into())
+                .await
+                .map_err(|e| conditional_publish_error(e, aggregate_id, last_sequence))?
+                .await
+                .map_err(|e| conditional_publish_error(e, aggregate_id, last_sequence))?;
+            last_sequence = ack.sequence;
+        }
+
+        Ok(last_sequence)
+    }
+
+    /// Build the subject, headers and payload an event is stored with
+    fn encode_event(
+        &self,
+        event: PolicyEvent,
+    ) -> Result<(String, async_nats::HeaderMap, Vec<u8>), NatsError> {
+        let subject = self.event_subject(&event);
+        let envelope = EventEnvelope::from(event);
+        let payload = self.codec.encode(&envelope)?;
+        let event = &envelope.event;
+
+        let mut headers = async_nats::HeaderMap::new();
+        headers.insert("event-type", event.event_type());
+        headers.insert("aggregate-id", event.aggregate_id().to_string().as_str());
+        headers.insert("schema-version", envelope.schema_version.to_string().as_str());
+        headers.insert("codec", self.codec.header_value());
+
+        Ok((subject, headers, payload))
+    }
+
     /// Load all events for a specific aggregate
     pub async fn load_events(&self, aggregate_id: Uuid) -> Result<Vec<PolicyEvent>, NatsError> {
-        let subject_filter = format!("events.policy.{}.*", aggregate_id);
+        Ok(self
+            .load_envelopes(aggregate_id)
+            .await?
+            .into_iter()
+            .map(|envelope| envelope.event)
+            .collect())
+    }
 
-        let consumer = self
-            .stream
-            .create_consumer(jetstream::consumer::pull::Config {
-                filter_subject: subject_filter,
+    /// Load all events for a specific aggregate together with their envelope metadata
+    pub async fn load_envelopes(
+        &self,
+        aggregate_id: Uuid,
+    ) -> Result<Vec<EventEnvelope<PolicyEvent>>, NatsError> {
+        self.load_filtered(jetstream::consumer::pull::Config {
+            filter_subject: aggregate_subjects(aggregate_id),
+            ..Default::default()
+        })
+        .await
+    }
+
+    /// Load an aggregate's events stored after `after_sequence`, paired with
+    /// their stream sequences
+    ///
+    /// Pass 0 to load the whole history.
+    pub async fn load_events_after(
+        &self,
+        aggregate_id: Uuid,
+        after_sequence: u64,
+    ) -> Result<Vec<(u64, PolicyEvent)>, NatsError> {
+        let deliver_policy = match after_sequence {
+            0 => jetstream::consumer::DeliverPolicy::All,
+            sequence => jetstream::consumer::DeliverPolicy::ByStartSequence {
+                start_sequence: sequence + 1,
+            },
+        };
+
+        Ok(self
+            .load_sequenced(jetstream::consumer::pull::Config {
+                filter_subject: aggregate_subjects(aggregate_id),
+                deliver_policy,
                 ..Default::default()
             })
-            .await?;
+            .await?
+            .into_iter()
+            .map(|(sequence, envelope)| (sequence, envelope.event))
+            .collect())
+    }
+
+    /// Stream sequence of an aggregate's last event (0 when it has none)
+    pub async fn last_sequence(&self, aggregate_id: Uuid) -> Result<u64, NatsError> {
+        Ok(self
+            .load_sequenced(jetstream::consumer::pull::Config {
+                filter_subject: aggregate_subjects(aggregate_id),
+                deliver_policy: jetstream::consumer::DeliverPolicy::LastPerSubject,
+                ..Default::default()
+            })
+            .await?
+            .into_iter()
+            .map(|(sequence, _)| sequence)
+            .max()
+            .unwrap_or(0))
+    }
+
+    /// Load all events of the given types across every aggregate, in stream order
+    pub async fn load_events_by_type(
+        &self,
+        event_types: &[&str],
+    ) -> Result<Vec<EventEnvelope<PolicyEvent>>, NatsError> {
+        let subject_filters = event_types
+            .iter()
+            .map(|event_type| format!("events.policy.*.{}", event_type.to_lowercase()))
+            .collect();
+
+        self.load_filtered(jetstream::consumer::pull::Config {
+            filter_subjects: subject_filters,
+            ..Default::default()
+        })
+        .await
+    }
+
+    /// Read every event matched by a consumer configuration
+    async fn load_filtered(
+        &self,
+        config: jetstream::consumer::pull::Config,
+    ) -> Result<Vec<EventEnvelope<PolicyEvent>>, NatsError> {
+        Ok(self
+            .load_sequenced(config)
+            .await?
+            .into_iter()
+            .map(|(_, envelope)| envelope)
+            .collect())
+    }
+
+    /// Read every event matched by a consumer configuration with its stream sequence
+    async fn load_sequenced(
+        &self,
+        config: jetstream::consumer::pull::Config,
+    ) -> Result<Vec<(u64, EventEnvelope<PolicyEvent>)>, NatsError> {
+        let consumer = self.stream.create_consumer(config).await?;
 
         let mut messages = consumer.messages().await.map_err(|e| {
             NatsError::JetStream(format!("Failed to get messages: {}", e))
@@ -112,8 +403,11 @@ impl NatsEventStore {
         while let Some(message) = messages.next().await {
             match message {
                 Ok(msg) => {
-                    let event: PolicyEvent = serde_json::from_slice(&msg.payload)?;
-                    events.push(event);
+                    let sequence = msg
+                        .info()
+                        .map_err(|e| NatsError::JetStream(e.to_string()))?
+                        .stream_sequence;
+                    events.push((sequence, self.decode_message(&msg)?));
                     let _ = msg.ack().await;
                 }
                 Err(e) => {
@@ -126,6 +420,107 @@ impl NatsEventStore {
         Ok(events)
     }
 
+    /// Store a snapshot of an aggregate
+    pub async fn save_snapshot<T: Serialize>(
+        &self,
+        snapshot: &AggregateSnapshot<T>,
+    ) -> Result<(), NatsError> {
+        let payload = serde_json::to_vec(snapshot)?;
+
+        // Wait for the ack so compaction never runs ahead of the new snapshot
+        self.jetstream
+            .publish(snapshot_subject(snapshot.aggregate_id), payload.into())
+            .await?
+            .await?;
+
+        Ok(())
+    }
+
+    /// Load the most recent snapshot of an aggregate, if any
+    pub async fn load_snapshot<T: DeserializeOwned>(
+        &self,
+        aggregate_id: Uuid,
+    ) -> Result<Option<AggregateSnapshot<T>>, NatsError> {
+        match self
+            .snapshot_stream
+            .get_last_raw_message_by_subject(&snapshot_subject(aggregate_id))
+            .await
+        {
+            Ok(message) => Ok(Some(serde_json::from_slice(&message.payload)?)),
+            Err(e) if e.kind() == jetstream::stream::LastRawMessageErrorKind::NoMessageFound => {
+                Ok(None)
+            }
+            Err(e) => Err(NatsError::JetStream(e.to_string())),
+        }
+    }
+
+    /// Load an aggregate's latest snapshot and only the events stored after it
+    ///
+    /// Without a usable snapshot the whole history is loaded.
+    pub async fn load_since_snapshot<T: DeserializeOwned>(
+        &self,
+        aggregate_id: Uuid,
+    ) -> Result<SnapshotReplay<T>, NatsError> {
+        let (snapshot, after) = resume_point(self.load_snapshot::<T>(aggregate_id).await?);
+
+        let (sequences, events): (Vec<u64>, Vec<PolicyEvent>) = self
+            .load_events_after(aggregate_id, after)
+            .await?
+            .into_iter()
+            .unzip();
+
+        Ok(SnapshotReplay {
+            snapshot,
+            events,
+            last_sequence: sequences.last().copied().unwrap_or(after),
+        })
+    }
+
+    /// Delete all but the `keep` most recent snapshots of an aggregate
+    ///
+    /// At least one snapshot is always kept. Returns the number deleted.
+    pub async fn compact_snapshots(&self, aggregate_id: Uuid, keep: u64) -> Result<u64, NatsError> {
+        let response = self
+            .snapshot_stream
+            .purge()
+            .filter(snapshot_subject(aggregate_id))
+            .keep(keep.max(1))
+            .await
+            .map_err(|e| NatsError::JetStream(e.to_string()))?;
+
+        Ok(response.purged)
+    }
+
+    /// Get or create a durable consumer over every policy event
+    ///
+    /// Messages must be acknowledged explicitly, so unacknowledged events are
+    /// redelivered after a restart.
+    pub async fn durable_consumer(
+        &self,
+        durable_name: &str,
+    ) -> Result<jetstream::consumer::PullConsumer, NatsError> {
+        Ok(self
+            .stream
+            .get_or_create_consumer(
+                durable_name,
+                jetstream::consumer::pull::Config {
+                    durable_name: Some(durable_name.to_string()),
+                    filter_subject: "events.policy.>".to_string(),
+                    ack_policy: jetstream::consumer::AckPolicy::Explicit,
+                    ..Default::default()
+                },
+            )
+            .await?)
+    }
+
+    /// Decode a stored message into an envelope, upcasting older schema versions
+    pub fn decode_message(
+        &self,
+        msg: &jetstream::Message,
+    ) -> Result<EventEnvelope<PolicyEvent>, NatsError> {
+        decode_stored_event(&self.upcasters, msg.headers.as_ref(), &msg.payload)
+    }
+
     /// Generate the NATS subject for an event
     ///
     /// Subject pattern: events.policy.{aggregate_id}.{event_type}
@@ -142,3 +537,212 @@ impl NatsEventStore {
     }
 }
 
+/// Snapshot to replay from and the stream sequence to resume after
+///
+/// Snapshots that don't record a stream sequence can't be resumed from, so
+/// the whole history is replayed instead.
+fn resume_point<T>(snapshot: Option<AggregateSnapshot<T>>) -> (Option<AggregateSnapshot<T>>, u64) {
+    match snapshot {
+        Some(snapshot) if snapshot.stream_sequence > 0 => {
+            let after = snapshot.stream_sequence;
+            (Some(snapshot), after)
+        }
+        _ => (None, 0),
+    }
+}
+
+/// Header naming the subject filter `Nats-Expected-Last-Subject-Sequence` applies to
+const EXPECTED_LAST_SUBJECT_SEQUENCE_SUBJECT: &str = "Nats-Expected-Last-Subject-Sequence-Subject";
+
+/// Subject filter covering every event of an aggregate
+fn aggregate_subjects(aggregate_id: Uuid) -> String {
+    format!("events.policy.{}.*", aggregate_id)
+}
+
+/// Map a failed conditional publish, singling out a stale expected sequence
+fn conditional_publish_error(
+    err: jetstream::context::PublishError,
+    aggregate_id: Uuid,
+    expected: u64,
+) -> NatsError {
+    match err.kind() {
+        jetstream::context::PublishErrorKind::WrongLastSequence => {
+            NatsError::WrongSequence { aggregate_id, expected }
+        }
+        _ => NatsError::JetStream(err.to_string()),
+    }
+}
+
+/// Subject snapshots of an aggregate are stored under
+fn snapshot_subject(aggregate_id: Uuid) -> String {
+    format!("snapshots.policy.{}", aggregate_id)
+}
+
+/// Decode a stored event from its message headers and payload
+///
+/// The `codec` header selects the payload encoding (JSON when absent) and
+/// `schema-version` the version of bare legacy payloads. Anything reading
+/// the event stream should decode through here.
+pub fn decode_stored_event(
+    upcasters: &UpcasterRegistry,
+    headers: Option<&async_nats::HeaderMap>,
+    payload: &[u8],
+) -> Result<EventEnvelope<PolicyEvent>, NatsError> {
+    let header = |name: &str| {
+        headers
+            .and_then(|headers| headers.get(name))
+            .map(|value| value.as_str().to_string())
+    };
+
+    // Events written before versioning was introduced are version 1
+    let version = header("schema-version")
+        .and_then(|value| value.parse::<u16>().ok())
+        .unwrap_or(1);
+    let codec = match header("codec") {
+        Some(value) => EventCodec::from_header(&value)
+            .ok_or_else(|| NatsError::Codec(format!("Unknown codec: {}", value)))?,
+        None => EventCodec::Json,
+    };
+
+    decode_envelope(upcasters, codec.decode(payload)?, version)
+}
+
+/// Decode a stored payload into an envelope, upcasting older schema versions
+///
+/// Payloads written before envelopes were introduced are bare events; they
+/// are wrapped using the event's own identity and the header version.
+fn decode_envelope(
+    upcasters: &UpcasterRegistry,
+    payload: serde_json::Value,
+    header_version: u16,
+) -> Result<EventEnvelope<PolicyEvent>, NatsError> {
+    match payload {
+        serde_json::Value::Object(mut object) if object.contains_key("schema_version") => {
+            let version = object
+                .get("schema_version")
+                .and_then(serde_json::Value::as_u64)
+                .map(|v| v as u16)
+                .unwrap_or(header_version);
+            let event = object.remove("event").unwrap_or_default();
+            object.insert("event".to_string(), upcasters.upcast(event, version));
+            object.insert("schema_version".to_string(), upcasters.target_version().into());
+            Ok(serde_json::from_value(serde_json::Value::Object(object))?)
+        }
+        bare => {
+            let event: PolicyEvent =
+                serde_json::from_value(upcasters.upcast(bare, header_version))?;
+            Ok(EventEnvelope::from(event))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::PolicyApproved;
+    use crate::sagas::create_root_command;
+    use crate::value_objects::PolicyId;
+
+    fn approved_event() -> PolicyEvent {
+        PolicyEvent::PolicyApproved(PolicyApproved {
+            event_id: Uuid::now_v7(),
+            identity: create_root_command(),
+            policy_id: PolicyId::new(),
+            approved_by: "approver".to_string(),
+            approved_at: chrono::Utc::now(),
+            approval_notes: Some("Looks good".to_string()),
+        })
+    }
+
+    #[test]
+    fn test_envelope_round_trip_keeps_metadata() {
+        let event = approved_event();
+        let envelope = EventEnvelope::from(event.clone());
+
+        let payload = serde_json::to_value(&envelope).unwrap();
+        let decoded = decode_envelope(&UpcasterRegistry::new(), payload, 1).unwrap();
+
+        assert_eq!(decoded.recorded_at, envelope.recorded_at);
+        assert_eq!(decoded.schema_version, envelope.schema_version);
+        assert_eq!(decoded.message_identity.message_id, event.identity().message_id);
+        assert_eq!(decoded.event.aggregate_id(), event.aggregate_id());
+        assert_eq!(decoded.event.event_type(), "PolicyApproved");
+    }
+
+    #[test]
+    fn test_every_codec_round_trips_envelope() {
+        let envelope = EventEnvelope::from(approved_event());
+        let json_size = EventCodec::Json.encode(&envelope).unwrap().len();
+
+        for codec in [EventCodec::Json, EventCodec::Cbor, EventCodec::MessagePack] {
+            assert_eq!(EventCodec::from_header(codec.header_value()), Some(codec));
+
+            let bytes = codec.encode(&envelope).unwrap();
+            let decoded =
+                decode_envelope(&UpcasterRegistry::new(), codec.decode(&bytes).unwrap(), 1).unwrap();
+            assert_eq!(decoded.recorded_at, envelope.recorded_at);
+            assert_eq!(decoded.event.event_id(), envelope.event.event_id());
+            assert_eq!(decoded.event.aggregate_id(), envelope.event.aggregate_id());
+
+            if codec != EventCodec::Json {
+                assert!(bytes.len() < json_size, "{:?}: {} >= {}", codec, bytes.len(), json_size);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bare_legacy_payload_is_wrapped() {
+        let event = approved_event();
+        let payload = serde_json::to_value(&event).unwrap();
+
+        let decoded = decode_envelope(&UpcasterRegistry::new(), payload, 1).unwrap();
+        assert_eq!(decoded.message_identity.message_id, event.identity().message_id);
+        assert_eq!(decoded.event.aggregate_id(), event.aggregate_id());
+    }
+
+    #[test]
+    fn test_wrong_last_sequence_is_reported_as_wrong_sequence() {
+        let aggregate_id = Uuid::now_v7();
+
+        let stale = conditional_publish_error(
+            jetstream::context::PublishErrorKind::WrongLastSequence.into(),
+            aggregate_id,
+            7,
+        );
+        assert!(matches!(
+            stale,
+            NatsError::WrongSequence { aggregate_id: id, expected: 7 } if id == aggregate_id
+        ));
+
+        let other = conditional_publish_error(
+            jetstream::context::PublishErrorKind::TimedOut.into(),
+            aggregate_id,
+            7,
+        );
+        assert!(matches!(other, NatsError::JetStream(_)));
+    }
+
+    #[test]
+    fn test_snapshots_without_stream_sequence_are_not_resumed_from() {
+        let legacy = serde_json::json!({
+            "aggregate_id": Uuid::now_v7(),
+            "event_count": 12,
+            "state": "state",
+            "taken_at": chrono::Utc::now(),
+        });
+        let mut snapshot: AggregateSnapshot<String> = serde_json::from_value(legacy).unwrap();
+        assert_eq!(snapshot.stream_sequence, 0);
+        assert!(matches!(resume_point(Some(snapshot.clone())), (None, 0)));
+
+        snapshot.stream_sequence = 40;
+        let (resumed, after) = resume_point(Some(snapshot));
+        assert_eq!(after, 40);
+
+        let replay = SnapshotReplay {
+            snapshot: resumed,
+            events: vec![approved_event()],
+            last_sequence: 43,
+        };
+        assert_eq!(replay.event_count(), 13);
+    }
+}
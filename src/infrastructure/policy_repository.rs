@@ -2,8 +2,9 @@
 
 use crate::aggregate::Policy;
 use crate::events::{PolicyCreated, PolicyEvent};
-use crate::infrastructure::nats_integration::{NatsError, NatsEventStore};
+use crate::infrastructure::nats_integration::{AggregateSnapshot, NatsError, NatsEventStore};
 use crate::value_objects::PolicyId;
+use chrono::Utc;
 use cim_domain::DomainEvent;
 use std::sync::Arc;
 use thiserror::Error;
@@ -19,12 +20,16 @@ pub enum RepositoryError {
 
     #[error("Invalid event sequence: {0}")]
     InvalidSequence(String),
+
+    #[error("Concurrency conflict: expected version {expected}, found {actual}")]
+    ConcurrencyConflict { expected: u64, actual: u64 },
 }
 
 /// Repository for policy aggregates using event sourcing
 pub struct PolicyRepository {
     event_store: Arc<NatsEventStore>,
     snapshot_frequency: u64,
+    max_snapshots: u64,
 }
 
 impl PolicyRepository {
@@ -33,6 +38,7 @@ impl PolicyRepository {
         Self {
             event_store,
             snapshot_frequency: 100, // Default: snapshot every 100 events
+            max_snapshots: 3,
         }
     }
 
@@ -42,59 +48,250 @@ impl PolicyRepository {
         self
     }
 
+    /// Set how many snapshots are retained per policy (at least one is always kept)
+    pub fn with_max_snapshots(mut self, max_snapshots: u64) -> Self {
+        self.max_snapshots = max_snapshots.max(1);
+        self
+    }
+
     /// Load a policy by reconstructing it from its event history
     ///
     /// This is the core of event sourcing - we rebuild the aggregate state
-    /// by applying each event in sequence.
+    /// by applying each event in sequence. Replay starts from the latest
+    /// snapshot when one exists, reading only the events stored after it,
+    /// and a new snapshot is taken once `snapshot_frequency` events have
+    /// been replayed on top of it.
     pub async fn load(&self, policy_id: PolicyId) -> Result<Option<Policy>, RepositoryError> {
-        let uuid_id = policy_id.0;
-        let events = self.event_store.load_events(uuid_id).await?;
+        Ok(self.load_versioned(policy_id).await?.map(|(policy, _)| policy))
+    }
 
-        if events.is_empty() {
-            return Ok(None);
-        }
+    /// Load a policy together with its stream version
+    ///
+    /// The stream version is the stream sequence of the policy's last event
+    /// and is what `save` expects callers to pass back.
+    pub async fn load_versioned(
+        &self,
+        policy_id: PolicyId,
+    ) -> Result<Option<(Policy, u64)>, RepositoryError> {
+        let replay = self.event_store.load_since_snapshot::<Policy>(policy_id.0).await?;
+        let event_count = replay.event_count();
 
-        // First event must be PolicyCreated
-        let mut policy = None;
-
-        for event in events {
-            match &policy {
-                None => {
-                    // First event must create the policy
-                    if let PolicyEvent::PolicyCreated(created) = &event {
-                        policy = Some(self.create_from_created_event(created)?);
-                    } else {
-                        return Err(RepositoryError::InvalidSequence(format!(
-                            "First event must be PolicyCreated, got: {}",
-                            event.event_type()
-                        )));
-                    }
-                }
-                Some(pol) => {
-                    // Apply subsequent events
-                    policy = Some(pol.apply_event_pure(&event)?);
-                }
+        let policy = replay_policy(replay.snapshot.map(|s| s.state), &replay.events)?
+            .map(|policy| Policy {
+                last_sequence: replay.last_sequence,
+                ..policy
+            });
+
+        if let Some(policy) = &policy {
+            if replay.events.len() as u64 >= self.snapshot_frequency {
+                self.take_snapshot(policy, event_count, replay.last_sequence)
+                    .await?;
             }
         }
 
-        Ok(policy)
+        Ok(policy.map(|policy| (policy, replay.last_sequence)))
     }
 
     /// Save a batch of events for a policy
-    pub async fn save(&self, events: Vec<PolicyEvent>) -> Result<(), RepositoryError> {
-        for event in events {
-            self.event_store.append_event(event).await?;
+    ///
+    /// `expected_version` is the stream version the events were produced
+    /// against (0 for a new policy). JetStream checks it as part of each
+    /// publish, so if another writer has appended events since,
+    /// `ConcurrencyConflict` is returned and nothing is written; callers
+    /// should reload and retry.
+    pub async fn save(
+        &self,
+        policy_id: PolicyId,
+        events: Vec<PolicyEvent>,
+        expected_version: u64,
+    ) -> Result<(), RepositoryError> {
+        match self
+            .event_store
+            .append_events_at(policy_id.0, events, expected_version)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(NatsError::WrongSequence { .. }) => Err(RepositoryError::ConcurrencyConflict {
+                expected: expected_version,
+                actual: self.event_store.last_sequence(policy_id.0).await?,
+            }),
+            Err(e) => Err(e.into()),
         }
+    }
+
+    /// Store a snapshot and drop snapshots outside the retained window
+    async fn take_snapshot(
+        &self,
+        policy: &Policy,
+        event_count: u64,
+        stream_sequence: u64,
+    ) -> Result<(), RepositoryError> {
+        self.event_store
+            .save_snapshot(&AggregateSnapshot {
+                aggregate_id: policy.id.0,
+                event_count,
+                stream_sequence,
+                state: policy.clone(),
+                taken_at: Utc::now(),
+            })
+            .await?;
+        self.event_store
+            .compact_snapshots(policy.id.0, self.max_snapshots)
+            .await?;
         Ok(())
     }
+}
 
-    /// Create a policy from a PolicyCreated event
-    fn create_from_created_event(&self, event: &PolicyCreated) -> Result<Policy, RepositoryError> {
-        let policy = Policy::new(&event.name, &event.description);
+/// Apply events on top of an optional starting state (pure function)
+///
+/// Without a starting state the first event must be PolicyCreated.
+pub fn replay_policy(
+    base: Option<Policy>,
+    events: &[PolicyEvent],
+) -> Result<Option<Policy>, RepositoryError> {
+    let mut policy = base;
 
-        // Apply the created event to set proper state
-        let policy_event = PolicyEvent::PolicyCreated(event.clone());
-        Ok(policy.apply_event_pure(&policy_event)?)
+    for event in events {
+        match &policy {
+            None => {
+                // First event must create the policy
+                if let PolicyEvent::PolicyCreated(created) = event {
+                    policy = Some(create_from_created_event(created)?);
+                } else {
+                    return Err(RepositoryError::InvalidSequence(format!(
+                        "First event must be PolicyCreated, got: {}",
+                        event.event_type()
+                    )));
+                }
+            }
+            Some(pol) => {
+                // Apply subsequent events
+                policy = Some(pol.apply_event_pure(event)?);
+            }
+        }
     }
+
+    Ok(policy)
 }
 
+/// Create a policy from a PolicyCreated event
+fn create_from_created_event(event: &PolicyCreated) -> Result<Policy, RepositoryError> {
+    let policy = Policy::new(&event.name, &event.description);
+
+    // Apply the created event to set proper state
+    let policy_event = PolicyEvent::PolicyCreated(event.clone());
+    Ok(policy.apply_event_pure(&policy_event)?)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{PolicyActivated, PolicyApproved, PolicyUpdated};
+    use crate::infrastructure::nats_integration::SnapshotReplay;
+    use crate::sagas::create_root_command;
+    use crate::value_objects::PolicyStatus;
+    use uuid::Uuid;
+
+    fn history(policy_id: PolicyId, updates: u32) -> Vec<PolicyEvent> {
+        let now = Utc::now();
+        let mut events = vec![
+            PolicyEvent::PolicyCreated(PolicyCreated {
+                event_id: Uuid::now_v7(),
+                identity: create_root_command(),
+                policy_id,
+                name: "Key Policy".to_string(),
+                description: "Key size requirements".to_string(),
+                policy_type: "Security".to_string(),
+                created_by: "alice".to_string(),
+                created_at: now,
+                approval_requirements: Default::default(),
+            }),
+            PolicyEvent::PolicyApproved(PolicyApproved {
+                event_id: Uuid::now_v7(),
+                identity: create_root_command(),
+                policy_id,
+                approved_by: "bob".to_string(),
+                approved_at: now,
+                approval_notes: None,
+            }),
+            PolicyEvent::PolicyActivated(PolicyActivated {
+                event_id: Uuid::now_v7(),
+                identity: create_root_command(),
+                policy_id,
+                activated_by: "bob".to_string(),
+                activated_at: now,
+                effective_from: now,
+                effective_until: None,
+            }),
+        ];
+        events.extend((2..updates + 2).map(|version| {
+            PolicyEvent::PolicyUpdated(PolicyUpdated {
+                event_id: Uuid::now_v7(),
+                identity: create_root_command(),
+                policy_id,
+                version,
+                changes: Vec::new(),
+                updated_by: "alice".to_string(),
+                updated_at: now,
+            })
+        }));
+        events
+    }
+
+    #[test]
+    fn test_snapshot_plus_tail_matches_full_replay() {
+        let policy_id = PolicyId::new();
+        let events = history(policy_id, 10);
+        let full = replay_policy(None, &events).unwrap().unwrap();
+
+        // The policy's events interleave with other aggregates in the stream
+        let sequenced: Vec<(u64, PolicyEvent)> = events
+            .iter()
+            .enumerate()
+            .map(|(i, event)| (3 * i as u64 + 1, event.clone()))
+            .collect();
+
+        // Snapshot part-way through, round-tripped through its stored form
+        let snapshot = AggregateSnapshot {
+            aggregate_id: policy_id.0,
+            event_count: 5,
+            stream_sequence: sequenced[4].0,
+            state: replay_policy(None, &events[..5]).unwrap().unwrap(),
+            taken_at: Utc::now(),
+        };
+        let stored = serde_json::to_vec(&snapshot).unwrap();
+        let snapshot: AggregateSnapshot<Policy> = serde_json::from_slice(&stored).unwrap();
+
+        // Only events stored after the snapshot's sequence are replayed
+        let tail: Vec<PolicyEvent> = sequenced
+            .into_iter()
+            .filter(|(sequence, _)| *sequence > snapshot.stream_sequence)
+            .map(|(_, event)| event)
+            .collect();
+        let replay = SnapshotReplay {
+            snapshot: Some(snapshot),
+            events: tail,
+            last_sequence: 3 * events.len() as u64 - 2,
+        };
+        assert_eq!(replay.event_count(), events.len() as u64);
+
+        let restored = replay_policy(replay.snapshot.map(|s| s.state), &replay.events)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(restored.id, full.id);
+        assert_eq!(restored.version, full.version);
+        assert_eq!(restored.version, 11);
+        assert_eq!(restored.status, PolicyStatus::Active);
+    }
+
+    #[test]
+    fn test_replay_requires_created_event_without_snapshot() {
+        let events = history(PolicyId::new(), 0);
+        assert!(matches!(
+            replay_policy(None, &events[1..]),
+            Err(RepositoryError::InvalidSequence(_))
+        ));
+    }
+}
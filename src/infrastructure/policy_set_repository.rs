@@ -2,8 +2,9 @@
 
 use crate::aggregate::PolicySet;
 use crate::events::{PolicyEvent, PolicySetCreated};
-use crate::infrastructure::nats_integration::{NatsError, NatsEventStore};
+use crate::infrastructure::nats_integration::{AggregateSnapshot, NatsError, NatsEventStore};
 use crate::value_objects::PolicySetId;
+use chrono::Utc;
 use cim_domain::DomainEvent;
 use std::sync::Arc;
 use thiserror::Error;
@@ -25,6 +26,7 @@ pub enum RepositoryError {
 pub struct PolicySetRepository {
     event_store: Arc<NatsEventStore>,
     snapshot_frequency: u64,
+    max_snapshots: u64,
 }
 
 impl PolicySetRepository {
@@ -33,6 +35,7 @@ impl PolicySetRepository {
         Self {
             event_store,
             snapshot_frequency: 100,
+            max_snapshots: 3,
         }
     }
 
@@ -42,32 +45,23 @@ impl PolicySetRepository {
         self
     }
 
+    /// Set how many snapshots are retained (at least one is always kept)
+    pub fn with_max_snapshots(mut self, max_snapshots: u64) -> Self {
+        self.max_snapshots = max_snapshots.max(1);
+        self
+    }
+
     /// Load a policy set by reconstructing it from its event history
     pub async fn load(&self, set_id: PolicySetId) -> Result<Option<PolicySet>, RepositoryError> {
-        let uuid_id = set_id.0;
-        let events = self.event_store.load_events(uuid_id).await?;
-
-        if events.is_empty() {
-            return Ok(None);
-        }
+        let replay = self.event_store.load_since_snapshot::<PolicySet>(set_id.0).await?;
+        let event_count = replay.event_count();
 
-        let mut policy_set = None;
+        let policy_set = replay_policy_set(replay.snapshot.map(|s| s.state), &replay.events)?;
 
-        for event in events {
-            match &policy_set {
-                None => {
-                    if let PolicyEvent::PolicySetCreated(created) = &event {
-                        policy_set = Some(self.create_from_created_event(created)?);
-                    } else {
-                        return Err(RepositoryError::InvalidSequence(format!(
-                            "First event must be PolicySetCreated, got: {}",
-                            event.event_type()
-                        )));
-                    }
-                }
-                Some(set) => {
-                    policy_set = Some(set.apply_event_pure(&event)?);
-                }
+        if let Some(policy_set) = &policy_set {
+            if replay.events.len() as u64 >= self.snapshot_frequency {
+                self.take_snapshot(policy_set, event_count, replay.last_sequence)
+                    .await?;
             }
         }
 
@@ -82,10 +76,62 @@ impl PolicySetRepository {
         Ok(())
     }
 
-    /// Create a policy set from a PolicySetCreated event
-    fn create_from_created_event(&self, event: &PolicySetCreated) -> Result<PolicySet, RepositoryError> {
-        let policy_set = PolicySet::new(&event.name, &event.description);
-        let policy_event = PolicyEvent::PolicySetCreated(event.clone());
-        Ok(policy_set.apply_event_pure(&policy_event)?)
+    /// Store a snapshot and drop snapshots outside the retained window
+    async fn take_snapshot(
+        &self,
+        policy_set: &PolicySet,
+        event_count: u64,
+        stream_sequence: u64,
+    ) -> Result<(), RepositoryError> {
+        self.event_store
+            .save_snapshot(&AggregateSnapshot {
+                aggregate_id: policy_set.id.0,
+                event_count,
+                stream_sequence,
+                state: policy_set.clone(),
+                taken_at: Utc::now(),
+            })
+            .await?;
+        self.event_store
+            .compact_snapshots(policy_set.id.0, self.max_snapshots)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Apply events on top of an optional starting state (pure function)
+///
+/// Without a starting state the first event must be PolicySetCreated.
+pub fn replay_policy_set(
+    base: Option<PolicySet>,
+    events: &[PolicyEvent],
+) -> Result<Option<PolicySet>, RepositoryError> {
+    let mut policy_set = base;
+
+    for event in events {
+        match &policy_set {
+            None => {
+                if let PolicyEvent::PolicySetCreated(created) = event {
+                    policy_set = Some(create_from_created_event(created)?);
+                } else {
+                    return Err(RepositoryError::InvalidSequence(format!(
+                        "First event must be PolicySetCreated, got: {}",
+                        event.event_type()
+                    )));
+                }
+            }
+            Some(set) => {
+                policy_set = Some(set.apply_event_pure(event)?);
+            }
+        }
     }
+
+    Ok(policy_set)
+}
+
+/// Create a policy set from a PolicySetCreated event
+fn create_from_created_event(event: &PolicySetCreated) -> Result<PolicySet, RepositoryError> {
+    let policy_set = PolicySet::new(&event.name, &event.description);
+    let policy_event = PolicyEvent::PolicySetCreated(event.clone());
+    Ok(policy_set.apply_event_pure(&policy_event)?)
 }
@@ -0,0 +1,185 @@
+//! Subscription-based projection updates from the policy event stream
+
+use crate::events::{EventEnvelope, PolicyEvent};
+use crate::infrastructure::nats_integration::{NatsError, NatsEventStore};
+use crate::projections::PolicyProjection;
+use futures::{Stream, StreamExt};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Background consumer that keeps a `PolicyProjection` up to date
+///
+/// Events are read from a durable JetStream consumer and acknowledged only
+/// after they have been applied, giving at-least-once processing. The
+/// projection ignores events at or below the stream sequence it last applied
+/// for their aggregate, so redeliveries are safe.
+pub struct ProjectionConsumer {
+    event_store: Arc<NatsEventStore>,
+    durable_name: String,
+    projection: Arc<RwLock<PolicyProjection>>,
+}
+
+impl ProjectionConsumer {
+    /// Create a new projection consumer
+    pub fn new(event_store: Arc<NatsEventStore>, durable_name: impl Into<String>) -> Self {
+        Self {
+            event_store,
+            durable_name: durable_name.into(),
+            projection: Arc::new(RwLock::new(PolicyProjection::new())),
+        }
+    }
+
+    /// Use an existing projection instead of an empty one
+    pub fn with_projection(mut self, projection: Arc<RwLock<PolicyProjection>>) -> Self {
+        self.projection = projection;
+        self
+    }
+
+    /// Shared handle to the projection being updated
+    pub fn projection(&self) -> Arc<RwLock<PolicyProjection>> {
+        self.projection.clone()
+    }
+
+    /// Consume events until the subscription ends
+    pub async fn run(self) -> Result<(), NatsError> {
+        let consumer = self.event_store.durable_consumer(&self.durable_name).await?;
+        let mut messages = consumer.messages().await.map_err(|e| {
+            NatsError::JetStream(format!("Failed to get messages: {}", e))
+        })?;
+
+        while let Some(message) = messages.next().await {
+            let msg = message.map_err(|e| NatsError::JetStream(e.to_string()))?;
+
+            let sequence = msg
+                .info()
+                .map_err(|e| NatsError::JetStream(e.to_string()))?
+                .stream_sequence;
+
+            match self.event_store.decode_message(&msg) {
+                Ok(envelope) => {
+                    self.projection.write().await.apply(sequence, &envelope.event);
+                }
+                Err(e) => {
+                    // Undecodable events would be redelivered forever
+                    tracing::warn!("Skipping undecodable event on {}: {}", msg.subject, e);
+                }
+            }
+
+            msg.ack().await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Apply a stream of sequenced events to a projection, returning how many were new
+pub async fn apply_events<S>(projection: &RwLock<PolicyProjection>, events: S) -> usize
+where
+    S: Stream<Item = (u64, EventEnvelope<PolicyEvent>)>,
+{
+    let mut events = std::pin::pin!(events);
+    let mut applied = 0;
+
+    while let Some((sequence, envelope)) = events.next().await {
+        if projection.write().await.apply(sequence, &envelope.event) {
+            applied += 1;
+        }
+    }
+
+    applied
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::ExemptionScope;
+    use crate::events::{PolicyActivated, PolicyApproved, PolicyCreated, PolicyExemptionGranted};
+    use crate::sagas::create_root_command;
+    use crate::value_objects::{ExemptionId, PolicyId, PolicyStatus};
+    use chrono::{Duration, Utc};
+    use uuid::Uuid;
+
+    fn lifecycle(policy_id: PolicyId) -> Vec<PolicyEvent> {
+        let now = Utc::now();
+        vec![
+            PolicyEvent::PolicyCreated(PolicyCreated {
+                event_id: Uuid::now_v7(),
+                identity: create_root_command(),
+                policy_id,
+                name: "Key Policy".to_string(),
+                description: "Key size requirements".to_string(),
+                policy_type: "Security".to_string(),
+                created_by: "alice".to_string(),
+                created_at: now,
+                approval_requirements: Default::default(),
+            }),
+            PolicyEvent::PolicyApproved(PolicyApproved {
+                event_id: Uuid::now_v7(),
+                identity: create_root_command(),
+                policy_id,
+                approved_by: "bob".to_string(),
+                approved_at: now,
+                approval_notes: None,
+            }),
+            PolicyEvent::PolicyActivated(PolicyActivated {
+                event_id: Uuid::now_v7(),
+                identity: create_root_command(),
+                policy_id,
+                activated_by: "bob".to_string(),
+                activated_at: now,
+                effective_from: now,
+                effective_until: None,
+            }),
+            PolicyEvent::PolicyExemptionGranted(PolicyExemptionGranted {
+                event_id: Uuid::now_v7(),
+                identity: create_root_command(),
+                exemption_id: ExemptionId::new(),
+                policy_id,
+                granted_by: "security-admin".to_string(),
+                granted_at: now,
+                reason: "Legacy HSM".to_string(),
+                justification: "Replacement scheduled for next quarter".to_string(),
+                valid_until: now + Duration::days(30),
+                risk_acceptance: None,
+                scope: ExemptionScope::Global,
+                conditions: Vec::new(),
+                max_uses: None,
+            }),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_projection_converges_with_redelivered_events() {
+        let policy_id = PolicyId::new();
+        let events = lifecycle(policy_id);
+
+        // At-least-once delivery: the whole history is delivered twice
+        let sequenced = events
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(i, event)| (i as u64 + 1, EventEnvelope::from(event)))
+            .collect::<Vec<_>>();
+        let delivered = sequenced.iter().chain(sequenced.iter()).cloned().collect::<Vec<_>>();
+
+        let projection = RwLock::new(PolicyProjection::new());
+        let applied = apply_events(&projection, futures::stream::iter(delivered)).await;
+        assert_eq!(applied, events.len());
+
+        let projection = projection.read().await;
+        let view = projection.policy(&policy_id).unwrap();
+        assert_eq!(view.status, PolicyStatus::Active);
+        assert_eq!(view.name, "Key Policy");
+
+        let exemptions = projection.exemptions().collect::<Vec<_>>();
+        assert_eq!(exemptions.len(), 1);
+        assert_eq!(exemptions[0].policy_id, policy_id);
+
+        assert_eq!(projection.last_sequence(policy_id.0), Some(events.len() as u64));
+        assert_eq!(projection.policies_with_status(PolicyStatus::Active).len(), 1);
+        assert!(projection.policies_with_status(PolicyStatus::Draft).is_empty());
+        assert_eq!(projection.active_exemptions_for(&policy_id).len(), 1);
+        assert!(projection.lapsed_exemptions(Utc::now()).is_empty());
+        assert_eq!(projection.lapsed_exemptions(Utc::now() + Duration::days(31)).len(), 1);
+    }
+}
@@ -0,0 +1,220 @@
+//! PolicyTemplate repository with event sourcing support
+
+use crate::entities::PolicyTemplate;
+use crate::events::{PolicyEvent, PolicyTemplateDeleted, PolicyTemplateSaved};
+use crate::infrastructure::nats_integration::{NatsError, NatsEventStore};
+use crate::sagas::create_root_command;
+use chrono::Utc;
+use cim_domain::DomainEvent;
+use indexmap::IndexMap;
+use std::sync::Arc;
+use thiserror::Error;
+use uuid::Uuid;
+
+/// Errors that can occur in the template repository
+#[derive(Debug, Error)]
+pub enum RepositoryError {
+    #[error("NATS error: {0}")]
+    Nats(#[from] NatsError),
+
+    #[error("Invalid event sequence: {0}")]
+    InvalidSequence(String),
+}
+
+/// Repository for user-defined policy templates using event sourcing
+pub struct TemplateRepository {
+    event_store: Arc<NatsEventStore>,
+}
+
+impl TemplateRepository {
+    /// Create a new template repository
+    pub fn new(event_store: Arc<NatsEventStore>) -> Self {
+        Self { event_store }
+    }
+
+    /// Load a template by replaying its event history
+    pub async fn load(&self, template_id: Uuid) -> Result<Option<PolicyTemplate>, RepositoryError> {
+        let events = self.event_store.load_events(template_id).await?;
+
+        for event in &events {
+            if !is_template_event(event) {
+                return Err(RepositoryError::InvalidSequence(format!(
+                    "Expected template event, got: {}",
+                    event.event_type()
+                )));
+            }
+        }
+
+        Ok(replay_templates(&events).pop())
+    }
+
+    /// List all persisted templates that have not been deleted
+    pub async fn list(&self) -> Result<Vec<PolicyTemplate>, RepositoryError> {
+        let events = self
+            .event_store
+            .load_events_by_type(&["PolicyTemplateSaved", "PolicyTemplateDeleted"])
+            .await?
+            .into_iter()
+            .map(|envelope| envelope.event)
+            .collect::<Vec<_>>();
+
+        Ok(replay_templates(&events))
+    }
+
+    /// Save a batch of template events
+    pub async fn save(&self, events: Vec<PolicyEvent>) -> Result<(), RepositoryError> {
+        for event in events {
+            self.event_store.append_event(event).await?;
+        }
+        Ok(())
+    }
+
+    /// Create or update a template
+    pub async fn save_template(
+        &self,
+        template: PolicyTemplate,
+        saved_by: impl Into<String>,
+    ) -> Result<(), RepositoryError> {
+        self.save(vec![template_saved(template, saved_by)]).await
+    }
+
+    /// Delete a template
+    pub async fn delete_template(
+        &self,
+        template_id: Uuid,
+        deleted_by: impl Into<String>,
+    ) -> Result<(), RepositoryError> {
+        self.save(vec![template_deleted(template_id, deleted_by)]).await
+    }
+}
+
+/// Build a PolicyTemplateSaved event
+pub fn template_saved(template: PolicyTemplate, saved_by: impl Into<String>) -> PolicyEvent {
+    PolicyEvent::PolicyTemplateSaved(PolicyTemplateSaved {
+        event_id: Uuid::now_v7(),
+        identity: create_root_command(),
+        template,
+        saved_by: saved_by.into(),
+        saved_at: Utc::now(),
+    })
+}
+
+/// Build a PolicyTemplateDeleted event
+pub fn template_deleted(template_id: Uuid, deleted_by: impl Into<String>) -> PolicyEvent {
+    PolicyEvent::PolicyTemplateDeleted(PolicyTemplateDeleted {
+        event_id: Uuid::now_v7(),
+        identity: create_root_command(),
+        template_id,
+        deleted_by: deleted_by.into(),
+        deleted_at: Utc::now(),
+    })
+}
+
+/// Reconstruct the current set of templates from template events (pure function)
+///
+/// Templates are returned in the order they were first saved; later saves
+/// replace earlier versions and deletions remove them.
+pub fn replay_templates(events: &[PolicyEvent]) -> Vec<PolicyTemplate> {
+    let mut templates: IndexMap<Uuid, PolicyTemplate> = IndexMap::new();
+
+    for event in events {
+        match event {
+            PolicyEvent::PolicyTemplateSaved(e) => {
+                templates.insert(e.template.id, e.template.clone());
+            }
+            PolicyEvent::PolicyTemplateDeleted(e) => {
+                templates.shift_remove(&e.template_id);
+            }
+            _ => {}
+        }
+    }
+
+    templates.into_values().collect()
+}
+
+fn is_template_event(event: &PolicyEvent) -> bool {
+    matches!(
+        event,
+        PolicyEvent::PolicyTemplateSaved(_) | PolicyEvent::PolicyTemplateDeleted(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{ParameterType, PolicyRule, TemplateParameter};
+    use crate::services::PolicyTemplateEngine;
+    use crate::value_objects::{RuleExpression, Severity, Value};
+    use std::collections::HashMap;
+
+    fn team_template() -> PolicyTemplate {
+        let mut template = PolicyTemplate::new("Team Access", "Restrict access to a team");
+        template.add_parameter(TemplateParameter {
+            name: "team".to_string(),
+            description: "Team allowed access".to_string(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            required: true,
+            min_length: None,
+            max_length: None,
+            allowed_values: None,
+            validation: None,
+        });
+        template.base_rules.push(PolicyRule::new(
+            "Team membership",
+            "Requester must belong to the team",
+            RuleExpression::Equal {
+                field: "team".to_string(),
+                value: Value::String("${team}".to_string()),
+            },
+            Severity::High,
+        ));
+        template
+    }
+
+    #[test]
+    fn test_replay_applies_updates_and_deletions() {
+        let mut template = team_template();
+        let other = PolicyTemplate::new("Obsolete", "Will be deleted");
+        let original = template_saved(template.clone(), "alice");
+        template.description = "Updated description".to_string();
+
+        let events = vec![
+            original,
+            template_saved(other.clone(), "alice"),
+            template_saved(template.clone(), "bob"),
+            template_deleted(other.id, "bob"),
+        ];
+
+        let templates = replay_templates(&events);
+        assert_eq!(templates.len(), 1);
+        assert_eq!(templates[0].id, template.id);
+        assert_eq!(templates[0].description, "Updated description");
+    }
+
+    #[test]
+    fn test_engine_instantiates_persisted_template() {
+        let events = vec![template_saved(team_template(), "alice")];
+
+        // Round-trip through the stored representation
+        let payload = serde_json::to_vec(&events).unwrap();
+        let stored: Vec<PolicyEvent> = serde_json::from_slice(&payload).unwrap();
+
+        let engine = PolicyTemplateEngine::with_templates(replay_templates(&stored));
+        let mut parameters = HashMap::new();
+        parameters.insert("team".to_string(), Value::String("platform".to_string()));
+
+        let policy = engine
+            .instantiate("Team Access", parameters, "Platform Access".to_string(), "Platform only".to_string())
+            .unwrap();
+
+        assert_eq!(policy.rules.len(), 1);
+        assert_eq!(
+            policy.rules[0].expression,
+            RuleExpression::Equal {
+                field: "team".to_string(),
+                value: Value::String("platform".to_string()),
+            }
+        );
+    }
+}
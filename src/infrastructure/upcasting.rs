@@ -0,0 +1,188 @@
+//! Event upcasting for schema evolution of stored policy events
+//!
+//! Stored payloads are transformed as raw JSON, one version step at a time,
+//! before they are deserialized into the current `PolicyEvent` structs.
+
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+
+pub use crate::events::EVENT_SCHEMA_VERSION;
+
+/// Transforms a stored event payload from one schema version to the next
+pub trait EventUpcaster: Send + Sync {
+    /// Event type this upcaster applies to (e.g. "PolicyCreated")
+    fn event_type(&self) -> &str;
+
+    /// Version of the payload this upcaster accepts; it produces `source_version + 1`
+    fn source_version(&self) -> u16;
+
+    /// Transform the payload
+    fn upcast(&self, payload: JsonValue) -> JsonValue;
+}
+
+/// Upcaster that adds a field with a default value when it is missing
+pub struct DefaultFieldUpcaster {
+    event_type: String,
+    from_version: u16,
+    field: String,
+    default: JsonValue,
+}
+
+impl DefaultFieldUpcaster {
+    /// Create a new default-field upcaster
+    pub fn new(
+        event_type: impl Into<String>,
+        from_version: u16,
+        field: impl Into<String>,
+        default: JsonValue,
+    ) -> Self {
+        Self {
+            event_type: event_type.into(),
+            from_version,
+            field: field.into(),
+            default,
+        }
+    }
+}
+
+impl EventUpcaster for DefaultFieldUpcaster {
+    fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    fn source_version(&self) -> u16 {
+        self.from_version
+    }
+
+    fn upcast(&self, mut payload: JsonValue) -> JsonValue {
+        if let Some(object) = payload.as_object_mut() {
+            object
+                .entry(self.field.clone())
+                .or_insert_with(|| self.default.clone());
+        }
+        payload
+    }
+}
+
+/// Registry of upcasters keyed by event type
+pub struct UpcasterRegistry {
+    target_version: u16,
+    upcasters: HashMap<String, Vec<Box<dyn EventUpcaster>>>,
+}
+
+impl UpcasterRegistry {
+    /// Create an empty registry upcasting to the current schema version
+    pub fn new() -> Self {
+        Self {
+            target_version: EVENT_SCHEMA_VERSION,
+            upcasters: HashMap::new(),
+        }
+    }
+
+    /// Set the schema version payloads are upcast to
+    pub fn with_target_version(mut self, version: u16) -> Self {
+        self.target_version = version;
+        self
+    }
+
+    /// Schema version payloads are upcast to
+    pub fn target_version(&self) -> u16 {
+        self.target_version
+    }
+
+    /// Register an upcaster
+    pub fn register(&mut self, upcaster: Box<dyn EventUpcaster>) {
+        let chain = self
+            .upcasters
+            .entry(upcaster.event_type().to_string())
+            .or_default();
+        chain.push(upcaster);
+        chain.sort_by_key(|u| u.source_version());
+    }
+
+    /// Upcast a stored payload written at `version` to the target version
+    ///
+    /// The event type is read from the payload's `event_type` tag.
+    pub fn upcast(&self, payload: JsonValue, version: u16) -> JsonValue {
+        let Some(chain) = payload
+            .get("event_type")
+            .and_then(JsonValue::as_str)
+            .and_then(|event_type| self.upcasters.get(event_type))
+        else {
+            return payload;
+        };
+
+        chain
+            .iter()
+            .filter(|u| u.source_version() >= version && u.source_version() < self.target_version)
+            .fold(payload, |payload, upcaster| upcaster.upcast(payload))
+    }
+}
+
+impl Default for UpcasterRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{PolicyCreated, PolicyEvent};
+    use crate::value_objects::PolicyId;
+    use serde_json::json;
+
+    fn v1_policy_created() -> JsonValue {
+        let event = PolicyEvent::PolicyCreated(PolicyCreated {
+            event_id: uuid::Uuid::now_v7(),
+            identity: crate::sagas::create_root_command(),
+            policy_id: PolicyId::new(),
+            name: "Legacy".to_string(),
+            description: "Created before policy types".to_string(),
+            policy_type: "Security".to_string(),
+            created_by: "admin".to_string(),
+            created_at: chrono::Utc::now(),
+            approval_requirements: Default::default(),
+        });
+
+        // Synthetic v1 payload written before `policy_type` existed
+        let mut payload = serde_json::to_value(event).unwrap();
+        payload.as_object_mut().unwrap().remove("policy_type");
+        payload
+    }
+
+    #[test]
+    fn test_upcasts_v1_payload_into_current_struct() {
+        let payload = v1_policy_created();
+        assert!(serde_json::from_value::<PolicyEvent>(payload.clone()).is_err());
+
+        let mut registry = UpcasterRegistry::new().with_target_version(2);
+        registry.register(Box::new(DefaultFieldUpcaster::new(
+            "PolicyCreated",
+            1,
+            "policy_type",
+            json!("General"),
+        )));
+
+        let upcast = registry.upcast(payload, 1);
+        let PolicyEvent::PolicyCreated(event) = serde_json::from_value(upcast).unwrap() else {
+            panic!("expected PolicyCreated");
+        };
+        assert_eq!(event.name, "Legacy");
+        assert_eq!(event.policy_type, "General");
+    }
+
+    #[test]
+    fn test_current_payloads_are_left_untouched() {
+        let mut registry = UpcasterRegistry::new();
+        registry.register(Box::new(DefaultFieldUpcaster::new(
+            "PolicyCreated",
+            EVENT_SCHEMA_VERSION,
+            "policy_type",
+            json!("General"),
+        )));
+
+        let payload = v1_policy_created();
+        assert_eq!(registry.upcast(payload.clone(), EVENT_SCHEMA_VERSION), payload);
+    }
+}
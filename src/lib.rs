@@ -39,23 +39,28 @@
 
 pub mod adapters;
 pub mod aggregate;
+pub mod clock;
 pub mod commands;
+pub mod documents;
 pub mod entities;
 pub mod events;
 pub mod infrastructure;
+pub mod metrics;
 pub mod ports;
+pub mod projections;
 pub mod sagas;
 pub mod services;
 pub mod value_objects;
 
 // Re-export main types
-pub use aggregate::{Policy, PolicySet, PolicyExemption, ConflictResolution, CompositionRule};
+pub use clock::{Clock, MockClock, SystemClock};
+pub use aggregate::{Policy, PolicySet, PolicyExemption, ClaimsPolicy, ConflictResolution, CompositionRule};
 pub use commands::{PolicyCommand, CreatePolicy, UpdatePolicy, EvaluatePolicy, EnforcementAction};
-pub use entities::{PolicyRule, PolicyEvaluation};
-pub use events::{PolicyEvent, PolicyCreated, PolicyEvaluated, PolicyViolationDetected};
+pub use entities::{PolicyRule, PolicyEvaluation, SetEvaluation, PolicyDiff};
+pub use events::{PolicyEvent, PolicyCreated, PolicyEvaluated, PolicyViolationDetected, EventEnvelope};
 pub use value_objects::{
     PolicyId, PolicyStatus, PolicyTarget, EnforcementLevel,
-    ComplianceResult, RuleExpression, Severity, EvaluationContext, Value, Violation,
+    ComplianceResult, RuleExpression, Severity, EvaluationContext, Value, Violation, WireValue,
 };
 pub use services::{PolicyEvaluator, PolicyConflictResolver};
 
@@ -84,6 +89,31 @@ pub enum PolicyError {
 
     #[error("Unauthorized policy operation: {0}")]
     UnauthorizedOperation(String),
+
+    #[error("Unknown external approval request: {0}")]
+    UnknownApprovalRequest(uuid::Uuid),
+
+    #[error("Policy serialization failed: {0}")]
+    Serialization(String),
+}
+
+impl PolicyError {
+    /// Stable, machine-readable code for this error
+    ///
+    /// Codes are part of the service's wire contract; messages may change.
+    pub fn code(&self) -> &'static str {
+        match self {
+            PolicyError::PolicyNotFound(_) => "POLICY_NOT_FOUND",
+            PolicyError::ValidationError(_) => "VALIDATION_FAILED",
+            PolicyError::ConflictError(_) => "POLICY_CONFLICT",
+            PolicyError::EvaluationError(_) => "EVALUATION_FAILED",
+            PolicyError::EnforcementError(_) => "ENFORCEMENT_FAILED",
+            PolicyError::InvalidRuleExpression(_) => "INVALID_RULE_EXPRESSION",
+            PolicyError::UnauthorizedOperation(_) => "UNAUTHORIZED",
+            PolicyError::UnknownApprovalRequest(_) => "UNKNOWN_APPROVAL_REQUEST",
+            PolicyError::Serialization(_) => "SERIALIZATION_FAILED",
+        }
+    }
 }
 
 pub type PolicyResult<T> = Result<T, PolicyError>;
\ No newline at end of file
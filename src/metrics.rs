@@ -0,0 +1,42 @@
+//! Metrics for policy evaluation and exemptions
+//!
+//! Metrics are recorded through the `metrics` facade; the application picks
+//! the exporter (e.g. Prometheus) by installing a recorder. Without one,
+//! recording is a no-op.
+
+use crate::entities::PolicyEvaluation;
+use std::time::Duration;
+
+/// Counter of policy evaluations, labeled by `result`
+pub const EVALUATIONS_TOTAL: &str = "policy_evaluations_total";
+
+/// Histogram of policy evaluation durations in milliseconds
+pub const EVALUATION_DURATION_MS: &str = "policy_evaluation_duration_ms";
+
+/// Gauge of registered exemptions that are valid with uses left, summed over evaluators
+pub const ACTIVE_EXEMPTIONS: &str = "policy_active_exemptions";
+
+/// Record a completed evaluation
+pub fn record_evaluation(evaluation: &PolicyEvaluation, duration: Duration) {
+    let result = if evaluation.is_compliant() {
+        "compliant"
+    } else {
+        "non_compliant"
+    };
+    ::metrics::counter!(EVALUATIONS_TOTAL, "result" => result).increment(1);
+    ::metrics::histogram!(EVALUATION_DURATION_MS).record(duration.as_secs_f64() * 1000.0);
+}
+
+/// Record an evaluation that failed with an error
+pub fn record_evaluation_error() {
+    ::metrics::counter!(EVALUATIONS_TOTAL, "result" => "error").increment(1);
+}
+
+/// Adjust the number of active exemptions by an evaluator's change in its own count
+///
+/// Each evaluator adds what it registers, takes away exemptions that lapse
+/// or are used up, and takes the rest away when dropped, so evaluators
+/// running side by side don't overwrite each other.
+pub fn adjust_active_exemptions(delta: i64) {
+    ::metrics::gauge!(ACTIVE_EXEMPTIONS).increment(delta as f64);
+}
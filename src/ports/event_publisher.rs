@@ -71,3 +71,146 @@ pub fn event_to_subject(event: &PolicyEvent) -> String {
     format!("events.policy.{}.{}", aggregate_id, event_type)
 }
 
+/// Subject filter matching every event of one type, across all aggregates
+///
+/// `event_type` is matched the way [`event_to_subject`] writes it, so either
+/// `"PolicyViolationDetected"` or `"policyviolationdetected"` works.
+pub fn subject_filter_for_event_type(event_type: &str) -> String {
+    format!("events.policy.*.{}", event_type.to_lowercase())
+}
+
+/// Subject filter matching every event of one aggregate
+pub fn subject_filter_for_policy(aggregate_id: Uuid) -> String {
+    format!("events.policy.{}.*", aggregate_id)
+}
+
+/// Whether `subject` matches a NATS subject `filter`
+///
+/// `*` matches exactly one token and a trailing `>` matches one or more.
+pub fn subject_matches_filter(filter: &str, subject: &str) -> bool {
+    let mut subject_tokens = subject.split('.');
+    for filter_token in filter.split('.') {
+        match (filter_token, subject_tokens.next()) {
+            (">", Some(_)) => return true,
+            ("*", Some(_)) => {}
+            (literal, Some(token)) if literal == token => {}
+            _ => return false,
+        }
+    }
+    subject_tokens.next().is_none()
+}
+
+/// Parse a subject built by [`event_to_subject`] back into its aggregate id
+/// and (lowercased) event type
+///
+/// Returns `None` unless the subject is exactly
+/// `events.policy.{hyphenated uuid}.{event_type}`; wildcards and extra
+/// tokens are rejected.
+pub fn subject_to_event_meta(subject: &str) -> Option<(Uuid, String)> {
+    let (aggregate_id, event_type) = subject.strip_prefix("events.policy.")?.split_once('.')?;
+
+    // Only the hyphenated form is ever produced
+    if aggregate_id.len() != 36 {
+        return None;
+    }
+    let aggregate_id = Uuid::parse_str(aggregate_id).ok()?;
+
+    if event_type.is_empty() || !event_type.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+
+    Some((aggregate_id, event_type.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::fixtures::{event_of_every_type, EVENT_VARIANT_COUNT};
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn prop_subject_round_trips_for_every_event_type(raw_id in any::<u128>()) {
+            let id = Uuid::from_u128(raw_id);
+            for event in event_of_every_type(id) {
+                let (parsed_id, event_type) = subject_to_event_meta(&event_to_subject(&event))
+                    .expect("generated subject parses");
+                prop_assert_eq!(parsed_id, id);
+                prop_assert_eq!(event_type, event.event_type().to_lowercase());
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_event_type_is_covered() {
+        let types: std::collections::HashSet<_> = event_of_every_type(Uuid::now_v7())
+            .iter()
+            .map(|event| event.event_type())
+            .collect();
+        assert_eq!(types.len(), EVENT_VARIANT_COUNT);
+    }
+
+    #[test]
+    fn test_event_type_filter_selects_only_that_type() {
+        let id = Uuid::now_v7();
+        let filter = subject_filter_for_event_type("PolicyViolationDetected");
+        assert_eq!(filter, "events.policy.*.policyviolationdetected");
+
+        for event in event_of_every_type(id) {
+            let subject = event_to_subject(&event);
+            assert_eq!(
+                subject_matches_filter(&filter, &subject),
+                event.event_type() == "PolicyViolationDetected",
+                "{}",
+                subject
+            );
+        }
+    }
+
+    #[test]
+    fn test_policy_filter_selects_only_that_aggregate() {
+        let id = Uuid::now_v7();
+        let filter = subject_filter_for_policy(id);
+
+        for event in event_of_every_type(id) {
+            assert!(subject_matches_filter(&filter, &event_to_subject(&event)));
+        }
+        for event in event_of_every_type(Uuid::now_v7()) {
+            assert!(!subject_matches_filter(&filter, &event_to_subject(&event)));
+        }
+        assert!(!subject_matches_filter(&filter, &format!("events.policy.{}.a.b", id)));
+    }
+
+    #[test]
+    fn test_subject_matching() {
+        assert!(subject_matches_filter("events.policy.>", "events.policy.x.y"));
+        assert!(!subject_matches_filter("events.policy.>", "events.policy"));
+        assert!(subject_matches_filter("a.*.c", "a.b.c"));
+        assert!(!subject_matches_filter("a.*.c", "a.b.d"));
+        assert!(!subject_matches_filter("a.*", "a.b.c"));
+        assert!(!subject_matches_filter("a.b.c", "a.b"));
+    }
+
+    #[test]
+    fn test_malformed_subjects_are_rejected() {
+        let id = Uuid::now_v7();
+        assert_eq!(
+            subject_to_event_meta(&format!("events.policy.{}.policycreated", id)),
+            Some((id, "policycreated".to_string()))
+        );
+
+        for subject in [
+            format!("events.other.{}.policycreated", id),
+            format!("policy.{}.policycreated", id),
+            "events.policy.not-a-uuid.policycreated".to_string(),
+            format!("events.policy.{}.policycreated", id.simple()),
+            format!("events.policy.{}", id),
+            format!("events.policy.{}.", id),
+            format!("events.policy.{}.*", id),
+            format!("events.policy.{}.policycreated.extra", id),
+            "events.policy.*.policycreated".to_string(),
+        ] {
+            assert_eq!(subject_to_event_meta(&subject), None, "{}", subject);
+        }
+    }
+}
@@ -2,4 +2,7 @@
 
 pub mod event_publisher;
 
-pub use event_publisher::{EventPublisher, PublishError, QueryError, event_to_subject};
+pub use event_publisher::{
+    EventPublisher, PublishError, QueryError, event_to_subject, subject_to_event_meta,
+    subject_filter_for_event_type, subject_filter_for_policy, subject_matches_filter,
+};
@@ -0,0 +1,205 @@
+//! Read-model projections built from policy events
+
+use crate::aggregate::ExemptionStatus;
+use crate::events::PolicyEvent;
+use crate::value_objects::*;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use cim_domain::DomainEvent;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Read model of a policy's current lifecycle state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyView {
+    pub policy_id: PolicyId,
+    pub name: String,
+    pub description: String,
+    pub status: PolicyStatus,
+    pub version: u32,
+    pub effective_from: Option<DateTime<Utc>>,
+    pub effective_until: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Read model of an exemption's current state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExemptionView {
+    pub exemption_id: ExemptionId,
+    pub policy_id: PolicyId,
+    pub status: ExemptionStatus,
+    pub granted_by: String,
+    pub valid_until: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Projection of policy and exemption views
+///
+/// Application is idempotent: the last stream sequence applied is kept per
+/// aggregate, so redelivered events (at-least-once delivery) are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct PolicyProjection {
+    policies: HashMap<PolicyId, PolicyView>,
+    exemptions: HashMap<ExemptionId, ExemptionView>,
+    sequences: HashMap<Uuid, u64>,
+}
+
+impl PolicyProjection {
+    /// Create an empty projection
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the view of a policy
+    pub fn policy(&self, policy_id: &PolicyId) -> Option<&PolicyView> {
+        self.policies.get(policy_id)
+    }
+
+    /// Get the view of an exemption
+    pub fn exemption(&self, exemption_id: &ExemptionId) -> Option<&ExemptionView> {
+        self.exemptions.get(exemption_id)
+    }
+
+    /// All policy views
+    pub fn policies(&self) -> impl Iterator<Item = &PolicyView> {
+        self.policies.values()
+    }
+
+    /// All exemption views
+    pub fn exemptions(&self) -> impl Iterator<Item = &ExemptionView> {
+        self.exemptions.values()
+    }
+
+    /// Policies currently in `status`
+    pub fn policies_with_status(&self, status: PolicyStatus) -> Vec<&PolicyView> {
+        self.policies.values().filter(|view| view.status == status).collect()
+    }
+
+    /// Active exemptions granted against a policy
+    pub fn active_exemptions_for(&self, policy_id: &PolicyId) -> Vec<&ExemptionView> {
+        self.exemptions
+            .values()
+            .filter(|view| view.policy_id == *policy_id && view.status == ExemptionStatus::Active)
+            .collect()
+    }
+
+    /// Exemptions still marked active whose validity ended before `now`
+    pub fn lapsed_exemptions(&self, now: DateTime<Utc>) -> Vec<&ExemptionView> {
+        self.exemptions
+            .values()
+            .filter(|view| view.status == ExemptionStatus::Active && view.valid_until < now)
+            .collect()
+    }
+
+    /// Stream sequence of the last event applied for an aggregate
+    pub fn last_sequence(&self, aggregate_id: Uuid) -> Option<u64> {
+        self.sequences.get(&aggregate_id).copied()
+    }
+
+    /// Apply an event read at `sequence`, returning false if it was already processed
+    ///
+    /// Stream sequences only grow, so an event at or below the last sequence
+    /// applied for its aggregate is a redelivery.
+    pub fn apply(&mut self, sequence: u64, event: &PolicyEvent) -> bool {
+        let last = self.sequences.entry(event.aggregate_id()).or_insert(0);
+        if sequence <= *last {
+            return false;
+        }
+        *last = sequence;
+
+        match event {
+            PolicyEvent::PolicyCreated(e) => {
+                self.policies.insert(e.policy_id, PolicyView {
+                    policy_id: e.policy_id,
+                    name: e.name.clone(),
+                    description: e.description.clone(),
+                    status: PolicyStatus::Draft,
+                    version: 1,
+                    effective_from: None,
+                    effective_until: None,
+                    updated_at: e.created_at,
+                });
+            }
+            PolicyEvent::PolicyUpdated(e) => {
+                self.update_policy(&e.policy_id, e.updated_at, |view| view.version = e.version);
+            }
+            PolicyEvent::PolicyApproved(e) => {
+                self.update_policy(&e.policy_id, e.approved_at, |view| {
+                    view.status = PolicyStatus::Approved;
+                });
+            }
+            PolicyEvent::PolicyActivated(e) => {
+                self.update_policy(&e.policy_id, e.activated_at, |view| {
+                    view.status = PolicyStatus::Active;
+                    view.effective_from = Some(e.effective_from);
+                    view.effective_until = e.effective_until;
+                });
+            }
+            PolicyEvent::PolicySuspended(e) => {
+                self.update_policy(&e.policy_id, e.suspended_at, |view| {
+                    view.status = PolicyStatus::Suspended;
+                });
+            }
+            PolicyEvent::PolicyRevoked(e) => {
+                self.update_policy(&e.policy_id, e.revoked_at, |view| {
+                    view.status = PolicyStatus::Revoked;
+                });
+            }
+            PolicyEvent::PolicyArchived(e) => {
+                self.update_policy(&e.policy_id, e.archived_at, |view| {
+                    view.status = PolicyStatus::Archived;
+                });
+            }
+            PolicyEvent::PolicyExemptionGranted(e) => {
+                self.exemptions.insert(e.exemption_id, ExemptionView {
+                    exemption_id: e.exemption_id,
+                    policy_id: e.policy_id,
+                    status: ExemptionStatus::Active,
+                    granted_by: e.granted_by.clone(),
+                    valid_until: e.valid_until,
+                    updated_at: e.granted_at,
+                });
+            }
+            PolicyEvent::PolicyExemptionRevoked(e) => {
+                if let Some(view) = self.exemptions.get_mut(&e.exemption_id) {
+                    view.status = ExemptionStatus::Revoked {
+                        revoked_by: e.revoked_by.clone(),
+                        revoked_at: e.revoked_at,
+                        reason: e.reason.clone(),
+                    };
+                    view.updated_at = e.revoked_at;
+                }
+            }
+            PolicyEvent::PolicyExemptionExpired(e) => {
+                if let Some(view) = self.exemptions.get_mut(&e.exemption_id) {
+                    view.status = ExemptionStatus::Expired;
+                    view.updated_at = e.expired_at;
+                }
+            }
+            PolicyEvent::PolicyExemptionRenewed(e) => {
+                if let Some(view) = self.exemptions.get_mut(&e.exemption_id) {
+                    view.status = ExemptionStatus::Active;
+                    view.valid_until = e.new_valid_until;
+                    view.updated_at = e.renewed_at;
+                }
+            }
+            _ => {
+                // Other events don't affect these views
+            }
+        }
+
+        true
+    }
+
+    fn update_policy(
+        &mut self,
+        policy_id: &PolicyId,
+        at: DateTime<Utc>,
+        update: impl FnOnce(&mut PolicyView),
+    ) {
+        if let Some(view) = self.policies.get_mut(policy_id) {
+            update(view);
+            view.updated_at = at;
+        }
+    }
+}
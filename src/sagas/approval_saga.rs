@@ -49,6 +49,12 @@ impl PolicyApprovalSaga {
         }
     }
 
+    /// Read the current time from `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.metadata = self.metadata.with_clock(clock);
+        self
+    }
+
     /// Add an approval to the saga
     pub fn add_approval(&mut self, approver: String, level: ApprovalLevel) {
         self.approvals.push((approver, level));
@@ -144,6 +150,9 @@ impl PolicySaga for PolicyApprovalSaga {
                     policy_id: self.policy_id,
                     approved_by: self.metadata.initiated_by.clone(),
                     approval_notes: Some("Approved by required stakeholders".to_string()),
+                    approver_role: None,
+                    external: false,
+                    approver_kind: None,
                 }));
             }
             SagaState::Approved => {
@@ -12,6 +12,34 @@ pub struct ComplianceAuditSaga {
     audit_results: HashMap<PolicyId, ComplianceResult>,
     findings: Vec<AuditFinding>,
     overall_compliance: Option<ComplianceStatus>,
+    schedule: AuditSchedule,
+    started_at: Option<DateTime<Utc>>,
+    last_run_at: Option<DateTime<Utc>>,
+}
+
+/// When a compliance audit recurs
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuditSchedule {
+    /// Run a fixed interval after the previous run started
+    Interval(chrono::Duration),
+    /// Run once a day at the given UTC time
+    Daily { hour: u32, minute: u32 },
+}
+
+impl AuditSchedule {
+    /// Next run time after a run that started at `last_run`
+    pub fn next_after(&self, last_run: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            AuditSchedule::Interval(interval) => last_run + *interval,
+            AuditSchedule::Daily { hour, minute } => {
+                match last_run.date_naive().and_hms_opt(*hour, *minute, 0) {
+                    Some(time) if time.and_utc() > last_run => time.and_utc(),
+                    Some(time) => time.and_utc() + chrono::Duration::days(1),
+                    None => last_run + chrono::Duration::days(1),
+                }
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -66,7 +94,102 @@ impl ComplianceAuditSaga {
             audit_results: HashMap::new(),
             findings: Vec::new(),
             overall_compliance: None,
+            schedule: AuditSchedule::Interval(chrono::Duration::days(30)),
+            started_at: None,
+            last_run_at: None,
+        }
+    }
+
+    /// Read the current time from `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.metadata = self.metadata.with_clock(clock);
+        self
+    }
+
+    /// Set how often the audit recurs
+    pub fn with_schedule(mut self, schedule: AuditSchedule) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
+    /// Start a scheduled audit run
+    pub fn start(&mut self, now: DateTime<Utc>) -> Result<(), SagaError> {
+        if self.current_state != SagaState::AuditScheduled {
+            return Err(SagaError::InvalidTransition(
+                self.current_state.clone(),
+                SagaState::AuditInProgress,
+            ));
+        }
+
+        self.current_state = SagaState::AuditInProgress;
+        self.started_at = Some(now);
+        self.metadata.update();
+        Ok(())
+    }
+
+    /// When the next run is due, computed from the start of the last completed run
+    pub fn next_run_at(&self) -> Option<DateTime<Utc>> {
+        self.last_run_at.map(|last_run| self.schedule.next_after(last_run))
+    }
+
+    /// Whether a scheduled audit should start now
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.current_state == SagaState::AuditScheduled
+            && self.next_run_at().is_none_or(|next| now >= next)
+    }
+
+    /// Reset a finished audit so the next run can be scheduled
+    pub fn reschedule(&mut self) -> Result<(), SagaError> {
+        if !self.is_complete() {
+            return Err(SagaError::InvalidTransition(
+                self.current_state.clone(),
+                SagaState::AuditScheduled,
+            ));
+        }
+
+        self.current_state = SagaState::AuditScheduled;
+        self.audit_results.clear();
+        self.findings.clear();
+        self.overall_compliance = None;
+        self.started_at = None;
+        self.metadata.update();
+        Ok(())
+    }
+
+    /// Violation events for every non-compliant policy in a finished audit
+    pub fn violation_events(&self) -> Vec<PolicyEvent> {
+        if self.current_state != SagaState::NonCompliant {
+            return Vec::new();
         }
+
+        let detected_at = self.last_run_at.unwrap_or_else(Utc::now);
+        self.policy_ids
+            .iter()
+            .filter_map(|policy_id| match self.audit_results.get(policy_id) {
+                Some(ComplianceResult::NonCompliant { violations }) => Some((policy_id, violations)),
+                _ => None,
+            })
+            .map(|(policy_id, violations)| {
+                PolicyEvent::PolicyViolationDetected(PolicyViolationDetected {
+                    event_id: Uuid::now_v7(),
+                    identity: MessageIdentity {
+                        correlation_id: CorrelationId::Single(self.metadata.correlation_id),
+                        causation_id: CausationId(self.metadata.id),
+                        message_id: Uuid::now_v7(),
+                    },
+                    policy_id: *policy_id,
+                    violation_id: Uuid::now_v7(),
+                    detected_at,
+                    violations: violations.clone(),
+                    severity: violations
+                        .iter()
+                        .map(|v| v.severity)
+                        .max()
+                        .unwrap_or(Severity::Info),
+                    enforcement_action: None,
+                })
+            })
+            .collect()
     }
 
     /// Add an audit result for a policy
@@ -99,8 +222,17 @@ impl ComplianceAuditSaga {
         }
     }
 
+    /// Results arriving for a scheduled audit implicitly start the run
+    fn begin_if_scheduled(&mut self, at: DateTime<Utc>) {
+        if self.current_state == SagaState::AuditScheduled {
+            self.current_state = SagaState::AuditInProgress;
+            self.started_at = Some(at);
+        }
+    }
+
     /// Determine overall compliance status
     fn determine_compliance_status(&mut self) {
+        self.last_run_at = Some(self.started_at.unwrap_or_else(Utc::now));
         let total = self.audit_results.len();
         let compliant = self.audit_results.values()
             .filter(|r| r.is_compliant())
@@ -182,22 +314,27 @@ impl PolicySaga for ComplianceAuditSaga {
     }
 
     fn apply_event(&mut self, event: &PolicyEvent) -> Result<(), SagaError> {
+        // Results only count towards a run that is still open
+        if self.is_complete() {
+            return Ok(());
+        }
+
         match event {
             PolicyEvent::PolicyEvaluated(e) if self.policy_ids.contains(&e.policy_id) => {
+                self.begin_if_scheduled(e.evaluated_at);
                 self.add_audit_result(e.policy_id, e.result.clone());
-                if self.current_state == SagaState::AuditScheduled {
-                    self.current_state = SagaState::AuditInProgress;
-                }
                 Ok(())
             }
             PolicyEvent::PolicyViolationDetected(e) if self.policy_ids.contains(&e.policy_id) => {
                 let result = ComplianceResult::NonCompliant {
                     violations: e.violations.clone(),
                 };
+                self.begin_if_scheduled(e.detected_at);
                 self.add_audit_result(e.policy_id, result);
                 Ok(())
             }
             PolicyEvent::PolicyCompliancePassed(e) if self.policy_ids.contains(&e.policy_id) => {
+                self.begin_if_scheduled(e.passed_at);
                 self.add_audit_result(e.policy_id, ComplianceResult::Compliant);
                 Ok(())
             }
@@ -237,7 +374,7 @@ impl PolicySaga for ComplianceAuditSaga {
                                 policy_id: finding.policy_id,
                                 suspended_by: self.metadata.initiated_by.clone(),
                                 reason: format!("Critical compliance violation: {}", finding.description),
-                                expected_resume_date: Some(Utc::now() + chrono::Duration::days(7)),
+                                expected_resume_date: Some(self.metadata.now() + chrono::Duration::days(7)),
                             }));
                         }
                     }
@@ -263,4 +400,88 @@ impl PolicySaga for ComplianceAuditSaga {
     fn metadata(&self) -> &SagaMetadata {
         &self.metadata
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn violation(severity: Severity) -> Violation {
+        Violation {
+            rule_id: Uuid::now_v7(),
+            rule_description: "Minimum Key Size".to_string(),
+            severity,
+            details: "Key size 1024 is below 2048".to_string(),
+            suggested_remediation: None,
+        }
+    }
+
+    #[test]
+    fn test_full_audit_cycle_schedules_next_run() {
+        let now = Utc::now();
+        let compliant = PolicyId::new();
+        let violating = PolicyId::new();
+        let mut saga = ComplianceAuditSaga::new(vec![compliant, violating], "auditor".to_string())
+            .with_schedule(AuditSchedule::Interval(chrono::Duration::days(7)));
+
+        assert!(saga.is_due(now));
+        saga.start(now).unwrap();
+        assert_eq!(saga.current_state(), SagaState::AuditInProgress);
+        assert_eq!(saga.get_commands().len(), 2);
+
+        saga.apply_event(&PolicyEvent::PolicyCompliancePassed(PolicyCompliancePassed {
+            event_id: Uuid::now_v7(),
+            identity: create_root_command(),
+            policy_id: compliant,
+            evaluation_id: Uuid::now_v7(),
+            passed_at: now,
+            rules_evaluated: 1,
+        })).unwrap();
+        saga.apply_event(&PolicyEvent::PolicyViolationDetected(PolicyViolationDetected {
+            event_id: Uuid::now_v7(),
+            identity: create_root_command(),
+            policy_id: violating,
+            violation_id: Uuid::now_v7(),
+            detected_at: now,
+            violations: vec![violation(Severity::Medium)],
+            severity: Severity::Medium,
+            enforcement_action: None,
+        })).unwrap();
+
+        assert_eq!(saga.current_state(), SagaState::NonCompliant);
+        let events = saga.violation_events();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0],
+            PolicyEvent::PolicyViolationDetected(e) if e.policy_id == violating
+        ));
+
+        // Emitted violations don't feed back into the finished run
+        saga.apply_event(&events[0]).unwrap();
+        assert_eq!(saga.violation_events().len(), 1);
+
+        assert_eq!(saga.next_run_at(), Some(now + chrono::Duration::days(7)));
+        saga.reschedule().unwrap();
+        assert_eq!(saga.current_state(), SagaState::AuditScheduled);
+        assert!(!saga.is_due(now + chrono::Duration::days(6)));
+        assert!(saga.is_due(now + chrono::Duration::days(7)));
+    }
+
+    #[test]
+    fn test_daily_schedule_rolls_over_to_next_day() {
+        let schedule = AuditSchedule::Daily { hour: 2, minute: 30 };
+        let before = "2025-03-10T01:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let after = "2025-03-10T03:00:00Z".parse::<DateTime<Utc>>().unwrap();
+
+        assert_eq!(schedule.next_after(before), "2025-03-10T02:30:00Z".parse::<DateTime<Utc>>().unwrap());
+        assert_eq!(schedule.next_after(after), "2025-03-11T02:30:00Z".parse::<DateTime<Utc>>().unwrap());
+    }
+
+    #[test]
+    fn test_start_requires_scheduled_audit() {
+        let mut saga = ComplianceAuditSaga::new(vec![PolicyId::new()], "auditor".to_string());
+        saga.start(Utc::now()).unwrap();
+        assert!(saga.start(Utc::now()).is_err());
+        assert!(saga.reschedule().is_err());
+    }
+}
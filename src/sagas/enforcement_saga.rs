@@ -2,7 +2,7 @@
 
 use super::*;
 use crate::aggregate::CompositionRule;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Saga for managing policy enforcement workflow
 pub struct PolicyEnforcementSaga {
@@ -13,6 +13,42 @@ pub struct PolicyEnforcementSaga {
     evaluation_results: HashMap<PolicyId, ComplianceResult>,
     enforcement_decision: Option<EnforcementDecision>,
     composition_rule: CompositionRule,
+    severity_thresholds: SeverityThresholds,
+    remediated: HashSet<(PolicyId, Uuid)>,
+}
+
+/// Weighted-severity thresholds applied on top of the composition rule
+///
+/// Violations are weighted by severity (High 5, Medium 2, Low 1, Info 0.5)
+/// and summed across all evaluated policies. A decision the composition rule
+/// would allow is escalated to `AllowWithWarning` or `Quarantine` once the
+/// score reaches the matching threshold. Any `Critical` violation blocks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeverityThresholds {
+    pub warning_score: f64,
+    pub quarantine_score: f64,
+}
+
+impl Default for SeverityThresholds {
+    fn default() -> Self {
+        Self {
+            warning_score: 1.0,
+            quarantine_score: 10.0,
+        }
+    }
+}
+
+impl SeverityThresholds {
+    /// Weight of a single violation of the given severity
+    pub fn weight(severity: Severity) -> f64 {
+        match severity {
+            Severity::Critical => f64::INFINITY,
+            Severity::High => 5.0,
+            Severity::Medium => 2.0,
+            Severity::Low => 1.0,
+            Severity::Info => 0.5,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -36,6 +72,8 @@ impl PolicyEnforcementSaga {
         markov_chain.add_transition(SagaState::Enforcing, SagaState::Allowed, 0.6);
         markov_chain.add_transition(SagaState::Enforcing, SagaState::Blocked, 0.3);
         markov_chain.add_transition(SagaState::Enforcing, SagaState::Remediation, 0.1);
+        markov_chain.add_transition(SagaState::Blocked, SagaState::Remediation, 0.7);
+        markov_chain.add_transition(SagaState::Remediation, SagaState::Allowed, 0.8);
 
         // Set rewards
         markov_chain.set_state_reward(SagaState::Allowed, 50.0);
@@ -51,9 +89,41 @@ impl PolicyEnforcementSaga {
             evaluation_results: HashMap::new(),
             enforcement_decision: None,
             composition_rule: composition,
+            severity_thresholds: SeverityThresholds::default(),
+            remediated: HashSet::new(),
         }
     }
 
+    /// Read the current time from `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.metadata = self.metadata.with_clock(clock);
+        self
+    }
+
+    /// Use custom weighted-severity thresholds
+    pub fn with_severity_thresholds(mut self, thresholds: SeverityThresholds) -> Self {
+        self.severity_thresholds = thresholds;
+        self
+    }
+
+    /// The enforcement decision, once every policy has been evaluated
+    pub fn enforcement_decision(&self) -> Option<&EnforcementDecision> {
+        self.enforcement_decision.as_ref()
+    }
+
+    /// Sum of severity weights over all recorded violations
+    pub fn severity_score(&self) -> f64 {
+        self.evaluation_results
+            .values()
+            .filter_map(|result| match result {
+                ComplianceResult::NonCompliant { violations } => Some(violations),
+                _ => None,
+            })
+            .flatten()
+            .map(|v| SeverityThresholds::weight(v.severity))
+            .sum()
+    }
+
     /// Add evaluation result for a policy
     pub fn add_evaluation_result(&mut self, policy_id: PolicyId, result: ComplianceResult) {
         self.evaluation_results.insert(policy_id, result);
@@ -65,14 +135,14 @@ impl PolicyEnforcementSaga {
         }
     }
 
-    /// Make enforcement decision based on composition rule
+    /// Make enforcement decision based on composition rule and violation severity
     fn make_enforcement_decision(&mut self) {
         let compliant_count = self.evaluation_results.values()
             .filter(|r| r.is_compliant())
             .count();
         let total = self.evaluation_results.len();
 
-        self.enforcement_decision = Some(match self.composition_rule {
+        let decision = match self.composition_rule {
             CompositionRule::All => {
                 if compliant_count == total {
                     EnforcementDecision::Allow
@@ -101,6 +171,21 @@ impl PolicyEnforcementSaga {
                     EnforcementDecision::Block
                 }
             }
+        };
+
+        // Escalate by weighted severity; a critical violation always blocks
+        let score = self.severity_score();
+        self.enforcement_decision = Some(match decision {
+            _ if score.is_infinite() => EnforcementDecision::Block,
+            EnforcementDecision::Allow | EnforcementDecision::AllowWithWarning
+                if score >= self.severity_thresholds.quarantine_score =>
+            {
+                EnforcementDecision::Quarantine
+            }
+            EnforcementDecision::Allow if score >= self.severity_thresholds.warning_score => {
+                EnforcementDecision::AllowWithWarning
+            }
+            decision => decision,
         });
 
         // Update state based on decision
@@ -115,6 +200,83 @@ impl PolicyEnforcementSaga {
         };
     }
 
+    /// Mark a violation as remediated
+    ///
+    /// The first remediation moves a blocked saga into `Remediation`. Once
+    /// every violation with a suggested remediation has been remediated the
+    /// saga moves to `Allowed` and a `PolicyCompliancePassed` event is
+    /// returned for each previously non-compliant policy.
+    pub fn remediate(&mut self, command: &RemediateViolation) -> Result<Vec<PolicyEvent>, SagaError> {
+        if !matches!(self.current_state, SagaState::Blocked | SagaState::Remediation) {
+            return Err(SagaError::InvalidTransition(
+                self.current_state.clone(),
+                SagaState::Remediation,
+            ));
+        }
+
+        // Validate before touching state so a bad command leaves the saga as it was
+        let key = (command.policy_id, command.rule_id);
+        if !self.remediable_violations().contains(&key) {
+            return Err(SagaError::MissingData(format!(
+                "No remediable violation of rule {} in policy {}",
+                command.rule_id, command.policy_id.0
+            )));
+        }
+
+        self.current_state = SagaState::Remediation;
+        self.remediated.insert(key);
+        self.metadata.update();
+
+        if !self.remaining_remediations().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.current_state = SagaState::Allowed;
+        let now = self.metadata.now();
+        Ok(self
+            .policy_ids
+            .iter()
+            .filter_map(|policy_id| match self.evaluation_results.get(policy_id) {
+                Some(ComplianceResult::NonCompliant { violations }) => Some((policy_id, violations.len())),
+                _ => None,
+            })
+            .map(|(policy_id, rules_evaluated)| {
+                PolicyEvent::PolicyCompliancePassed(PolicyCompliancePassed {
+                    event_id: Uuid::now_v7(),
+                    identity: super::create_caused_by(&command.identity),
+                    policy_id: *policy_id,
+                    evaluation_id: Uuid::now_v7(),
+                    passed_at: now,
+                    rules_evaluated,
+                })
+            })
+            .collect())
+    }
+
+    /// Violations with a suggested remediation that are not yet remediated
+    pub fn remaining_remediations(&self) -> Vec<(PolicyId, Uuid)> {
+        self.remediable_violations()
+            .into_iter()
+            .filter(|key| !self.remediated.contains(key))
+            .collect()
+    }
+
+    fn remediable_violations(&self) -> Vec<(PolicyId, Uuid)> {
+        self.evaluation_results
+            .iter()
+            .filter_map(|(policy_id, result)| match result {
+                ComplianceResult::NonCompliant { violations } => Some((policy_id, violations)),
+                _ => None,
+            })
+            .flat_map(|(policy_id, violations)| {
+                violations
+                    .iter()
+                    .filter(|v| v.suggested_remediation.is_some())
+                    .map(|v| (*policy_id, v.rule_id))
+            })
+            .collect()
+    }
+
     /// Get remediation steps for non-compliant policies
     pub fn get_remediation_steps(&self) -> Vec<String> {
         let mut steps = Vec::new();
@@ -148,6 +310,7 @@ impl PolicySaga for PolicyEnforcementSaga {
                 StateTransition::Remediate,
             ],
             SagaState::Blocked => vec![StateTransition::Remediate],
+            SagaState::Remediation => vec![StateTransition::Remediate, StateTransition::Allow],
             _ => vec![],
         }
     }
@@ -249,4 +412,138 @@ impl PolicySaga for PolicyEnforcementSaga {
     fn metadata(&self) -> &SagaMetadata {
         &self.metadata
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn violation(remediation: &str) -> Violation {
+        violation_with(Severity::High, Some(remediation))
+    }
+
+    fn violation_with(severity: Severity, remediation: Option<&str>) -> Violation {
+        Violation {
+            rule_id: Uuid::now_v7(),
+            rule_description: "Minimum Key Size".to_string(),
+            severity,
+            details: "Key size is too small".to_string(),
+            suggested_remediation: remediation.map(str::to_string),
+        }
+    }
+
+    fn remediate_command(policy_id: PolicyId, rule_id: Uuid) -> RemediateViolation {
+        RemediateViolation {
+            identity: create_root_command(),
+            policy_id,
+            rule_id,
+            remediated_by: "operator".to_string(),
+            notes: None,
+        }
+    }
+
+    fn blocked_saga(policy_id: PolicyId, violations: Vec<Violation>) -> PolicyEnforcementSaga {
+        let mut saga = PolicyEnforcementSaga::new(vec![policy_id], "operator".to_string(), CompositionRule::All);
+        saga.add_evaluation_result(policy_id, ComplianceResult::NonCompliant { violations });
+        assert_eq!(saga.current_state(), SagaState::Blocked);
+        saga
+    }
+
+    #[test]
+    fn test_partial_remediation_stays_in_remediation() {
+        let policy_id = PolicyId::new();
+        let violations = vec![violation("Rotate key"), violation("Reissue certificate")];
+        let mut saga = blocked_saga(policy_id, violations.clone());
+
+        let events = saga.remediate(&remediate_command(policy_id, violations[0].rule_id)).unwrap();
+        assert!(events.is_empty());
+        assert_eq!(saga.current_state(), SagaState::Remediation);
+        assert_eq!(saga.remaining_remediations(), vec![(policy_id, violations[1].rule_id)]);
+
+        // Unknown violations can't be remediated
+        assert!(saga.remediate(&remediate_command(policy_id, Uuid::now_v7())).is_err());
+    }
+
+    #[test]
+    fn test_unknown_rule_leaves_blocked_saga_untouched() {
+        let policy_id = PolicyId::new();
+        let mut saga = blocked_saga(policy_id, vec![violation("Rotate key")]);
+        let version = saga.metadata.version;
+
+        assert!(matches!(
+            saga.remediate(&remediate_command(policy_id, Uuid::now_v7())),
+            Err(SagaError::MissingData(_))
+        ));
+        assert_eq!(saga.current_state(), SagaState::Blocked);
+        assert_eq!(saga.metadata.version, version);
+    }
+
+    #[test]
+    fn test_full_remediation_allows_and_passes_compliance() {
+        let policy_id = PolicyId::new();
+        let violations = vec![violation("Rotate key"), violation("Reissue certificate")];
+        let remediated_at: DateTime<Utc> = "2025-06-01T09:00:00Z".parse().unwrap();
+        let mut saga = blocked_saga(policy_id, violations.clone())
+            .with_clock(Arc::new(crate::MockClock::new(remediated_at)));
+
+        saga.remediate(&remediate_command(policy_id, violations[0].rule_id)).unwrap();
+        let events = saga.remediate(&remediate_command(policy_id, violations[1].rule_id)).unwrap();
+
+        assert_eq!(saga.current_state(), SagaState::Allowed);
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0],
+            PolicyEvent::PolicyCompliancePassed(e)
+                if e.policy_id == policy_id && e.passed_at == remediated_at
+        ));
+        assert!(saga.remediate(&remediate_command(policy_id, violations[0].rule_id)).is_err());
+    }
+
+    fn majority_saga(last: ComplianceResult) -> PolicyEnforcementSaga {
+        let policy_ids = vec![PolicyId::new(), PolicyId::new(), PolicyId::new()];
+        let mut saga = PolicyEnforcementSaga::new(policy_ids.clone(), "operator".to_string(), CompositionRule::Majority);
+        saga.add_evaluation_result(policy_ids[0], ComplianceResult::Compliant);
+        saga.add_evaluation_result(policy_ids[1], ComplianceResult::Compliant);
+        saga.add_evaluation_result(policy_ids[2], last);
+        saga
+    }
+
+    #[test]
+    fn test_single_critical_violation_blocks_compliant_majority() {
+        let saga = majority_saga(ComplianceResult::NonCompliant {
+            violations: vec![violation_with(Severity::Critical, None)],
+        });
+
+        assert_eq!(saga.enforcement_decision(), Some(&EnforcementDecision::Block));
+        assert_eq!(saga.current_state(), SagaState::Blocked);
+
+        // The same majority with a minor violation is only a warning
+        let saga = majority_saga(ComplianceResult::NonCompliant {
+            violations: vec![violation_with(Severity::Low, None)],
+        });
+        assert_eq!(saga.enforcement_decision(), Some(&EnforcementDecision::AllowWithWarning));
+        assert_eq!(saga.current_state(), SagaState::Allowed);
+    }
+
+    #[test]
+    fn test_weighted_severity_quarantines_past_threshold() {
+        let violations = vec![
+            violation_with(Severity::High, None),
+            violation_with(Severity::High, None),
+        ];
+        let saga = majority_saga(ComplianceResult::NonCompliant { violations: violations.clone() });
+        assert_eq!(saga.severity_score(), 10.0);
+        assert_eq!(saga.enforcement_decision(), Some(&EnforcementDecision::Quarantine));
+
+        // Raising the quarantine threshold lets the same violations through with a warning
+        let policy_ids = vec![PolicyId::new(), PolicyId::new()];
+        let mut saga = PolicyEnforcementSaga::new(policy_ids.clone(), "operator".to_string(), CompositionRule::Any)
+            .with_severity_thresholds(SeverityThresholds {
+                warning_score: 1.0,
+                quarantine_score: 20.0,
+            });
+        saga.add_evaluation_result(policy_ids[0], ComplianceResult::Compliant);
+        saga.add_evaluation_result(policy_ids[1], ComplianceResult::NonCompliant { violations });
+        assert_eq!(saga.enforcement_decision(), Some(&EnforcementDecision::AllowWithWarning));
+    }
+}
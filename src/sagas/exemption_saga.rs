@@ -76,6 +76,12 @@ impl ExemptionWorkflowSaga {
         }
     }
 
+    /// Read the current time from `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.metadata = self.metadata.with_clock(clock);
+        self
+    }
+
     /// Set risk assessment for the exemption
     pub fn set_risk_assessment(&mut self, level: RiskLevel, notes: String) {
         self.risk_assessment = Some((level, notes));
@@ -116,7 +122,7 @@ impl ExemptionWorkflowSaga {
     pub fn grant_exemption(&mut self, conditions: Vec<ExemptionCondition>, duration: Duration) {
         self.exemption_conditions = conditions;
         self.exemption_id = Some(ExemptionId::new());
-        self.expiry = Some(Utc::now() + duration);
+        self.expiry = Some(self.metadata.now() + duration);
         self.current_state = SagaState::ExemptionGranted;
         self.metadata.update();
     }
@@ -129,7 +135,7 @@ impl ExemptionWorkflowSaga {
     /// Check if exemption has expired
     pub fn check_expiry(&mut self) {
         if let Some(expiry) = self.expiry {
-            if Utc::now() > expiry {
+            if self.metadata.now() > expiry {
                 self.current_state = SagaState::ExemptionExpired;
                 self.metadata.update();
             }
@@ -234,8 +240,8 @@ impl PolicySaga for ExemptionWorkflowSaga {
                         .map(|(just, _)| just.clone())
                         .unwrap_or_default(),
                     risk_acceptance,
-                    valid_from: Utc::now(),
-                    valid_until: self.expiry.unwrap_or_else(|| Utc::now() + Duration::days(30)),
+                    valid_from: self.metadata.now(),
+                    valid_until: self.expiry.unwrap_or_else(|| self.metadata.now() + Duration::days(30)),
                     conditions: self.exemption_conditions.clone(),
                 }));
             }
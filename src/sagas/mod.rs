@@ -1,11 +1,13 @@
 //! Sagas for policy domain - aggregates of aggregates with Markov chain state machines
 
+use crate::clock::{system_clock, Clock};
 use crate::commands::*;
 use crate::events::*;
 use crate::value_objects::*;
 use cim_domain::{MessageIdentity, CorrelationId, CausationId};
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
+use std::sync::Arc;
 use uuid::Uuid;
 
 /// Helper function to create a root command message identity
@@ -18,15 +20,24 @@ pub fn create_root_command() -> MessageIdentity {
     }
 }
 
+/// Helper function to create the identity of a message caused by another message
+pub fn create_caused_by(cause: &MessageIdentity) -> MessageIdentity {
+    MessageIdentity {
+        correlation_id: cause.correlation_id.clone(),
+        causation_id: CausationId(cause.message_id),
+        message_id: Uuid::now_v7(),
+    }
+}
+
 pub mod approval_saga;
 pub mod enforcement_saga;
 pub mod exemption_saga;
 pub mod audit_saga;
 
 pub use approval_saga::PolicyApprovalSaga;
-pub use enforcement_saga::PolicyEnforcementSaga;
+pub use enforcement_saga::{PolicyEnforcementSaga, SeverityThresholds};
 pub use exemption_saga::ExemptionWorkflowSaga;
-pub use audit_saga::ComplianceAuditSaga;
+pub use audit_saga::{AuditSchedule, ComplianceAuditSaga};
 
 /// Base trait for all sagas (aggregates of aggregates)
 pub trait PolicySaga: Send + Sync {
@@ -140,7 +151,10 @@ pub enum StateTransition {
 }
 
 /// Metadata for saga tracking
-#[derive(Debug, Clone)]
+///
+/// Also holds the saga's clock, so every timestamp a saga records comes
+/// from the same source.
+#[derive(Clone)]
 pub struct SagaMetadata {
     pub id: Uuid,
     pub correlation_id: Uuid,
@@ -150,11 +164,28 @@ pub struct SagaMetadata {
     pub last_updated: DateTime<Utc>,
     pub version: u32,
     pub tags: HashMap<String, String>,
+    clock: Arc<dyn Clock>,
+}
+
+impl std::fmt::Debug for SagaMetadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SagaMetadata")
+            .field("id", &self.id)
+            .field("correlation_id", &self.correlation_id)
+            .field("causation_id", &self.causation_id)
+            .field("initiated_at", &self.initiated_at)
+            .field("initiated_by", &self.initiated_by)
+            .field("last_updated", &self.last_updated)
+            .field("version", &self.version)
+            .field("tags", &self.tags)
+            .finish_non_exhaustive()
+    }
 }
 
 impl SagaMetadata {
     pub fn new(initiated_by: String) -> Self {
-        let now = Utc::now();
+        let clock = system_clock();
+        let now = clock.now();
         Self {
             id: Uuid::now_v7(),
             correlation_id: Uuid::now_v7(),
@@ -164,16 +195,31 @@ impl SagaMetadata {
             last_updated: now,
             version: 1,
             tags: HashMap::new(),
+            clock,
         }
     }
 
+    /// Read the current time from `clock`, restamping the initiation time
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        let now = clock.now();
+        self.initiated_at = now;
+        self.last_updated = now;
+        self.clock = clock;
+        self
+    }
+
     pub fn with_causation(mut self, causation_id: Uuid) -> Self {
         self.causation_id = Some(causation_id);
         self
     }
 
+    /// Current time on the saga's clock
+    pub fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+
     pub fn update(&mut self) {
-        self.last_updated = Utc::now();
+        self.last_updated = self.clock.now();
         self.version += 1;
     }
 }
@@ -324,6 +370,12 @@ impl CompositeSaga {
         }
     }
 
+    /// Read the current time from `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.metadata = self.metadata.with_clock(clock);
+        self
+    }
+
     /// Add a sub-saga to coordinate
     pub fn add_sub_saga(&mut self, saga: Box<dyn PolicySaga>) {
         self.sub_sagas.push(saga);
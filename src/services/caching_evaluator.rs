@@ -0,0 +1,326 @@
+//! Caching wrapper around the policy evaluator
+
+use super::{EvaluationError, PolicyEvaluator};
+use crate::aggregate::{Policy, PolicyExemption};
+use crate::entities::PolicyEvaluation;
+use crate::value_objects::*;
+use indexmap::IndexMap;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Cache key: the policy, the version evaluated, and the context key
+type CacheKey = (PolicyId, u32, String);
+
+/// Cached results and the policy version they were cached for
+#[derive(Default)]
+struct CacheState {
+    entries: IndexMap<CacheKey, (PolicyEvaluation, Instant)>,
+    versions: HashMap<PolicyId, u32>,
+}
+
+/// Policy evaluator that remembers recent results
+///
+/// Evaluations are cached by `(policy.id, policy.version, context key)`. The
+/// context key covers the context's fields, requester and environment; its
+/// exact timestamp also counts for policies with time constraints, custom
+/// predicates or exemptions, since their result can change at any instant.
+/// The cache holds at most `capacity` entries, evicting the least recently
+/// used, and entries older than `ttl` are re-evaluated. Evaluating a policy
+/// at a new version, or registering exemptions for it, drops every entry
+/// cached for it. Failed evaluations, and results granted by usage-limited
+/// exemptions, are never cached, and neither are contexts that can't be
+/// hashed.
+pub struct CachingPolicyEvaluator {
+    evaluator: PolicyEvaluator,
+    capacity: usize,
+    ttl: Duration,
+    state: Mutex<CacheState>,
+}
+
+impl CachingPolicyEvaluator {
+    /// Wrap an evaluator with a cache of `capacity` entries living for `ttl`
+    pub fn new(evaluator: PolicyEvaluator, capacity: usize, ttl: Duration) -> Self {
+        Self {
+            evaluator,
+            capacity,
+            ttl,
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    /// Evaluate a policy, reusing a cached result when one is fresh
+    pub fn evaluate(
+        &self,
+        policy: &Policy,
+        context: &EvaluationContext,
+    ) -> Result<PolicyEvaluation, EvaluationError> {
+        // Whether the policy is in force depends on the exact timestamp
+        if !policy.is_effective_at(context.timestamp) {
+            return self.evaluator.evaluate(policy, context);
+        }
+
+        let Some(context_key) = self.context_key(policy, context) else {
+            return self.evaluator.evaluate(policy, context);
+        };
+        let key = (policy.id, policy.version, context_key);
+
+        {
+            let mut state = self.state.lock().unwrap();
+            if state.versions.insert(policy.id, policy.version) != Some(policy.version) {
+                state
+                    .entries
+                    .retain(|(id, version, _), _| *id != policy.id || *version == policy.version);
+            }
+
+            // Remove and re-insert on a hit to mark the entry most recently used
+            if let Some((evaluation, cached_at)) = state.entries.shift_remove(&key) {
+                if cached_at.elapsed() < self.ttl {
+                    state.entries.insert(key, (evaluation.clone(), cached_at));
+                    return Ok(evaluation);
+                }
+            }
+        }
+
+        let evaluation = self.evaluator.evaluate(policy, context)?;
+
+        // Each use of a usage-limited exemption has to be counted
+        if let ComplianceResult::CompliantWithExemption { exemption_id } = &evaluation.overall_result {
+            if self.evaluator.exemption_is_usage_limited(policy.id, *exemption_id) {
+                return Ok(evaluation);
+            }
+        }
+
+        let mut state = self.state.lock().unwrap();
+        if self.capacity > 0 {
+            while state.entries.len() >= self.capacity {
+                state.entries.shift_remove_index(0);
+            }
+            state.entries.insert(key, (evaluation.clone(), Instant::now()));
+        }
+        Ok(evaluation)
+    }
+
+    /// Register exemptions with the wrapped evaluator
+    ///
+    /// Results cached for the exempted policies are dropped, as the
+    /// exemptions may change them.
+    pub fn register_exemptions(&mut self, exemptions: Vec<PolicyExemption>) {
+        let policy_ids: Vec<_> = exemptions.iter().map(|exemption| exemption.policy_id).collect();
+        self.evaluator.register_exemptions(exemptions);
+        for policy_id in policy_ids {
+            self.invalidate(policy_id);
+        }
+    }
+
+    /// Drop every cached result for a policy
+    pub fn invalidate(&self, policy_id: PolicyId) {
+        let mut state = self.state.lock().unwrap();
+        state.versions.remove(&policy_id);
+        state.entries.retain(|(id, _, _), _| *id != policy_id);
+    }
+
+    /// Number of cached results
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().entries.len()
+    }
+
+    /// Whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Hex-encoded hash of the parts of a context the policy's result depends on
+    ///
+    /// `None` when they can't be hashed.
+    fn context_key(&self, policy: &Policy, context: &EvaluationContext) -> Option<String> {
+        let time_dependent = self.evaluator.has_exemptions(policy.id)
+            || policy.rules.iter().any(|rule| reads_time(&rule.expression));
+        let timestamp = time_dependent.then_some(context.timestamp);
+
+        let hash =
+            content_hash(&(&context.fields, &context.requester, &context.environment, timestamp))?;
+        Some(hash.iter().map(|b| format!("{:02x}", b)).collect())
+    }
+}
+
+/// Whether an expression may depend on the context timestamp
+///
+/// Custom predicates see the whole context, so they are assumed to.
+fn reads_time(expression: &RuleExpression) -> bool {
+    match expression {
+        RuleExpression::WithinTimeConstraint { .. } | RuleExpression::Custom { .. } => true,
+        RuleExpression::And(children) | RuleExpression::Or(children) => {
+            children.iter().any(reads_time)
+        }
+        RuleExpression::Not(inner) => reads_time(inner),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::PolicyRule;
+
+    fn active_policy() -> Policy {
+        let mut policy = Policy::new("Key Size", "Minimum key size");
+        policy.status = PolicyStatus::Active;
+        policy.add_rule(PolicyRule::min_key_size(2048));
+        policy
+    }
+
+    fn cache(capacity: usize, ttl: Duration) -> CachingPolicyEvaluator {
+        CachingPolicyEvaluator::new(PolicyEvaluator::new(), capacity, ttl)
+    }
+
+    #[test]
+    fn test_repeated_context_hits_cache() {
+        let cache = cache(10, Duration::from_secs(60));
+        let policy = active_policy();
+        let context = EvaluationContext::new().with_field("key_size", 4096);
+
+        let first = cache.evaluate(&policy, &context).unwrap();
+        let second = cache.evaluate(&policy, &context).unwrap();
+        assert_eq!(first.id, second.id);
+        assert_eq!(cache.len(), 1);
+
+        let other = context.clone().with_field("key_size", 1024);
+        let third = cache.evaluate(&policy, &other).unwrap();
+        assert_ne!(third.id, first.id);
+        assert!(!third.is_compliant());
+    }
+
+    #[test]
+    fn test_version_bump_misses_and_evicts_old_version() {
+        let cache = cache(10, Duration::from_secs(60));
+        let mut policy = active_policy();
+        let context = EvaluationContext::new().with_field("key_size", 4096);
+
+        let first = cache.evaluate(&policy, &context).unwrap();
+        policy.version += 1;
+        policy.rules = vec![PolicyRule::min_key_size(8192)];
+
+        let second = cache.evaluate(&policy, &context).unwrap();
+        assert_ne!(first.id, second.id);
+        assert!(!second.is_compliant());
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_entries_expire_after_ttl() {
+        let cache = cache(10, Duration::from_millis(20));
+        let policy = active_policy();
+        let context = EvaluationContext::new().with_field("key_size", 4096);
+
+        let first = cache.evaluate(&policy, &context).unwrap();
+        std::thread::sleep(Duration::from_millis(30));
+        let second = cache.evaluate(&policy, &context).unwrap();
+        assert_ne!(first.id, second.id);
+    }
+
+    #[test]
+    fn test_least_recently_used_entry_is_evicted() {
+        let cache = cache(2, Duration::from_secs(60));
+        let policy = active_policy();
+        let a = EvaluationContext::new().with_field("key_size", 2048);
+        let b = EvaluationContext::new().with_field("key_size", 3072);
+        let c = EvaluationContext::new().with_field("key_size", 4096);
+
+        let first_a = cache.evaluate(&policy, &a).unwrap();
+        cache.evaluate(&policy, &b).unwrap();
+        cache.evaluate(&policy, &a).unwrap();
+        cache.evaluate(&policy, &c).unwrap();
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.evaluate(&policy, &a).unwrap().id, first_a.id);
+    }
+
+    #[test]
+    fn test_timestamp_only_counts_for_time_dependent_policies() {
+        let cache = cache(10, Duration::from_secs(60));
+        let policy = active_policy();
+        let mut context = EvaluationContext::new().with_field("key_size", 4096);
+        context.timestamp = "2026-03-02T10:00:05Z".parse().unwrap();
+
+        let first = cache.evaluate(&policy, &context).unwrap();
+        let mut later = context.clone();
+        later.timestamp = "2026-03-02T11:30:00Z".parse().unwrap();
+        assert_eq!(cache.evaluate(&policy, &later).unwrap().id, first.id);
+
+        let mut timed = active_policy();
+        timed.add_rule(PolicyRule::new(
+            "Office hours",
+            "Only during office hours",
+            RuleExpression::WithinTimeConstraint {
+                constraint: TimeConstraint {
+                    timezone: "UTC".to_string(),
+                    allowed_days: Vec::new(),
+                    allowed_hours: Some((9, 11)),
+                    blackout_periods: Vec::new(),
+                },
+            },
+            Severity::Medium,
+        ));
+
+        let first = cache.evaluate(&timed, &context).unwrap();
+        assert!(first.is_compliant());
+
+        assert_eq!(cache.evaluate(&timed, &context).unwrap().id, first.id);
+
+        let mut same_minute = context.clone();
+        same_minute.timestamp = "2026-03-02T10:00:50Z".parse().unwrap();
+        assert_ne!(cache.evaluate(&timed, &same_minute).unwrap().id, first.id);
+
+        let outside = cache.evaluate(&timed, &later).unwrap();
+        assert_ne!(outside.id, first.id);
+        assert!(!outside.is_compliant());
+    }
+
+    #[test]
+    fn test_exemption_lapsing_within_a_minute_is_not_served_from_cache() {
+        let mut cache = cache(10, Duration::from_secs(60));
+        let policy = active_policy();
+        let mut context = EvaluationContext::new().with_field("key_size", 1024);
+        context.timestamp = "2026-03-02T10:00:05Z".parse().unwrap();
+
+        // Cached before the exemption exists, then dropped when it is registered
+        assert!(!cache.evaluate(&policy, &context).unwrap().is_compliant());
+        let mut exemption = PolicyExemption::new(
+            policy.id,
+            "Legacy HSM",
+            "Replaced this morning",
+            "admin",
+            "2026-03-02T10:00:30Z".parse().unwrap(),
+        );
+        exemption.valid_from = "2026-03-02T09:00:00Z".parse().unwrap();
+        cache.register_exemptions(vec![exemption]);
+
+        assert!(matches!(
+            cache.evaluate(&policy, &context).unwrap().overall_result,
+            ComplianceResult::CompliantWithExemption { .. }
+        ));
+
+        let mut lapsed = context.clone();
+        lapsed.timestamp = "2026-03-02T10:00:55Z".parse().unwrap();
+        assert!(!cache.evaluate(&policy, &lapsed).unwrap().is_compliant());
+    }
+
+    #[test]
+    fn test_policy_not_in_force_is_never_served_from_cache() {
+        let cache = cache(10, Duration::from_secs(60));
+        let mut policy = active_policy();
+        let now = chrono::Utc::now();
+        policy.expiry_date = Some(now + chrono::Duration::hours(1));
+        let context = EvaluationContext::new().with_field("key_size", 4096);
+
+        assert!(cache.evaluate(&policy, &context).is_ok());
+
+        let mut after = context.clone();
+        after.timestamp = now + chrono::Duration::hours(2);
+        assert!(matches!(
+            cache.evaluate(&policy, &after),
+            Err(EvaluationError::PolicyNotActive(_))
+        ));
+    }
+}
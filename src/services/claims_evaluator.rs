@@ -0,0 +1,283 @@
+//! Claims-based policy evaluation service
+
+use crate::aggregate::ClaimsPolicy;
+use crate::clock::Clock;
+use crate::value_objects::*;
+use serde::{Deserialize, Serialize};
+
+/// Access decision together with the policy that produced it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthorizationDecision {
+    pub effect: PolicyEffect,
+    /// Index into the evaluated policies of the deciding policy, if any
+    pub deciding_policy: Option<usize>,
+    /// Resource pattern of the deciding policy that matched the resource
+    pub matched_resource: Option<String>,
+    pub reason: String,
+}
+
+impl AuthorizationDecision {
+    fn denied(reason: &str) -> Self {
+        Self {
+            effect: PolicyEffect::Deny,
+            deciding_policy: None,
+            matched_resource: None,
+            reason: reason.to_string(),
+        }
+    }
+
+    fn decided_by(
+        effect: PolicyEffect,
+        index: usize,
+        policy: &ClaimsPolicy,
+        resource: &str,
+        reason: &str,
+    ) -> Self {
+        Self {
+            effect,
+            deciding_policy: Some(index),
+            matched_resource: policy
+                .resources
+                .iter()
+                .find(|pattern| pattern.matches(resource))
+                .map(|pattern| pattern.pattern.clone()),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// Authorize access to a resource, recording which policy decided
+///
+/// Deny-overrides: the first applicable Deny decides; otherwise the first
+/// applicable Allow does. Without either, or with claims that at the clock's
+/// current time are expired or not yet valid (before `not_before`), access
+/// is denied and no policy is recorded.
+pub fn authorize(
+    policies: &[ClaimsPolicy],
+    resource: &str,
+    claims: &ClaimSet,
+    clock: &dyn Clock,
+) -> AuthorizationDecision {
+    let now = clock.now();
+    if claims.expires_at.is_some_and(|expires_at| now >= expires_at) {
+        return AuthorizationDecision::denied("claims expired");
+    }
+    if claims.not_before.is_some_and(|not_before| now < not_before) {
+        return AuthorizationDecision::denied("claims not yet valid");
+    }
+
+    let mut first_allow = None;
+
+    for (index, policy) in policies.iter().enumerate() {
+        if !policy.applies(resource, claims) {
+            continue;
+        }
+
+        match policy.effect {
+            PolicyEffect::Deny => {
+                return AuthorizationDecision::decided_by(
+                    PolicyEffect::Deny,
+                    index,
+                    policy,
+                    resource,
+                    "explicit deny",
+                );
+            }
+            PolicyEffect::Allow => {
+                first_allow.get_or_insert(index);
+            }
+        }
+    }
+
+    match first_allow {
+        Some(index) => AuthorizationDecision::decided_by(
+            PolicyEffect::Allow,
+            index,
+            &policies[index],
+            resource,
+            "matching allow",
+        ),
+        None => AuthorizationDecision::denied("no matching allow"),
+    }
+}
+
+/// Evaluate claims policies for a resource using deny-overrides semantics
+///
+/// Any applicable Deny wins, otherwise any applicable Allow grants access.
+/// When no policy applies the result is Deny.
+pub fn evaluate_claims_policies(
+    policies: &[ClaimsPolicy],
+    resource: &str,
+    claims: &ClaimSet,
+    clock: &dyn Clock,
+) -> PolicyEffect {
+    decide_claims_access(policies, resource, claims, clock).effect()
+}
+
+/// Decide access for a resource, reporting why access was denied
+///
+/// A claim set that is expired or not yet valid has no usable claims, so it
+/// is denied before any policy is consulted.
+pub fn decide_claims_access(
+    policies: &[ClaimsPolicy],
+    resource: &str,
+    claims: &ClaimSet,
+    clock: &dyn Clock,
+) -> EvaluationDecision {
+    let decision = authorize(policies, resource, claims, clock);
+    match decision.effect {
+        PolicyEffect::Allow => EvaluationDecision::Allowed,
+        PolicyEffect::Deny => EvaluationDecision::denied(decision.reason),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::{MockClock, SystemClock};
+
+    fn claims(pairs: &[(&str, &str)]) -> ClaimSet {
+        let mut set = ClaimSet::new("alice".to_string());
+        for (claim_type, claim_value) in pairs {
+            set.add_claim(Claim::new(claim_type.to_string(), claim_value.to_string()));
+        }
+        set
+    }
+
+    fn policy(effect: PolicyEffect, pattern: &str, claim_type: &str, claim_value: &str) -> ClaimsPolicy {
+        ClaimsPolicy::new(
+            effect,
+            vec![ResourcePattern::new(pattern.to_string(), PatternType::Glob)],
+            PolicyCondition::HasClaim {
+                claim_type: claim_type.to_string(),
+                claim_value: claim_value.to_string(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_matching_allow_grants_access() {
+        let policies = vec![policy(PolicyEffect::Allow, "documents/*", "role", "reader")];
+        let subject = claims(&[("role", "reader")]);
+
+        assert_eq!(
+            evaluate_claims_policies(&policies, "documents/report", &subject, &SystemClock),
+            PolicyEffect::Allow
+        );
+    }
+
+    #[test]
+    fn test_explicit_deny_overrides_allow() {
+        let policies = vec![
+            policy(PolicyEffect::Allow, "documents/*", "role", "reader"),
+            policy(PolicyEffect::Deny, "documents/secret*", "status", "contractor"),
+        ];
+        let subject = claims(&[("role", "reader"), ("status", "contractor")]);
+
+        assert_eq!(
+            evaluate_claims_policies(&policies, "documents/secret-plan", &subject, &SystemClock),
+            PolicyEffect::Deny
+        );
+        assert_eq!(
+            evaluate_claims_policies(&policies, "documents/report", &subject, &SystemClock),
+            PolicyEffect::Allow
+        );
+    }
+
+    #[test]
+    fn test_no_matching_policy_denies_by_default() {
+        let policies = vec![policy(PolicyEffect::Allow, "documents/*", "role", "reader")];
+
+        assert_eq!(
+            evaluate_claims_policies(&policies, "keys/root", &claims(&[("role", "reader")]), &SystemClock),
+            PolicyEffect::Deny
+        );
+        assert_eq!(
+            evaluate_claims_policies(&policies, "documents/report", &claims(&[("role", "guest")]), &SystemClock),
+            PolicyEffect::Deny
+        );
+    }
+
+    #[test]
+    fn test_expired_claims_are_denied() {
+        let policies = vec![policy(PolicyEffect::Allow, "documents/*", "role", "reader")];
+        let mut subject = claims(&[("role", "reader")]);
+        let expires_at = "2026-03-02T10:00:00Z".parse().unwrap();
+        subject.expires_at = Some(expires_at);
+
+        let before = MockClock::new(expires_at - chrono::Duration::seconds(1));
+        assert_eq!(
+            decide_claims_access(&policies, "documents/report", &subject, &before),
+            EvaluationDecision::Allowed
+        );
+
+        let clock = MockClock::new(expires_at);
+        assert_eq!(
+            decide_claims_access(&policies, "documents/report", &subject, &clock),
+            EvaluationDecision::denied("claims expired")
+        );
+        assert_eq!(
+            evaluate_claims_policies(&policies, "documents/report", &subject, &clock),
+            PolicyEffect::Deny
+        );
+    }
+
+    #[test]
+    fn test_claims_not_yet_valid_are_denied() {
+        let policies = vec![policy(PolicyEffect::Allow, "documents/*", "role", "reader")];
+        let mut subject = claims(&[("role", "reader")]);
+        let not_before = "2026-03-02T10:00:00Z".parse().unwrap();
+        subject.not_before = Some(not_before);
+
+        let early = MockClock::new(not_before - chrono::Duration::seconds(1));
+        assert_eq!(
+            decide_claims_access(&policies, "documents/report", &subject, &early),
+            EvaluationDecision::denied("claims not yet valid")
+        );
+        assert_eq!(
+            decide_claims_access(&policies, "documents/report", &subject, &MockClock::new(not_before)),
+            EvaluationDecision::Allowed
+        );
+    }
+
+    #[test]
+    fn test_authorize_records_denying_policy() {
+        let policies = vec![
+            policy(PolicyEffect::Allow, "documents/*", "role", "reader"),
+            policy(PolicyEffect::Allow, "documents/secret*", "role", "reader"),
+            policy(PolicyEffect::Deny, "documents/secret*", "status", "contractor"),
+        ];
+        let subject = claims(&[("role", "reader"), ("status", "contractor")]);
+
+        assert_eq!(
+            authorize(&policies, "documents/secret-plan", &subject, &SystemClock),
+            AuthorizationDecision {
+                effect: PolicyEffect::Deny,
+                deciding_policy: Some(2),
+                matched_resource: Some("documents/secret*".to_string()),
+                reason: "explicit deny".to_string(),
+            }
+        );
+
+        let allowed = authorize(&policies, "documents/report", &subject, &SystemClock);
+        assert_eq!(allowed.effect, PolicyEffect::Allow);
+        assert_eq!(allowed.deciding_policy, Some(0));
+        assert_eq!(allowed.matched_resource.as_deref(), Some("documents/*"));
+
+        let unmatched = authorize(&policies, "keys/root", &subject, &SystemClock);
+        assert_eq!(unmatched.effect, PolicyEffect::Deny);
+        assert_eq!(unmatched.deciding_policy, None);
+        assert_eq!(unmatched.reason, "no matching allow");
+    }
+
+    #[test]
+    fn test_denial_reason_distinguishes_no_match() {
+        let policies = vec![policy(PolicyEffect::Allow, "documents/*", "role", "reader")];
+        let subject = claims(&[("role", "guest")]);
+
+        assert_eq!(
+            decide_claims_access(&policies, "documents/report", &subject, &SystemClock),
+            EvaluationDecision::denied("no matching allow")
+        );
+    }
+}
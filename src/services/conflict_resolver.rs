@@ -1,9 +1,12 @@
 //! Policy conflict resolution service
 
-use crate::aggregate::{ConflictResolution, Policy};
+use crate::aggregate::{ConflictResolution, Policy, PolicyExemption};
+use crate::clock::{system_clock, Clock};
 use crate::entities::{PolicyConflict, ConflictType, PolicyRule};
 use crate::value_objects::*;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -17,27 +20,91 @@ pub enum ConflictResolutionError {
 
     #[error("Irreconcilable conflict: {0}")]
     IrreconcilableConflict(String),
+
+    #[error("Parent policy not found: {0}")]
+    ParentNotFound(PolicyId),
+
+    #[error("Policy inheritance cycle through {0}")]
+    InheritanceCycle(PolicyId),
 }
 
 /// Service for detecting and resolving policy conflicts
 pub struct PolicyConflictResolver {
     resolution_strategy: ConflictResolution,
+    enforcement_tolerance: u8,
+    /// Rule pairs compared so far, for measuring detection cost
+    rule_comparisons: AtomicUsize,
+    clock: Arc<dyn Clock>,
 }
 
 impl PolicyConflictResolver {
     /// Create a new resolver with a default strategy
     pub fn new(resolution_strategy: ConflictResolution) -> Self {
-        Self { resolution_strategy }
+        Self {
+            resolution_strategy,
+            enforcement_tolerance: 1,
+            rule_comparisons: AtomicUsize::new(0),
+            clock: system_clock(),
+        }
+    }
+
+    /// Timestamp detected conflicts and check exemption validity with `clock`
+    /// instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Set how many enforcement levels overlapping policies may differ by
+    /// before it is reported as a conflict (default: 1)
+    pub fn with_enforcement_tolerance(mut self, tolerance: u8) -> Self {
+        self.enforcement_tolerance = tolerance;
+        self
     }
 
     /// Detect conflicts between policies
+    ///
+    /// Rules can only conflict when they share a field, so rules are first
+    /// indexed by the fields they read and only rules in the same bucket are
+    /// compared, rather than every rule of every overlapping policy pair.
     pub fn detect_conflicts(&self, policies: &[Policy]) -> Vec<PolicyConflict> {
+        // field -> (policy index, rule index)
+        let mut index: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+        for (p, policy) in policies.iter().enumerate() {
+            for (r, rule) in policy.rules.iter().enumerate() {
+                for field in extract_fields(&rule.expression) {
+                    index.entry(field).or_default().push((p, r));
+                }
+            }
+        }
+
+        // (policy1, policy2) -> rule pairs sharing a field, in rule order
+        let mut candidates: HashMap<(usize, usize), BTreeSet<(usize, usize)>> = HashMap::new();
+        for bucket in index.values() {
+            for (a, &(p1, r1)) in bucket.iter().enumerate() {
+                for &(p2, r2) in &bucket[a + 1..] {
+                    let (pair, rules) = if p1 < p2 {
+                        ((p1, p2), (r1, r2))
+                    } else if p2 < p1 {
+                        ((p2, p1), (r2, r1))
+                    } else {
+                        continue;
+                    };
+                    candidates.entry(pair).or_default().insert(rules);
+                }
+            }
+        }
+
         let mut conflicts = Vec::new();
+        let no_candidates = BTreeSet::new();
 
         // Check each pair of policies
         for i in 0..policies.len() {
             for j in i + 1..policies.len() {
-                if let Some(conflict) = self.check_policy_pair(&policies[i], &policies[j]) {
+                let rule_pairs = candidates.get(&(i, j)).unwrap_or(&no_candidates);
+                if let Some(conflict) =
+                    self.check_policy_pair(&policies[i], &policies[j], rule_pairs)
+                {
                     conflicts.push(conflict);
                 }
             }
@@ -46,37 +113,135 @@ impl PolicyConflictResolver {
         conflicts
     }
 
-    /// Check for conflicts between two policies
-    fn check_policy_pair(&self, policy1: &Policy, policy2: &Policy) -> Option<PolicyConflict> {
+    /// Detect conflicts between policies, ignoring those made moot by exemptions
+    ///
+    /// A conflict is suppressed when at least one of the involved policies has a
+    /// currently valid exemption whose scope covers that policy's target.
+    pub fn detect_conflicts_with_exemptions(
+        &self,
+        policies: &[Policy],
+        exemptions: &[PolicyExemption],
+    ) -> Vec<PolicyConflict> {
+        let is_exempt = |policy: &Policy| {
+            exemptions.iter().any(|exemption| {
+                exemption.policy_id == policy.id
+                    && exemption.is_valid(self.clock.as_ref())
+                    && exemption.scope.covers(&policy.target)
+            })
+        };
+
+        self.detect_conflicts(policies)
+            .into_iter()
+            .filter(|conflict| {
+                !conflict.policy_ids.iter().any(|id| {
+                    policies.iter().any(|p| p.id == *id && is_exempt(p))
+                })
+            })
+            .collect()
+    }
+
+    /// Detect numeric constraints that cannot all hold at once
+    ///
+    /// Unlike `detect_conflicts`, which only compares policies pairwise, this
+    /// collects every ordering/equality constraint on a field across all given
+    /// policies and checks whether the combined set is satisfiable. Callers should
+    /// pass policies that apply to the same scope.
+    pub fn detect_impossible_combinations(&self, policies: &[Policy]) -> Vec<PolicyConflict> {
+        let mut constraints: BTreeMap<String, Vec<(usize, &RuleExpression)>> = BTreeMap::new();
+        for (index, policy) in policies.iter().enumerate() {
+            for rule in &policy.rules {
+                collect_constraints(index, &rule.expression, &mut constraints);
+            }
+        }
+
+        let mut conflicts = Vec::new();
+        for (field, field_constraints) in constraints {
+            let mut involved: Vec<usize> = field_constraints.iter().map(|(i, _)| *i).collect();
+            involved.dedup();
+            if involved.len() < 2 {
+                continue;
+            }
+
+            let mut bounds = FieldBounds::default();
+            for (_, expr) in &field_constraints {
+                bounds.add(expr);
+            }
+
+            if !bounds.is_satisfiable() {
+                conflicts.push(PolicyConflict {
+                    id: Uuid::now_v7(),
+                    policy_ids: involved.iter().map(|i| policies[*i].id).collect(),
+                    conflict_type: ConflictType::Impossible,
+                    description: format!(
+                        "Combined constraints on field '{}' from policies {} cannot be satisfied",
+                        field,
+                        involved
+                            .iter()
+                            .map(|i| format!("'{}'", policies[*i].name))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    detected_at: self.clock.now(),
+                    resolution: Some(self.resolution_strategy),
+                });
+            }
+        }
+
+        conflicts
+    }
+
+    /// Check for conflicts between two policies, comparing only the given rule pairs
+    fn check_policy_pair(
+        &self,
+        policy1: &Policy,
+        policy2: &Policy,
+        rule_pairs: &BTreeSet<(usize, usize)>,
+    ) -> Option<PolicyConflict> {
         // Check if targets overlap
         if !self.targets_overlap(&policy1.target, &policy2.target) {
             return None;
         }
 
         // Check for rule conflicts
-        for rule1 in &policy1.rules {
-            for rule2 in &policy2.rules {
-                if let Some(conflict_type) = self.check_rule_conflict(rule1, rule2) {
-                    return Some(PolicyConflict {
-                        id: Uuid::now_v7(),
-                        policy_ids: vec![policy1.id, policy2.id],
-                        conflict_type,
-                        description: format!(
-                            "Conflict between rule '{}' in policy '{}' and rule '{}' in policy '{}'",
-                            rule1.name, policy1.name, rule2.name, policy2.name
-                        ),
-                        detected_at: chrono::Utc::now(),
-                        resolution: Some(self.resolution_strategy),
-                    });
-                }
+        for &(r1, r2) in rule_pairs {
+            let (rule1, rule2) = (&policy1.rules[r1], &policy2.rules[r2]);
+            if let Some(conflict_type) = self.check_rule_conflict(rule1, rule2) {
+                return Some(PolicyConflict {
+                    id: Uuid::now_v7(),
+                    policy_ids: vec![policy1.id, policy2.id],
+                    conflict_type,
+                    description: format!(
+                        "Conflict between rule '{}' in policy '{}' and rule '{}' in policy '{}'",
+                        rule1.name, policy1.name, rule2.name, policy2.name
+                    ),
+                    detected_at: self.clock.now(),
+                    resolution: Some(self.resolution_strategy),
+                });
             }
         }
 
+        // Check for incompatible enforcement levels
+        let level1 = policy1.enforcement_level as u8;
+        let level2 = policy2.enforcement_level as u8;
+        if level1.abs_diff(level2) > self.enforcement_tolerance {
+            return Some(PolicyConflict {
+                id: Uuid::now_v7(),
+                policy_ids: vec![policy1.id, policy2.id],
+                conflict_type: ConflictType::EnforcementMismatch,
+                description: format!(
+                    "Policy '{}' is enforced at {:?} but overlapping policy '{}' is enforced at {:?}",
+                    policy1.name, policy1.enforcement_level, policy2.name, policy2.enforcement_level
+                ),
+                detected_at: self.clock.now(),
+                resolution: Some(self.resolution_strategy),
+            });
+        }
+
         None
     }
 
     /// Check if two targets overlap
-    fn targets_overlap(&self, target1: &PolicyTarget, target2: &PolicyTarget) -> bool {
+    pub fn targets_overlap(&self, target1: &PolicyTarget, target2: &PolicyTarget) -> bool {
         match (target1, target2) {
             (PolicyTarget::Global, _) | (_, PolicyTarget::Global) => true,
             (PolicyTarget::Organization(id1), PolicyTarget::Organization(id2)) => id1 == id2,
@@ -99,9 +264,11 @@ impl PolicyConflictResolver {
 
     /// Check for conflicts between two rules
     fn check_rule_conflict(&self, rule1: &PolicyRule, rule2: &PolicyRule) -> Option<ConflictType> {
+        self.rule_comparisons.fetch_add(1, Ordering::Relaxed);
+
         // Check if rules operate on the same field
-        let fields1 = self.extract_fields(&rule1.expression);
-        let fields2 = self.extract_fields(&rule2.expression);
+        let fields1 = extract_fields(&rule1.expression);
+        let fields2 = extract_fields(&rule2.expression);
 
         let common_fields: HashSet<_> = fields1.intersection(&fields2).collect();
 
@@ -109,64 +276,36 @@ impl PolicyConflictResolver {
             return None;
         }
 
+        // Compare in negation normal form so nested `Not`s reach the flat
+        // checks below, pairing up the top-level conjuncts of each rule
+        let nnf1 = rule1.expression.clone().to_nnf();
+        let nnf2 = rule2.expression.clone().to_nnf();
+        let conjuncts1 = conjuncts(&nnf1);
+        let conjuncts2 = conjuncts(&nnf2);
+        let any_pair = |check: &dyn Fn(&RuleExpression, &RuleExpression) -> bool| {
+            conjuncts1
+                .iter()
+                .any(|c1| conjuncts2.iter().any(|c2| check(c1, c2)))
+        };
+
         // Check for contradictions
-        if self.are_contradictory(&rule1.expression, &rule2.expression) {
+        if any_pair(&|e1, e2| self.are_contradictory(e1, e2)) {
             return Some(ConflictType::Contradiction);
         }
 
         // Check for overlaps with different requirements
-        if self.are_overlapping(&rule1.expression, &rule2.expression) {
+        if any_pair(&|e1, e2| self.are_overlapping(e1, e2)) {
             return Some(ConflictType::Overlap);
         }
 
         // Check for impossible conditions
-        if self.create_impossible_condition(&rule1.expression, &rule2.expression) {
+        if any_pair(&|e1, e2| self.create_impossible_condition(e1, e2)) {
             return Some(ConflictType::Impossible);
         }
 
         None
     }
 
-    /// Extract field names from a rule expression
-    fn extract_fields(&self, expr: &RuleExpression) -> HashSet<String> {
-        let mut fields = HashSet::new();
-
-        match expr {
-            RuleExpression::Equal { field, .. } |
-            RuleExpression::NotEqual { field, .. } |
-            RuleExpression::GreaterThan { field, .. } |
-            RuleExpression::GreaterThanOrEqual { field, .. } |
-            RuleExpression::LessThan { field, .. } |
-            RuleExpression::LessThanOrEqual { field, .. } |
-            RuleExpression::In { field, .. } |
-            RuleExpression::NotIn { field, .. } |
-            RuleExpression::Contains { field, .. } |
-            RuleExpression::Matches { field, .. } |
-            RuleExpression::StartsWith { field, .. } |
-            RuleExpression::EndsWith { field, .. } |
-            RuleExpression::Exists { field } |
-            RuleExpression::NotExists { field } => {
-                fields.insert(field.clone());
-            }
-            RuleExpression::And(exprs) | RuleExpression::Or(exprs) => {
-                for expr in exprs {
-                    fields.extend(self.extract_fields(expr));
-                }
-            }
-            RuleExpression::Not(expr) => {
-                fields.extend(self.extract_fields(expr));
-            }
-            RuleExpression::Custom { args, .. } => {
-                // Extract field names from args
-                for key in args.keys() {
-                    fields.insert(key.clone());
-                }
-            }
-        }
-
-        fields
-    }
-
     /// Check if two expressions are contradictory
     fn are_contradictory(&self, expr1: &RuleExpression, expr2: &RuleExpression) -> bool {
         match (expr1, expr2) {
@@ -274,6 +413,52 @@ impl PolicyConflictResolver {
         }
     }
 
+    /// Drop policies that lose a conflict under the resolution strategy
+    ///
+    /// Only rule contradictions drop a policy; policies that merely overlap or
+    /// differ in enforcement level are both kept. Each contradicting pair
+    /// keeps one policy: the earlier (`FirstWins`) or
+    /// later (`LastWins`) one in the given order, or the one with the higher
+    /// (`MostRestrictive`) or lower (`LeastRestrictive`) enforcement level,
+    /// falling back to the earlier one on a tie. `FailOnConflict` fails if
+    /// any conflict exists. The surviving policies keep their order.
+    pub fn select_policies(&self, policies: &[Policy]) -> Result<Vec<Policy>, ConflictResolutionError> {
+        let conflicts = self.detect_conflicts(policies);
+        if matches!(self.resolution_strategy, ConflictResolution::FailOnConflict) && !conflicts.is_empty() {
+            return Err(ConflictResolutionError::IrreconcilableConflict(
+                format!("Found {} unresolvable conflicts", conflicts.len())
+            ));
+        }
+
+        let position = |id: &PolicyId| policies.iter().position(|p| p.id == *id);
+        let mut superseded = HashSet::new();
+        for conflict in conflicts.iter().filter(|c| c.conflict_type.is_rule_contradiction()) {
+            let [first, second] = conflict.policy_ids[..] else {
+                continue;
+            };
+            let (Some(i), Some(j)) = (position(&first), position(&second)) else {
+                continue;
+            };
+            let (earlier, later) = if i < j { (i, j) } else { (j, i) };
+            let (level_earlier, level_later) =
+                (policies[earlier].enforcement_level, policies[later].enforcement_level);
+
+            let loser = match self.resolution_strategy {
+                ConflictResolution::LastWins => earlier,
+                ConflictResolution::MostRestrictive if level_later > level_earlier => earlier,
+                ConflictResolution::LeastRestrictive if level_later < level_earlier => earlier,
+                _ => later,
+            };
+            superseded.insert(policies[loser].id);
+        }
+
+        Ok(policies
+            .iter()
+            .filter(|p| !superseded.contains(&p.id))
+            .cloned()
+            .collect())
+    }
+
     /// Apply most restrictive resolution
     fn apply_most_restrictive(&self, policies: Vec<Policy>) -> Vec<Policy> {
         // Sort policies by enforcement level (most restrictive first)
@@ -311,43 +496,766 @@ impl PolicyConflictResolver {
 
         // Deduplicate and resolve conflicts in rules
         for rule in all_rules {
-            let conflicts_with_existing = merged.rules.iter().any(|existing|
-                self.check_rule_conflict(existing, &rule).is_some()
+            // Structurally identical rules are kept only once
+            if merged.rules.iter().any(|existing| self.same_rule(existing, &rule)) {
+                continue;
+            }
+
+            // Rules differing only in severity are treated like conflicting rules
+            let conflicting = merged.rules.iter().position(|existing|
+                (existing.name == rule.name && existing.expression == rule.expression)
+                    || self.check_rule_conflict(existing, &rule).is_some()
             );
 
-            if !conflicts_with_existing {
+            let Some(index) = conflicting else {
                 merged.rules.push(rule);
-            } else {
-                // Apply resolution strategy
-                match self.resolution_strategy {
-                    ConflictResolution::MostRestrictive => {
-                        // Keep rule with higher severity
-                        if rule.severity > merged.rules[0].severity {
-                            merged.rules.insert(0, rule);
-                        }
-                    }
-                    ConflictResolution::LeastRestrictive => {
-                        // Keep rule with lower severity
-                        if rule.severity < merged.rules[0].severity {
-                            merged.rules.insert(0, rule);
-                        }
-                    }
-                    ConflictResolution::LastWins => {
-                        // Replace with new rule
-                        merged.rules.push(rule);
-                    }
-                    ConflictResolution::FirstWins => {
-                        // Keep existing rules
+                continue;
+            };
+
+            // Apply resolution strategy against the rule that actually conflicts
+            match self.resolution_strategy {
+                ConflictResolution::MostRestrictive => {
+                    // Keep rule with higher severity
+                    if rule.severity > merged.rules[index].severity {
+                        merged.rules[index] = rule;
                     }
-                    ConflictResolution::FailOnConflict => {
-                        return Err(ConflictResolutionError::IrreconcilableConflict(
-                            "Cannot merge policies with conflicts".to_string()
-                        ));
+                }
+                ConflictResolution::LeastRestrictive => {
+                    // Keep rule with lower severity
+                    if rule.severity < merged.rules[index].severity {
+                        merged.rules[index] = rule;
                     }
                 }
+                ConflictResolution::LastWins => {
+                    // Replace with new rule
+                    merged.rules[index] = rule;
+                }
+                ConflictResolution::FirstWins => {
+                    // Keep existing rules
+                }
+                ConflictResolution::FailOnConflict => {
+                    return Err(ConflictResolutionError::IrreconcilableConflict(
+                        "Cannot merge policies with conflicts".to_string()
+                    ));
+                }
             }
         }
 
         Ok(merged)
     }
-}
\ No newline at end of file
+
+    /// Resolve conflicts between attributed rules with the given strategy
+    ///
+    /// Rules are considered in order. Duplicates are kept once, and a rule
+    /// that conflicts with an earlier one either replaces it or is dropped,
+    /// following the same strategy rules as `merge_policies`. With
+    /// `FailOnConflict` conflicting rules are all kept so they can be seen.
+    pub fn resolve_rules(
+        &self,
+        rules: Vec<(PolicyId, PolicyRule)>,
+        strategy: ConflictResolution,
+    ) -> Vec<(PolicyId, PolicyRule)> {
+        let mut resolved: Vec<(PolicyId, PolicyRule)> = Vec::with_capacity(rules.len());
+
+        for (policy_id, rule) in rules {
+            if resolved.iter().any(|(_, existing)| self.same_rule(existing, &rule)) {
+                continue;
+            }
+
+            let conflicting = resolved.iter().position(|(_, existing)|
+                (existing.name == rule.name && existing.expression == rule.expression)
+                    || self.check_rule_conflict(existing, &rule).is_some()
+            );
+
+            let Some(index) = conflicting else {
+                resolved.push((policy_id, rule));
+                continue;
+            };
+
+            let replace = match strategy {
+                ConflictResolution::MostRestrictive => rule.severity > resolved[index].1.severity,
+                ConflictResolution::LeastRestrictive => rule.severity < resolved[index].1.severity,
+                ConflictResolution::LastWins => true,
+                ConflictResolution::FirstWins => false,
+                ConflictResolution::FailOnConflict => {
+                    resolved.push((policy_id, rule));
+                    continue;
+                }
+            };
+            if replace {
+                resolved[index] = (policy_id, rule);
+            }
+        }
+
+        resolved
+    }
+
+    /// Check if two rules are structurally identical (ignoring their ids)
+    fn same_rule(&self, rule1: &PolicyRule, rule2: &PolicyRule) -> bool {
+        rule1.name == rule2.name
+            && rule1.expression == rule2.expression
+            && rule1.severity == rule2.severity
+    }
+}
+
+/// Resolve the effective policy that applies to a target
+///
+/// Policies whose targets overlap the requested target (composite targets are
+/// matched recursively) are merged into a single policy using the given strategy.
+pub fn resolve_effective_policy(
+    policies: &[Policy],
+    target: &PolicyTarget,
+    strategy: ConflictResolution,
+) -> Result<Policy, ConflictResolutionError> {
+    let resolver = PolicyConflictResolver::new(strategy);
+
+    let applicable: Vec<Policy> = policies
+        .iter()
+        .filter(|policy| resolver.targets_overlap(&policy.target, target))
+        .cloned()
+        .collect();
+
+    if applicable.is_empty() {
+        return Err(ConflictResolutionError::NoPolicies);
+    }
+
+    // Order so the merge base reflects the strategy's enforcement preference
+    let ordered = match strategy {
+        ConflictResolution::MostRestrictive => resolver.apply_most_restrictive(applicable),
+        ConflictResolution::LeastRestrictive => resolver.apply_least_restrictive(applicable),
+        _ => applicable,
+    };
+
+    let mut effective = resolver.merge_policies(ordered)?;
+    effective.target = target.clone();
+    Ok(effective)
+}
+
+/// Resolve a policy together with the rules it inherits from its parent chain
+///
+/// Parents are looked up through `resolver` by `parent_policy_id`. Rules are
+/// merged from the root ancestor down, and a rule in a descendant replaces an
+/// inherited rule with the same id or name, or one it conflicts with. All other
+/// fields come from `policy` itself.
+pub fn resolve_with_inheritance(
+    policy: &Policy,
+    resolver: &dyn Fn(PolicyId) -> Option<Policy>,
+) -> Result<Policy, ConflictResolutionError> {
+    let mut chain = vec![policy.clone()];
+    let mut seen = HashSet::from([policy.id]);
+
+    while let Some(parent_id) = chain.last().and_then(|p| p.parent_policy_id) {
+        if !seen.insert(parent_id) {
+            return Err(ConflictResolutionError::InheritanceCycle(parent_id));
+        }
+        let parent = resolver(parent_id).ok_or(ConflictResolutionError::ParentNotFound(parent_id))?;
+        chain.push(parent);
+    }
+
+    let conflicts = PolicyConflictResolver::new(ConflictResolution::LastWins);
+    let mut rules: Vec<PolicyRule> = Vec::new();
+    for ancestor in chain.iter().rev() {
+        for rule in &ancestor.rules {
+            rules.retain(|inherited| {
+                inherited.id != rule.id
+                    && inherited.name != rule.name
+                    && conflicts.check_rule_conflict(inherited, rule).is_none()
+            });
+            rules.push(rule.clone());
+        }
+    }
+
+    let mut effective = policy.clone();
+    effective.rules = rules;
+    Ok(effective)
+}
+
+/// Drop policies masked by a contradicting higher-priority override
+///
+/// `priorities` maps a policy to its `(priority, override_lower)` pair;
+/// unlisted policies have priority 0 and override nothing. A policy is
+/// dropped when its rules contradict those of a kept policy of strictly higher priority
+/// that has `override_lower` set, so a masked policy masks nothing itself.
+/// The remaining policies keep their input order.
+pub fn resolve_by_priority(
+    policies: &[Policy],
+    priorities: &HashMap<PolicyId, (u32, bool)>,
+) -> Vec<Policy> {
+    let priority_of = |policy: &Policy| priorities.get(&policy.id).copied().unwrap_or((0, false));
+
+    let resolver = PolicyConflictResolver::new(ConflictResolution::LastWins);
+    let conflicting: HashSet<(PolicyId, PolicyId)> = resolver
+        .detect_conflicts(policies)
+        .iter()
+        .filter(|conflict| conflict.conflict_type.is_rule_contradiction())
+        .filter_map(|conflict| match conflict.policy_ids[..] {
+            [a, b] => Some([(a, b), (b, a)]),
+            _ => None,
+        })
+        .flatten()
+        .collect();
+
+    let mut by_priority: Vec<&Policy> = policies.iter().collect();
+    by_priority.sort_by_key(|policy| std::cmp::Reverse(priority_of(policy).0));
+
+    let mut masked = HashSet::new();
+    for (i, policy) in by_priority.iter().enumerate() {
+        let (priority, override_lower) = priority_of(policy);
+        if !override_lower || masked.contains(&policy.id) {
+            continue;
+        }
+        for lower in &by_priority[i + 1..] {
+            if priority_of(lower).0 < priority && conflicting.contains(&(policy.id, lower.id)) {
+                masked.insert(lower.id);
+            }
+        }
+    }
+
+    policies
+        .iter()
+        .filter(|policy| !masked.contains(&policy.id))
+        .cloned()
+        .collect()
+}
+
+/// Extract field names from a rule expression
+pub(crate) fn extract_fields(expr: &RuleExpression) -> HashSet<String> {
+    let mut fields = HashSet::new();
+
+    match expr {
+        RuleExpression::Equal { field, .. } |
+        RuleExpression::NotEqual { field, .. } |
+        RuleExpression::GreaterThan { field, .. } |
+        RuleExpression::GreaterThanOrEqual { field, .. } |
+        RuleExpression::LessThan { field, .. } |
+        RuleExpression::LessThanOrEqual { field, .. } |
+        RuleExpression::In { field, .. } |
+        RuleExpression::NotIn { field, .. } |
+        RuleExpression::Contains { field, .. } |
+        RuleExpression::Matches { field, .. } |
+        RuleExpression::MatchesRegex { field, .. } |
+        RuleExpression::StartsWith { field, .. } |
+        RuleExpression::EndsWith { field, .. } |
+        RuleExpression::Exists { field } |
+        RuleExpression::NotExists { field } |
+        RuleExpression::IpInNetwork { field, .. } |
+        RuleExpression::WithinGeoRegion { field, .. } => {
+            fields.insert(field.clone());
+        }
+        RuleExpression::And(exprs) | RuleExpression::Or(exprs) => {
+            for expr in exprs {
+                fields.extend(extract_fields(expr));
+            }
+        }
+        RuleExpression::Not(expr) => {
+            fields.extend(extract_fields(expr));
+        }
+        RuleExpression::WithinTimeConstraint { .. } => {
+            // Evaluated against the context timestamp, not a field
+        }
+        RuleExpression::Compute { left, right, .. } => {
+            for operand in [left, right] {
+                if let Operand::Field(field) = operand {
+                    fields.insert(field.clone());
+                }
+            }
+        }
+        RuleExpression::Custom { args, .. } => {
+            // Extract field names from args
+            for key in args.keys() {
+                fields.insert(key.clone());
+            }
+        }
+    }
+
+    fields
+}
+
+/// Expressions that must all hold for `expr` to hold (flattening nested `And`)
+fn conjuncts(expr: &RuleExpression) -> Vec<&RuleExpression> {
+    match expr {
+        RuleExpression::And(exprs) => exprs.iter().flat_map(conjuncts).collect(),
+        other => vec![other],
+    }
+}
+
+/// Collect numeric constraints that must all hold (top level and inside `And`)
+fn collect_constraints<'a>(
+    index: usize,
+    expr: &'a RuleExpression,
+    constraints: &mut BTreeMap<String, Vec<(usize, &'a RuleExpression)>>,
+) {
+    match expr {
+        RuleExpression::Equal { field, value } |
+        RuleExpression::NotEqual { field, value } |
+        RuleExpression::GreaterThan { field, value } |
+        RuleExpression::GreaterThanOrEqual { field, value } |
+        RuleExpression::LessThan { field, value } |
+        RuleExpression::LessThanOrEqual { field, value } if numeric_value(value).is_some() => {
+            constraints.entry(field.clone()).or_default().push((index, expr));
+        }
+        RuleExpression::And(exprs) => {
+            for expr in exprs {
+                collect_constraints(index, expr, constraints);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Whether the numeric constraints an expression imposes can all hold
+///
+/// Only constraints that must all hold (top level and inside `And`) are
+/// considered, so `false` means the expression can never be true.
+pub(crate) fn is_satisfiable(expr: &RuleExpression) -> bool {
+    let mut constraints = BTreeMap::new();
+    collect_constraints(0, expr, &mut constraints);
+
+    constraints.values().all(|field_constraints| {
+        let mut bounds = FieldBounds::default();
+        for (_, expr) in field_constraints {
+            bounds.add(expr);
+        }
+        bounds.is_satisfiable()
+    })
+}
+
+/// Accumulated numeric bounds for a single field
+#[derive(Default)]
+struct FieldBounds {
+    /// Lower bound and whether it is inclusive
+    lower: Option<(f64, bool)>,
+    /// Upper bound and whether it is inclusive
+    upper: Option<(f64, bool)>,
+    equals: Vec<f64>,
+    not_equals: Vec<f64>,
+}
+
+impl FieldBounds {
+    fn add(&mut self, expr: &RuleExpression) {
+        match expr {
+            RuleExpression::Equal { value, .. } => self.equals.extend(numeric_value(value)),
+            RuleExpression::NotEqual { value, .. } => self.not_equals.extend(numeric_value(value)),
+            RuleExpression::GreaterThan { value, .. } => self.raise_lower(numeric_value(value), false),
+            RuleExpression::GreaterThanOrEqual { value, .. } => self.raise_lower(numeric_value(value), true),
+            RuleExpression::LessThan { value, .. } => self.lower_upper(numeric_value(value), false),
+            RuleExpression::LessThanOrEqual { value, .. } => self.lower_upper(numeric_value(value), true),
+            _ => {}
+        }
+    }
+
+    fn raise_lower(&mut self, value: Option<f64>, inclusive: bool) {
+        let Some(value) = value else { return };
+        self.lower = match self.lower {
+            Some((current, current_inclusive))
+                if current > value || (current == value && !current_inclusive) =>
+            {
+                Some((current, current_inclusive))
+            }
+            _ => Some((value, inclusive)),
+        };
+    }
+
+    fn lower_upper(&mut self, value: Option<f64>, inclusive: bool) {
+        let Some(value) = value else { return };
+        self.upper = match self.upper {
+            Some((current, current_inclusive))
+                if current < value || (current == value && !current_inclusive) =>
+            {
+                Some((current, current_inclusive))
+            }
+            _ => Some((value, inclusive)),
+        };
+    }
+
+    fn within(&self, value: f64) -> bool {
+        let above_lower = match self.lower {
+            Some((lower, true)) => value >= lower,
+            Some((lower, false)) => value > lower,
+            None => true,
+        };
+        let below_upper = match self.upper {
+            Some((upper, true)) => value <= upper,
+            Some((upper, false)) => value < upper,
+            None => true,
+        };
+        above_lower && below_upper && !self.not_equals.contains(&value)
+    }
+
+    fn is_satisfiable(&self) -> bool {
+        if let Some(&first) = self.equals.first() {
+            return self.equals.iter().all(|v| *v == first) && self.within(first);
+        }
+
+        match (self.lower, self.upper) {
+            (Some((lower, lower_inclusive)), Some((upper, upper_inclusive))) => {
+                lower < upper
+                    || (lower == upper
+                        && lower_inclusive
+                        && upper_inclusive
+                        && !self.not_equals.contains(&lower))
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Numeric view of a value for constraint analysis
+fn numeric_value(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(i) => Some(*i as f64),
+        Value::Float(f) => Some(*f),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::{ExemptionScope, ExemptionStatus};
+
+    fn policy(name: &str, target: PolicyTarget, level: EnforcementLevel) -> Policy {
+        let mut policy = Policy::new(name, "Test policy");
+        policy.target = target;
+        policy.enforcement_level = level;
+        policy
+    }
+
+    fn rule(name: &str, field: &str, value: &str) -> PolicyRule {
+        PolicyRule::new(
+            name,
+            "Test rule",
+            RuleExpression::Equal {
+                field: field.to_string(),
+                value: Value::String(value.to_string()),
+            },
+            Severity::High,
+        )
+    }
+
+    fn contradicting_policies() -> (Policy, Policy) {
+        let mut eu = policy("EU", PolicyTarget::Global, EnforcementLevel::Hard);
+        eu.rules.push(rule("EU region", "region", "eu"));
+        let mut us = policy("US", PolicyTarget::Global, EnforcementLevel::Hard);
+        us.rules.push(rule("US region", "region", "us"));
+        (eu, us)
+    }
+
+    #[test]
+    fn test_field_index_skips_unrelated_rule_pairs() {
+        let resolver = PolicyConflictResolver::new(ConflictResolution::MostRestrictive);
+        let mut policies: Vec<Policy> = (0..50)
+            .map(|i| {
+                let mut p = policy(&format!("P{}", i), PolicyTarget::Global, EnforcementLevel::Hard);
+                for r in 0..4 {
+                    let field = format!("field_{}_{}", i, r);
+                    p.rules.push(rule(&format!("R{}", r), &field, "x"));
+                }
+                p
+            })
+            .collect();
+        let (eu, us) = contradicting_policies();
+        policies.push(eu);
+        policies.push(us);
+
+        let conflicts = resolver.detect_conflicts(&policies);
+        // Only the two region rules share a field
+        assert_eq!(resolver.rule_comparisons.load(Ordering::Relaxed), 1);
+
+        // Exhaustive all-pairs comparison, as done before indexing
+        let mut expected = Vec::new();
+        for i in 0..policies.len() {
+            for j in i + 1..policies.len() {
+                let found = policies[i].rules.iter().find_map(|r1| {
+                    policies[j].rules.iter().find_map(|r2| resolver.check_rule_conflict(r1, r2))
+                });
+                if let Some(conflict_type) = found {
+                    expected.push((vec![policies[i].id, policies[j].id], conflict_type));
+                }
+            }
+        }
+        let actual: Vec<_> = conflicts
+            .iter()
+            .map(|c| (c.policy_ids.clone(), c.conflict_type.clone()))
+            .collect();
+        assert_eq!(actual, expected);
+        assert_eq!(actual.len(), 1);
+        assert!(resolver.rule_comparisons.load(Ordering::Relaxed) > 1000);
+    }
+
+    #[test]
+    fn test_child_policy_overrides_and_inherits_parent_rules() {
+        let mut base = policy("Base", PolicyTarget::Global, EnforcementLevel::Hard);
+        base.rules.push(rule("Region", "region", "eu"));
+        base.rules.push(rule("Tier", "tier", "gold"));
+
+        let mut derived = policy("Derived", PolicyTarget::Global, EnforcementLevel::Hard);
+        derived.parent_policy_id = Some(base.id);
+        derived.rules.push(rule("Region", "region", "us"));
+
+        let lookup = |id: PolicyId| (id == base.id).then(|| base.clone());
+        let effective = resolve_with_inheritance(&derived, &lookup).unwrap();
+
+        assert_eq!(effective.id, derived.id);
+        assert_eq!(effective.rules.len(), 2);
+        let region = effective.rules.iter().find(|r| r.name == "Region").unwrap();
+        assert_eq!(region.id, derived.rules[0].id);
+        assert!(effective.rules.iter().any(|r| r.id == base.rules[1].id));
+
+        // A parent pointing back at its child is a cycle
+        base.parent_policy_id = Some(derived.id);
+        let lookup = |id: PolicyId| (id == base.id).then(|| base.clone());
+        assert!(matches!(
+            resolve_with_inheritance(&derived, &lookup),
+            Err(ConflictResolutionError::InheritanceCycle(id)) if id == derived.id
+        ));
+    }
+
+    #[test]
+    fn test_global_exemption_suppresses_contradiction() {
+        let resolver = PolicyConflictResolver::new(ConflictResolution::MostRestrictive);
+        let (eu, us) = contradicting_policies();
+        let policies = vec![eu, us.clone()];
+
+        assert_eq!(resolver.detect_conflicts_with_exemptions(&policies, &[]).len(), 1);
+
+        let exemption = PolicyExemption::new(
+            us.id,
+            "Migration",
+            "Temporary dual-region deployment",
+            "admin",
+            chrono::Utc::now() + chrono::Duration::days(7),
+        );
+        assert!(resolver.detect_conflicts_with_exemptions(&policies, &[exemption]).is_empty());
+        assert_eq!(resolver.detect_conflicts(&policies).len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_or_narrow_exemption_keeps_conflict() {
+        let resolver = PolicyConflictResolver::new(ConflictResolution::MostRestrictive);
+        let (eu, us) = contradicting_policies();
+        let policies = vec![eu, us.clone()];
+
+        let mut revoked = PolicyExemption::new(
+            us.id,
+            "Migration",
+            "Temporary dual-region deployment",
+            "admin",
+            chrono::Utc::now() + chrono::Duration::days(7),
+        );
+        revoked.revoke("admin", "Migration finished", chrono::Utc::now());
+
+        let mut narrow = revoked.clone();
+        narrow.status = ExemptionStatus::Active;
+        narrow.scope = ExemptionScope::Resource("Secret".to_string());
+
+        let conflicts = resolver.detect_conflicts_with_exemptions(&policies, &[revoked, narrow]);
+        assert_eq!(conflicts.len(), 1);
+    }
+
+    fn numeric_policy(name: &str, expression: RuleExpression) -> Policy {
+        let mut policy = policy(name, PolicyTarget::Global, EnforcementLevel::Hard);
+        policy.rules.push(PolicyRule::new(name, "Test rule", expression, Severity::Medium));
+        policy
+    }
+
+    fn x(value: i64) -> (String, Value) {
+        ("x".to_string(), Value::Integer(value))
+    }
+
+    #[test]
+    fn test_satisfiable_triple_has_no_impossible_combination() {
+        let resolver = PolicyConflictResolver::new(ConflictResolution::MostRestrictive);
+        let (field, value) = x(10);
+        let a = numeric_policy("A", RuleExpression::GreaterThan { field, value });
+        let (field, value) = x(20);
+        let b = numeric_policy("B", RuleExpression::LessThan { field, value });
+        let (field, value) = x(15);
+        let c = numeric_policy("C", RuleExpression::Equal { field, value });
+
+        assert!(resolver.detect_impossible_combinations(&[a, b, c]).is_empty());
+    }
+
+    #[test]
+    fn test_unsatisfiable_triple_reports_all_policies() {
+        let resolver = PolicyConflictResolver::new(ConflictResolution::MostRestrictive);
+        let (field, value) = x(10);
+        let a = numeric_policy("A", RuleExpression::GreaterThan { field, value });
+        let (field, value) = x(20);
+        let b = numeric_policy("B", RuleExpression::LessThan { field, value });
+        let (field, value) = x(5);
+        let c = numeric_policy("C", RuleExpression::Equal { field, value });
+        let ids = vec![a.id, b.id, c.id];
+
+        // No single pair is flagged by pairwise detection
+        let policies = vec![a, b, c];
+        assert!(resolver.detect_conflicts(&policies).is_empty());
+
+        let conflicts = resolver.detect_impossible_combinations(&policies);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].conflict_type, ConflictType::Impossible);
+        assert_eq!(conflicts[0].policy_ids, ids);
+    }
+
+    #[test]
+    fn test_merge_policy_with_itself_keeps_rules_once() {
+        let resolver = PolicyConflictResolver::new(ConflictResolution::MostRestrictive);
+        let (eu, _) = contradicting_policies();
+        let mut copy = eu.clone();
+        copy.rules[0].id = Uuid::now_v7();
+
+        let merged = resolver.merge_policies(vec![eu, copy]).unwrap();
+        assert_eq!(merged.rules.len(), 1);
+    }
+
+    #[test]
+    fn test_merge_keeps_severity_selected_by_strategy() {
+        let mut first = policy("First", PolicyTarget::Global, EnforcementLevel::Hard);
+        first.rules.push(rule("Audit", "audit", "on"));
+        first.rules.push(PolicyRule::min_key_size(2048));
+        let mut second = policy("Second", PolicyTarget::Global, EnforcementLevel::Hard);
+        let mut lenient = PolicyRule::min_key_size(2048);
+        lenient.severity = Severity::Low;
+        second.rules.push(lenient);
+
+        let most = PolicyConflictResolver::new(ConflictResolution::MostRestrictive)
+            .merge_policies(vec![first.clone(), second.clone()])
+            .unwrap();
+        assert_eq!(most.rules.len(), 2);
+        assert_eq!(most.rules[0].name, "Audit");
+        assert_eq!(most.rules[1].severity, Severity::Critical);
+
+        let least = PolicyConflictResolver::new(ConflictResolution::LeastRestrictive)
+            .merge_policies(vec![first, second])
+            .unwrap();
+        assert_eq!(least.rules.len(), 2);
+        assert_eq!(least.rules[0].severity, Severity::High);
+        assert_eq!(least.rules[1].severity, Severity::Low);
+    }
+
+    #[test]
+    fn test_resolve_effective_policy_for_composite_target() {
+        let admin = PolicyTarget::Role("admin".to_string());
+        let secrets = PolicyTarget::Resource(ResourceType::Secret);
+
+        let mut admin_policy = policy("Admins", admin.clone(), EnforcementLevel::Soft);
+        let mut eu = rule("EU region", "region", "eu");
+        eu.severity = Severity::Medium;
+        admin_policy.rules.push(eu);
+
+        let mut secret_policy = policy("Secrets", secrets.clone(), EnforcementLevel::Hard);
+        secret_policy.rules.push(rule("US region", "region", "us"));
+        secret_policy.rules.push(PolicyRule::min_key_size(4096));
+
+        let mut user_policy = policy("Users", PolicyTarget::Role("user".to_string()), EnforcementLevel::Critical);
+        user_policy.rules.push(rule("Audit", "audit", "on"));
+
+        let target = PolicyTarget::Composite(vec![admin, secrets]);
+        let effective = resolve_effective_policy(
+            &[admin_policy, secret_policy, user_policy],
+            &target,
+            ConflictResolution::MostRestrictive,
+        )
+        .unwrap();
+
+        assert_eq!(effective.target, target);
+        assert_eq!(effective.enforcement_level, EnforcementLevel::Hard);
+        assert_eq!(effective.rules.len(), 2);
+        assert!(effective.rules.iter().any(|r| r.name == "US region"));
+        assert!(effective.rules.iter().all(|r| r.name != "EU region" && r.name != "Audit"));
+    }
+
+    #[test]
+    fn test_resolve_effective_policy_without_matches() {
+        let admins = policy("Admins", PolicyTarget::Role("admin".to_string()), EnforcementLevel::Hard);
+        let result = resolve_effective_policy(
+            &[admins],
+            &PolicyTarget::Role("user".to_string()),
+            ConflictResolution::FirstWins,
+        );
+        assert!(matches!(result, Err(ConflictResolutionError::NoPolicies)));
+    }
+
+    #[test]
+    fn test_enforcement_mismatch_on_overlapping_targets() {
+        let clock = crate::clock::MockClock::new("2025-06-01T09:00:00Z".parse().unwrap());
+        let resolver = PolicyConflictResolver::new(ConflictResolution::MostRestrictive)
+            .with_clock(Arc::new(clock.clone()));
+        let advisory = policy("Advisory", PolicyTarget::Role("admin".to_string()), EnforcementLevel::Advisory);
+        let hard = policy("Hard", PolicyTarget::Role("admin".to_string()), EnforcementLevel::Hard);
+
+        let conflicts = resolver.detect_conflicts(&[advisory, hard]);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].conflict_type, ConflictType::EnforcementMismatch);
+        assert!(conflicts[0].description.contains("Advisory"));
+        assert!(conflicts[0].description.contains("Hard"));
+        assert_eq!(conflicts[0].detected_at, clock.now());
+    }
+
+    #[test]
+    fn test_enforcement_mismatch_respects_tolerance_and_targets() {
+        let resolver = PolicyConflictResolver::new(ConflictResolution::MostRestrictive);
+        let soft = policy("Soft", PolicyTarget::Global, EnforcementLevel::Soft);
+        let hard = policy("Hard", PolicyTarget::Global, EnforcementLevel::Hard);
+        assert!(resolver.detect_conflicts(&[soft.clone(), hard.clone()]).is_empty());
+
+        let strict = PolicyConflictResolver::new(ConflictResolution::MostRestrictive)
+            .with_enforcement_tolerance(0);
+        assert_eq!(strict.detect_conflicts(&[soft, hard]).len(), 1);
+
+        let admins = policy("Admins", PolicyTarget::Role("admin".to_string()), EnforcementLevel::Advisory);
+        let users = policy("Users", PolicyTarget::Role("user".to_string()), EnforcementLevel::Critical);
+        assert!(resolver.detect_conflicts(&[admins, users]).is_empty());
+    }
+
+    #[test]
+    fn test_priority_override_masks_conflicting_lower_policy() {
+        let (eu, us) = contradicting_policies();
+        // Enforced far more leniently than the override, but about other fields
+        let mut unrelated = policy("Keys", PolicyTarget::Global, EnforcementLevel::Advisory);
+        unrelated.rules.push(PolicyRule::min_key_size(2048));
+        let policies = vec![us.clone(), eu.clone(), unrelated.clone()];
+
+        let priorities = HashMap::from([
+            (eu.id, (10, true)),
+            (us.id, (1, false)),
+            (unrelated.id, (1, false)),
+        ]);
+        let resolved = resolve_by_priority(&policies, &priorities);
+        let ids: Vec<_> = resolved.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![eu.id, unrelated.id]);
+
+        // Without override_lower, higher priority alone masks nothing
+        let priorities = HashMap::from([(eu.id, (10, false))]);
+        assert_eq!(resolve_by_priority(&policies, &priorities).len(), 3);
+    }
+
+    #[test]
+    fn test_contradiction_under_nested_not_is_detected() {
+        let resolver = PolicyConflictResolver::new(ConflictResolution::MostRestrictive);
+        let mut eu = policy("EU", PolicyTarget::Global, EnforcementLevel::Hard);
+        eu.rules.push(rule("EU region", "region", "eu"));
+
+        // Not(Or(region == "eu", tier == "free")) requires region != "eu"
+        let mut not_eu = policy("Not EU", PolicyTarget::Global, EnforcementLevel::Hard);
+        not_eu.rules.push(PolicyRule::new(
+            "Outside EU",
+            "Test rule",
+            RuleExpression::Not(Box::new(RuleExpression::Or(vec![
+                RuleExpression::Equal {
+                    field: "region".to_string(),
+                    value: Value::String("eu".to_string()),
+                },
+                RuleExpression::Equal {
+                    field: "tier".to_string(),
+                    value: Value::String("free".to_string()),
+                },
+            ]))),
+            Severity::High,
+        ));
+
+        let conflicts = resolver.detect_conflicts(&[eu, not_eu]);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].conflict_type, ConflictType::Contradiction);
+    }
+}
@@ -0,0 +1,185 @@
+//! Coverage analysis - which of a resource's fields are governed by policy
+
+use super::conflict_resolver::extract_fields;
+use crate::aggregate::Policy;
+use crate::value_objects::{PolicyId, RuleExpression};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use uuid::Uuid;
+
+/// A rule that references fields the resource does not provide
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MissingFieldReference {
+    pub policy_id: PolicyId,
+    pub rule_id: Uuid,
+    pub rule_name: String,
+    /// Referenced fields absent from the available set, sorted
+    pub missing_fields: Vec<String>,
+}
+
+/// How well a set of policies covers the fields a resource provides
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoverageReport {
+    /// Available fields referenced by at least one rule, sorted
+    pub covered_fields: Vec<String>,
+    /// Available fields no rule references, sorted
+    pub uncovered_fields: Vec<String>,
+    /// Rules that will fail with `MissingContextField` against this resource
+    pub rules_referencing_missing_fields: Vec<MissingFieldReference>,
+}
+
+/// Compare the fields referenced by `policies` against `available_fields`
+///
+/// Uncovered fields are governance gaps; rules referencing missing fields
+/// are dead rules for this resource.
+pub fn analyze_coverage(policies: &[Policy], available_fields: &HashSet<String>) -> CoverageReport {
+    let mut referenced = HashSet::new();
+    let mut rules_referencing_missing_fields = Vec::new();
+
+    for policy in policies {
+        for rule in &policy.rules {
+            let fields = extract_fields(&rule.expression);
+            let missing: BTreeSet<_> = required_fields(&rule.expression)
+                .difference(available_fields)
+                .cloned()
+                .collect();
+            if !missing.is_empty() {
+                rules_referencing_missing_fields.push(MissingFieldReference {
+                    policy_id: policy.id,
+                    rule_id: rule.id,
+                    rule_name: rule.name.clone(),
+                    missing_fields: missing.into_iter().collect(),
+                });
+            }
+            referenced.extend(fields);
+        }
+    }
+
+    let (covered, uncovered): (BTreeSet<_>, BTreeSet<_>) = available_fields
+        .iter()
+        .cloned()
+        .partition(|field| referenced.contains(field));
+
+    CoverageReport {
+        covered_fields: covered.into_iter().collect(),
+        uncovered_fields: uncovered.into_iter().collect(),
+        rules_referencing_missing_fields,
+    }
+}
+
+/// Fields whose absence makes `expr` fail with `MissingContextField`
+///
+/// Existence checks are decided by absence rather than failing on it, and
+/// custom predicate args are arguments, not context fields.
+fn required_fields(expr: &RuleExpression) -> HashSet<String> {
+    match expr {
+        RuleExpression::Exists { .. }
+        | RuleExpression::NotExists { .. }
+        | RuleExpression::Custom { .. } => HashSet::new(),
+        RuleExpression::And(exprs) | RuleExpression::Or(exprs) => {
+            exprs.iter().flat_map(required_fields).collect()
+        }
+        RuleExpression::Not(expr) => required_fields(expr),
+        leaf => extract_fields(leaf),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::PolicyRule;
+    use crate::value_objects::*;
+
+    fn fields(names: &[&str]) -> HashSet<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_partial_coverage() {
+        let mut policy = Policy::new("Crypto", "Key requirements");
+        policy.rules = vec![
+            PolicyRule::min_key_size(2048),
+            PolicyRule::new(
+                "Owner",
+                "Key must have an owner",
+                RuleExpression::Exists {
+                    field: "owner".to_string(),
+                },
+                Severity::Low,
+            ),
+        ];
+
+        let report = analyze_coverage(
+            &[policy],
+            &fields(&["key_size", "owner", "algorithm", "region"]),
+        );
+
+        assert_eq!(report.covered_fields, vec!["key_size", "owner"]);
+        assert_eq!(report.uncovered_fields, vec!["algorithm", "region"]);
+        assert!(report.rules_referencing_missing_fields.is_empty());
+    }
+
+    #[test]
+    fn test_rule_referencing_missing_field() {
+        let mut policy = Policy::new("Access", "Access requirements");
+        let dead = PolicyRule::new(
+            "MFA",
+            "MFA must be enabled",
+            RuleExpression::And(vec![
+                RuleExpression::Equal {
+                    field: "mfa_enabled".to_string(),
+                    value: Value::Bool(true),
+                },
+                RuleExpression::Exists {
+                    field: "user".to_string(),
+                },
+            ]),
+            Severity::High,
+        );
+        policy.rules = vec![dead.clone()];
+
+        let report = analyze_coverage(std::slice::from_ref(&policy), &fields(&["user"]));
+
+        assert_eq!(report.covered_fields, vec!["user"]);
+        assert!(report.uncovered_fields.is_empty());
+        assert_eq!(
+            report.rules_referencing_missing_fields,
+            vec![MissingFieldReference {
+                policy_id: policy.id,
+                rule_id: dead.id,
+                rule_name: "MFA".to_string(),
+                missing_fields: vec!["mfa_enabled".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_existence_checks_and_custom_args_are_not_missing_fields() {
+        let mut policy = Policy::new("Access", "Access requirements");
+        policy.rules = vec![
+            PolicyRule::new(
+                "No legacy token",
+                "Legacy tokens must not be present",
+                RuleExpression::NotExists {
+                    field: "legacy_token".to_string(),
+                },
+                Severity::Medium,
+            ),
+            PolicyRule::new(
+                "Approved vendor",
+                "Vendor must be approved",
+                RuleExpression::Custom {
+                    predicate: "approved_vendor".to_string(),
+                    args: [("list".to_string(), Value::String("tier-1".to_string()))]
+                        .into_iter()
+                        .collect(),
+                },
+                Severity::Low,
+            ),
+        ];
+
+        let report = analyze_coverage(&[policy], &fields(&["user"]));
+
+        assert!(report.rules_referencing_missing_fields.is_empty());
+    }
+}
@@ -0,0 +1,94 @@
+//! Exemption expiry sweeping service
+
+use crate::aggregate::{ExemptionStatus, PolicyExemption};
+use crate::events::PolicyExemptionExpired;
+use crate::sagas::create_root_command;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Expires exemptions whose validity window has passed
+///
+/// `PolicyExemption::is_valid_at` already stops a lapsed exemption from
+/// applying, but its status stays `Active` until a `PolicyExemptionExpired`
+/// event is recorded. Running the sweeper periodically records those events
+/// so aggregates, projections and audits reflect the expiry.
+#[derive(Debug, Clone, Default)]
+pub struct ExemptionSweeper;
+
+impl ExemptionSweeper {
+    /// Create a sweeper
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Expiry events for the active exemptions whose `valid_until` is before `now`
+    pub fn sweep(
+        &self,
+        exemptions: &[PolicyExemption],
+        now: DateTime<Utc>,
+    ) -> Vec<PolicyExemptionExpired> {
+        exemptions
+            .iter()
+            .filter(|exemption| {
+                exemption.status == ExemptionStatus::Active && exemption.valid_until < now
+            })
+            .map(|exemption| PolicyExemptionExpired {
+                event_id: Uuid::now_v7(),
+                identity: create_root_command(),
+                exemption_id: exemption.id,
+                policy_id: exemption.policy_id,
+                expired_at: now,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::PolicyEvent;
+    use crate::value_objects::PolicyId;
+    use chrono::Duration;
+
+    fn exemption_until(valid_until: DateTime<Utc>) -> PolicyExemption {
+        PolicyExemption::new(
+            PolicyId::new(),
+            "Migration",
+            "Legacy keys",
+            "ciso",
+            valid_until,
+        )
+    }
+
+    #[test]
+    fn test_sweep_expires_only_lapsed_active_exemptions() {
+        let now = Utc::now();
+        let active = exemption_until(now + Duration::days(30));
+        let boundary = exemption_until(now);
+        let just_expired = exemption_until(now - Duration::seconds(1));
+        let mut revoked = exemption_until(now - Duration::days(3));
+        revoked.revoke("ciso", "No longer needed", now);
+        let mut already_expired = exemption_until(now - Duration::days(3));
+        already_expired.status = ExemptionStatus::Expired;
+
+        let exemptions = vec![
+            active,
+            boundary,
+            just_expired.clone(),
+            revoked,
+            already_expired,
+        ];
+        let events = ExemptionSweeper::new().sweep(&exemptions, now);
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].exemption_id, just_expired.id);
+        assert_eq!(events[0].policy_id, just_expired.policy_id);
+        assert_eq!(events[0].expired_at, now);
+
+        let swept = just_expired
+            .apply_event_pure(&PolicyEvent::PolicyExemptionExpired(events[0].clone()))
+            .unwrap();
+        assert_eq!(swept.status, ExemptionStatus::Expired);
+        assert!(ExemptionSweeper::new().sweep(&[swept], now).is_empty());
+    }
+}
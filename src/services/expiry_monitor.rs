@@ -0,0 +1,107 @@
+//! Policy expiry monitoring service
+
+use crate::aggregate::Policy;
+use crate::events::{PolicyArchived, PolicyEvent, PolicyExpiringSoon};
+use crate::sagas::create_root_command;
+use crate::value_objects::*;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+/// Who lapsed policies are archived by
+const EXPIRY_ARCHIVER: &str = "system:expiry-monitor";
+
+/// Check policies for upcoming and past expiry
+///
+/// Active and suspended policies expiring within `threshold_days` produce a
+/// `PolicyExpiringSoon` notification. Policies whose expiry date has passed
+/// are no longer effective and produce `PolicyArchived`. Archived policies
+/// and policies without an expiry date are ignored.
+pub fn check_expiring_policies(
+    policies: &[Policy],
+    now: DateTime<Utc>,
+    threshold_days: i64,
+) -> Vec<PolicyEvent> {
+    let threshold = now + Duration::days(threshold_days);
+
+    policies
+        .iter()
+        .filter(|policy| {
+            matches!(policy.status, PolicyStatus::Active | PolicyStatus::Suspended)
+        })
+        .filter_map(|policy| {
+            let expires_at = policy.expiry_date?;
+
+            if expires_at <= now {
+                Some(PolicyEvent::PolicyArchived(PolicyArchived {
+                    event_id: Uuid::now_v7(),
+                    identity: create_root_command(),
+                    policy_id: policy.id,
+                    archived_by: EXPIRY_ARCHIVER.to_string(),
+                    archived_at: now,
+                    retention_period_days: None,
+                }))
+            } else if expires_at <= threshold {
+                Some(PolicyEvent::PolicyExpiringSoon(PolicyExpiringSoon {
+                    event_id: Uuid::now_v7(),
+                    identity: create_root_command(),
+                    policy_id: policy.id,
+                    expires_at,
+                    days_remaining: (expires_at - now).num_days(),
+                    detected_at: now,
+                }))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy_expiring_in(now: DateTime<Utc>, days: Option<i64>) -> Policy {
+        let mut policy = Policy::new("Certificates", "Certificate requirements");
+        policy.status = PolicyStatus::Active;
+        policy.expiry_date = days.map(|days| now + Duration::days(days));
+        policy
+    }
+
+    #[test]
+    fn test_notifications_depend_on_distance_from_expiry() {
+        let now = Utc::now();
+        let soon = policy_expiring_in(now, Some(5));
+        let later = policy_expiring_in(now, Some(90));
+        let lapsed = policy_expiring_in(now, Some(-1));
+        let open_ended = policy_expiring_in(now, None);
+        let mut archived = policy_expiring_in(now, Some(-30));
+        archived.status = PolicyStatus::Archived;
+
+        let policies = vec![soon.clone(), later, lapsed.clone(), open_ended, archived];
+        let events = check_expiring_policies(&policies, now, 30);
+
+        assert_eq!(events.len(), 2);
+        match &events[0] {
+            PolicyEvent::PolicyExpiringSoon(e) => {
+                assert_eq!(e.policy_id, soon.id);
+                assert_eq!(e.days_remaining, 5);
+                assert_eq!(Some(e.expires_at), soon.expiry_date);
+            }
+            other => panic!("expected expiry warning, got {:?}", other),
+        }
+        match &events[1] {
+            PolicyEvent::PolicyArchived(e) => {
+                assert_eq!(e.policy_id, lapsed.id);
+                assert_eq!(e.archived_by, EXPIRY_ARCHIVER);
+            }
+            other => panic!("expected archival, got {:?}", other),
+        }
+
+        // A wider threshold also warns about the later policy
+        let events = check_expiring_policies(&[policy_expiring_in(now, Some(90))], now, 120);
+        assert!(matches!(
+            &events[..],
+            [PolicyEvent::PolicyExpiringSoon(e)] if e.days_remaining == 90
+        ));
+    }
+}
@@ -0,0 +1,293 @@
+//! Policy linter - catches authoring mistakes before activation
+
+use super::conflict_resolver::{extract_fields, is_satisfiable};
+use crate::aggregate::Policy;
+use crate::entities::PolicyRule;
+use crate::value_objects::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use uuid::Uuid;
+
+/// How serious a lint finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum LintLevel {
+    /// Probably unintended, but the policy still works
+    Warning,
+    /// The policy cannot behave as written
+    Error,
+}
+
+/// A problem found in a policy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LintFinding {
+    pub level: LintLevel,
+    pub message: String,
+    /// The rule the finding is about, if it is about a single rule
+    pub rule_id: Option<Uuid>,
+}
+
+impl fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self.level {
+            LintLevel::Warning => "warning",
+            LintLevel::Error => "error",
+        };
+        match self.rule_id {
+            Some(rule_id) => write!(f, "{} (rule {}): {}", level, rule_id, self.message),
+            None => write!(f, "{}: {}", level, self.message),
+        }
+    }
+}
+
+/// Checks policies for common authoring mistakes
+///
+/// Flags rules that can never be satisfied, rules that reference no
+/// fields, policies without rules, high-severity rules on advisory policies,
+/// and duplicate rules.
+#[derive(Debug, Clone, Default)]
+pub struct PolicyLinter;
+
+impl PolicyLinter {
+    /// Create a linter
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Lint a policy, returning findings in rule order
+    pub fn lint(&self, policy: &Policy) -> Vec<LintFinding> {
+        let mut findings = Vec::new();
+
+        if policy.rules.is_empty() {
+            findings.push(LintFinding {
+                level: LintLevel::Warning,
+                message: "Policy has no rules and will always be compliant".to_string(),
+                rule_id: None,
+            });
+        }
+
+        let mut seen_ids = HashSet::new();
+        for (index, rule) in policy.rules.iter().enumerate() {
+            self.lint_rule(policy, rule, &mut findings);
+
+            if !seen_ids.insert(rule.id) {
+                findings.push(finding(
+                    LintLevel::Error,
+                    rule,
+                    format!("Rule id {} is used more than once", rule.id),
+                ));
+            } else if let Some(earlier) = policy.rules[..index]
+                .iter()
+                .find(|other| other.expression == rule.expression)
+            {
+                findings.push(finding(
+                    LintLevel::Warning,
+                    rule,
+                    format!(
+                        "Rule '{}' duplicates the expression of rule '{}'",
+                        rule.name, earlier.name
+                    ),
+                ));
+            }
+        }
+
+        findings
+    }
+
+    fn lint_rule(&self, policy: &Policy, rule: &PolicyRule, findings: &mut Vec<LintFinding>) {
+        if let Some(path) = empty_connective(&rule.expression) {
+            findings.push(finding(
+                LintLevel::Error,
+                rule,
+                format!("Rule '{}' has an empty {}", rule.name, path),
+            ));
+        }
+
+        if !is_satisfiable(&rule.expression) {
+            findings.push(finding(
+                LintLevel::Error,
+                rule,
+                format!("Rule '{}' can never be satisfied", rule.name),
+            ));
+        }
+
+        if extract_fields(&rule.expression).is_empty() && !uses_time(&rule.expression) {
+            findings.push(finding(
+                LintLevel::Warning,
+                rule,
+                format!("Rule '{}' references no context fields", rule.name),
+            ));
+        }
+
+        if policy.enforcement_level == EnforcementLevel::Advisory && rule.severity >= Severity::High
+        {
+            findings.push(finding(
+                LintLevel::Warning,
+                rule,
+                format!(
+                    "Rule '{}' has {:?} severity but the policy is only advisory",
+                    rule.name, rule.severity
+                ),
+            ));
+        }
+    }
+}
+
+fn finding(level: LintLevel, rule: &PolicyRule, message: String) -> LintFinding {
+    LintFinding {
+        level,
+        message,
+        rule_id: Some(rule.id),
+    }
+}
+
+/// Name of the first `And`/`Or` without operands, if any
+fn empty_connective(expr: &RuleExpression) -> Option<&'static str> {
+    match expr {
+        RuleExpression::And(exprs) if exprs.is_empty() => Some("And"),
+        RuleExpression::Or(exprs) if exprs.is_empty() => Some("Or"),
+        RuleExpression::And(exprs) | RuleExpression::Or(exprs) => {
+            exprs.iter().find_map(empty_connective)
+        }
+        RuleExpression::Not(inner) => empty_connective(inner),
+        _ => None,
+    }
+}
+
+/// Whether the expression depends on the evaluation time
+fn uses_time(expr: &RuleExpression) -> bool {
+    match expr {
+        RuleExpression::WithinTimeConstraint { .. } => true,
+        RuleExpression::And(exprs) | RuleExpression::Or(exprs) => exprs.iter().any(uses_time),
+        RuleExpression::Not(inner) => uses_time(inner),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(name: &str, expression: RuleExpression, severity: Severity) -> PolicyRule {
+        PolicyRule::new(name, name, expression, severity)
+    }
+
+    fn policy_with(level: EnforcementLevel, rules: Vec<PolicyRule>) -> Policy {
+        let mut policy = Policy::new("Linted", "Policy under lint");
+        policy.enforcement_level = level;
+        policy.rules = rules;
+        policy
+    }
+
+    fn messages(findings: &[LintFinding]) -> Vec<&str> {
+        findings.iter().map(|f| f.message.as_str()).collect()
+    }
+
+    #[test]
+    fn test_clean_policy_has_no_findings() {
+        let policy = policy_with(EnforcementLevel::Hard, vec![PolicyRule::min_key_size(2048)]);
+        assert!(PolicyLinter::new().lint(&policy).is_empty());
+    }
+
+    #[test]
+    fn test_unsatisfiable_rule_is_an_error() {
+        let impossible = rule(
+            "Impossible",
+            RuleExpression::And(vec![
+                RuleExpression::GreaterThan {
+                    field: "x".to_string(),
+                    value: Value::Integer(10),
+                },
+                RuleExpression::LessThan {
+                    field: "x".to_string(),
+                    value: Value::Integer(5),
+                },
+            ]),
+            Severity::Medium,
+        );
+        let findings = PolicyLinter::new().lint(&policy_with(
+            EnforcementLevel::Hard,
+            vec![impossible.clone()],
+        ));
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].level, LintLevel::Error);
+        assert_eq!(findings[0].rule_id, Some(impossible.id));
+        assert!(findings[0].message.contains("never be satisfied"));
+    }
+
+    #[test]
+    fn test_empty_policy_and_fieldless_rules_are_warnings() {
+        let findings = PolicyLinter::new().lint(&policy_with(EnforcementLevel::Hard, Vec::new()));
+        assert_eq!(
+            messages(&findings),
+            vec!["Policy has no rules and will always be compliant"]
+        );
+
+        let fieldless = rule(
+            "Always",
+            RuleExpression::Custom {
+                predicate: "always".to_string(),
+                args: Default::default(),
+            },
+            Severity::Low,
+        );
+        let empty_or = rule("Nothing", RuleExpression::Or(Vec::new()), Severity::Low);
+        let findings = PolicyLinter::new().lint(&policy_with(
+            EnforcementLevel::Hard,
+            vec![fieldless, empty_or],
+        ));
+
+        assert!(findings.iter().any(|f| f.level == LintLevel::Warning
+            && f.message == "Rule 'Always' references no context fields"));
+        assert!(findings
+            .iter()
+            .any(|f| f.level == LintLevel::Error && f.message == "Rule 'Nothing' has an empty Or"));
+    }
+
+    #[test]
+    fn test_critical_rule_on_advisory_policy_is_flagged() {
+        let critical = rule(
+            "MFA",
+            RuleExpression::Exists {
+                field: "mfa".to_string(),
+            },
+            Severity::Critical,
+        );
+        let findings =
+            PolicyLinter::new().lint(&policy_with(EnforcementLevel::Advisory, vec![critical]));
+        assert_eq!(
+            messages(&findings),
+            vec!["Rule 'MFA' has Critical severity but the policy is only advisory"]
+        );
+
+        let findings = PolicyLinter::new().lint(&policy_with(
+            EnforcementLevel::Advisory,
+            vec![rule(
+                "Note",
+                RuleExpression::Exists {
+                    field: "note".to_string(),
+                },
+                Severity::Low,
+            )],
+        ));
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_rules_are_flagged() {
+        let original = PolicyRule::min_key_size(2048);
+        let mut copy = PolicyRule::min_key_size(2048);
+        copy.name = "Copy".to_string();
+        let findings = PolicyLinter::new().lint(&policy_with(
+            EnforcementLevel::Hard,
+            vec![original.clone(), copy.clone(), original.clone()],
+        ));
+
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].level, LintLevel::Warning);
+        assert_eq!(findings[0].rule_id, Some(copy.id));
+        assert_eq!(findings[1].level, LintLevel::Error);
+        assert_eq!(findings[1].rule_id, Some(original.id));
+    }
+}
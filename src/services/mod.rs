@@ -3,7 +3,32 @@
 mod policy_evaluator;
 mod conflict_resolver;
 mod template_engine;
+mod claims_evaluator;
+mod simulator;
+mod expiry_monitor;
+mod exemption_sweeper;
+mod caching_evaluator;
+mod reporting;
+mod linter;
+mod coverage;
+mod selection;
 
-pub use policy_evaluator::{PolicyEvaluator, EvaluationError};
-pub use conflict_resolver::{PolicyConflictResolver, ConflictResolutionError};
-pub use template_engine::{PolicyTemplateEngine, TemplateError};
\ No newline at end of file
+pub use policy_evaluator::{PolicyEvaluator, EvaluationError, CustomPredicate};
+pub use conflict_resolver::{
+    PolicyConflictResolver, ConflictResolutionError, resolve_by_priority, resolve_effective_policy,
+    resolve_with_inheritance,
+};
+pub use template_engine::{PolicyTemplateEngine, TemplateError};
+pub use claims_evaluator::{authorize, evaluate_claims_policies, decide_claims_access, AuthorizationDecision};
+pub use simulator::{PolicySimulator, SimulationReport};
+pub use expiry_monitor::check_expiring_policies;
+pub use exemption_sweeper::ExemptionSweeper;
+pub use caching_evaluator::CachingPolicyEvaluator;
+pub use coverage::{analyze_coverage, CoverageReport, MissingFieldReference};
+pub use linter::{LintFinding, LintLevel, PolicyLinter};
+pub use selection::select_applicable;
+pub(crate) use conflict_resolver::is_satisfiable;
+pub use reporting::{
+    compliance_report_by_framework, generate_compliance_report, group_violations, ComplianceReport,
+    ExemptionUsage, FrameworkStatus, GroupedViolation, ReportPeriod, RuleViolationCount,
+};
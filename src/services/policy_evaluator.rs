@@ -1,9 +1,18 @@
 //! Policy evaluation service
 
 use crate::aggregate::{Policy, PolicyExemption};
-use crate::entities::{PolicyEvaluation, PolicyRule, RuleResult};
+use crate::clock::{system_clock, Clock};
+use crate::entities::{
+    EvaluationTrace, ExpressionTrace, PolicyEvaluation, PolicyRule, RuleResult, RuleTrace,
+    SetEvaluation,
+};
+use crate::events::{PolicyEvaluated, PolicyEvent, PolicyExemptionExhausted, PolicyExemptionUsed};
 use crate::value_objects::*;
-use std::collections::HashMap;
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -19,11 +28,37 @@ pub enum EvaluationError {
 
     #[error("Rule evaluation failed: {0}")]
     RuleEvaluationFailed(String),
+
+    #[error("{delegate} has no delegation from {delegator} covering policy {policy_id}")]
+    DelegationNotGranted {
+        delegate: String,
+        delegator: String,
+        policy_id: PolicyId,
+    },
+
+    #[error("Conflict resolution failed: {0}")]
+    ConflictResolution(#[from] crate::services::ConflictResolutionError),
 }
 
+/// Predicate backing `RuleExpression::Custom`, called with the rule's args
+pub type CustomPredicate =
+    Arc<dyn Fn(&HashMap<String, Value>, &EvaluationContext) -> bool + Send + Sync>;
+
 /// Service for evaluating policies against contexts
 pub struct PolicyEvaluator {
     exemptions: HashMap<PolicyId, Vec<PolicyExemption>>,
+    /// Uses of usage-limited exemptions, seeded from their recorded count
+    exemption_uses: Mutex<HashMap<ExemptionId, u32>>,
+    /// Usage and exhaustion events not yet taken by the caller
+    exemption_events: Mutex<Vec<PolicyEvent>>,
+    predicates: HashMap<String, CustomPredicate>,
+    /// Compiled `MatchesRegex` patterns, keyed by pattern
+    regexes: Mutex<HashMap<String, regex::Regex>>,
+    time_budget: Option<Duration>,
+    parallel: bool,
+    clock: Arc<dyn Clock>,
+    /// Exemptions this evaluator has added to the active exemptions gauge
+    counted_exemptions: Mutex<HashSet<ExemptionId>>,
 }
 
 impl PolicyEvaluator {
@@ -31,19 +66,132 @@ impl PolicyEvaluator {
     pub fn new() -> Self {
         Self {
             exemptions: HashMap::new(),
+            exemption_uses: Mutex::new(HashMap::new()),
+            exemption_events: Mutex::new(Vec::new()),
+            predicates: HashMap::new(),
+            regexes: Mutex::new(HashMap::new()),
+            time_budget: None,
+            parallel: false,
+            clock: system_clock(),
+            counted_exemptions: Mutex::new(HashSet::new()),
         }
     }
 
+    /// Read the time from `clock` where no evaluation context supplies it
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Evaluate a policy's rules in parallel
+    ///
+    /// Rules are independent, so results are identical to sequential
+    /// evaluation and keep the policy's rule order. Worth enabling for
+    /// policies with many CPU-heavy rules; off by default.
+    pub fn with_parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Abort evaluation of a policy once its rules have taken longer than `budget`
+    ///
+    /// The budget is checked after each rule, so a single slow rule runs to
+    /// completion before the evaluation is abandoned.
+    pub fn with_time_budget(mut self, budget: Duration) -> Self {
+        self.time_budget = Some(budget);
+        self
+    }
+
+    /// Register the predicate evaluated for `RuleExpression::Custom { predicate: name, .. }`
+    pub fn register_predicate(
+        &mut self,
+        name: impl Into<String>,
+        predicate: impl Fn(&HashMap<String, Value>, &EvaluationContext) -> bool + Send + Sync + 'static,
+    ) {
+        self.predicates.insert(name.into(), Arc::new(predicate));
+    }
+
     /// Register exemptions for consideration during evaluation
+    ///
+    /// Validity windows are checked against each context's timestamp at
+    /// evaluation time, so only revoked or expired exemptions are dropped here.
+    /// Registering an exemption again replaces the earlier copy, so a
+    /// reloaded revoked exemption stops applying.
     pub fn register_exemptions(&mut self, exemptions: Vec<PolicyExemption>) {
         for exemption in exemptions {
-            if exemption.is_valid() {
+            for registered in self.exemptions.values_mut() {
+                registered.retain(|existing| existing.id != exemption.id);
+            }
+            self.exemption_uses.get_mut().unwrap().remove(&exemption.id);
+
+            if exemption.status == crate::aggregate::ExemptionStatus::Active {
+                if exemption.max_uses.is_some() {
+                    self.exemption_uses
+                        .get_mut()
+                        .unwrap()
+                        .insert(exemption.id, exemption.uses_count);
+                }
                 self.exemptions
                     .entry(exemption.policy_id)
                     .or_insert_with(Vec::new)
                     .push(exemption);
             }
         }
+
+        self.recount_active_exemptions();
+    }
+
+    /// Bring this evaluator's share of the active exemptions gauge up to date
+    ///
+    /// Counts the registered exemptions valid at the clock's current time
+    /// that have uses left, so ones that expired, were exhausted or were
+    /// replaced by a revoked copy stop being counted.
+    fn recount_active_exemptions(&self) {
+        let now = self.clock.now();
+        let uses = self.exemption_uses.lock().unwrap();
+        let valid: HashSet<_> = self
+            .exemptions
+            .values()
+            .flatten()
+            .filter(|exemption| exemption.is_valid_at(now))
+            .filter(|exemption| match (exemption.max_uses, uses.get(&exemption.id)) {
+                (Some(max_uses), Some(count)) => *count < max_uses,
+                _ => true,
+            })
+            .map(|exemption| exemption.id)
+            .collect();
+
+        let mut counted = self.counted_exemptions.lock().unwrap();
+        if valid.len() != counted.len() {
+            crate::metrics::adjust_active_exemptions(valid.len() as i64 - counted.len() as i64);
+        }
+        *counted = valid;
+    }
+
+    /// Take the exemption usage events raised since the last call
+    ///
+    /// Each use of a usage-limited exemption raises `PolicyExemptionUsed`,
+    /// and the last allowed use also raises `PolicyExemptionExhausted`.
+    /// Persisting them keeps the exemption aggregate's `uses_count` current,
+    /// so the limit survives restarts. To hold across evaluators running
+    /// concurrently, append them conditionally on the exemption's
+    /// `last_sequence` and evaluate again from a reload when that fails.
+    pub fn take_exemption_events(&self) -> Vec<PolicyEvent> {
+        std::mem::take(&mut *self.exemption_events.lock().unwrap())
+    }
+
+    /// Whether any exemptions are registered for the policy
+    pub(crate) fn has_exemptions(&self, policy_id: PolicyId) -> bool {
+        self.exemptions.get(&policy_id).is_some_and(|exemptions| !exemptions.is_empty())
+    }
+
+    /// Whether the exemption is usage-limited, so results it produced can't be reused
+    pub(crate) fn exemption_is_usage_limited(&self, policy_id: PolicyId, exemption_id: ExemptionId) -> bool {
+        self.exemptions
+            .get(&policy_id)
+            .into_iter()
+            .flatten()
+            .any(|exemption| exemption.id == exemption_id && exemption.max_uses.is_some())
     }
 
     /// Evaluate a policy against a context
@@ -52,51 +200,357 @@ impl PolicyEvaluator {
         policy: &Policy,
         context: &EvaluationContext,
     ) -> Result<PolicyEvaluation, EvaluationError> {
-        // Check if policy is active
-        if !policy.is_effective() {
+        // Check if policy is in force at the time of the context
+        if !policy.is_effective_at(context.timestamp) {
+            crate::metrics::record_evaluation_error();
             return Err(EvaluationError::PolicyNotActive(policy.id));
         }
 
-        let start = std::time::Instant::now();
+        if !self.exemptions.is_empty() {
+            self.recount_active_exemptions();
+        }
+
+        let start = Instant::now();
+        let evaluation = self.evaluate_rules(policy, context);
+        match &evaluation {
+            Ok(evaluation) => crate::metrics::record_evaluation(evaluation, start.elapsed()),
+            Err(_) => crate::metrics::record_evaluation_error(),
+        }
+        evaluation
+    }
+
+    /// Evaluate a policy and record the outcome as a `PolicyEvaluated` event
+    ///
+    /// The event carries a fingerprint of the context rather than the context
+    /// itself, so evaluations can be analysed without retaining request data.
+    pub fn evaluate_and_record(
+        &self,
+        policy: &Policy,
+        context: &EvaluationContext,
+    ) -> Result<(PolicyEvaluation, PolicyEvent), EvaluationError> {
+        let evaluation = self.evaluate(policy, context)?;
+        let event = PolicyEvent::PolicyEvaluated(PolicyEvaluated::from_evaluation(
+            &evaluation,
+            crate::sagas::create_root_command(),
+        ));
+        Ok((evaluation, event))
+    }
+
+    /// Trace how each of a policy's rules evaluates against a context
+    ///
+    /// Every sub-expression is traced with the field values it reads and
+    /// its outcome, including children that evaluation would short-circuit
+    /// past. Exemptions and whether the policy is in force are not
+    /// considered; this shows what the rules themselves decide.
+    pub fn explain(&self, policy: &Policy, context: &EvaluationContext) -> EvaluationTrace {
+        EvaluationTrace {
+            policy_id: policy.id,
+            rules: policy
+                .rules
+                .iter()
+                .map(|rule| RuleTrace {
+                    rule_id: rule.id,
+                    rule_name: rule.name.clone(),
+                    expression: self.trace_expression(&rule.expression, context),
+                })
+                .collect(),
+        }
+    }
+
+    fn trace_expression(&self, expr: &RuleExpression, context: &EvaluationContext) -> ExpressionTrace {
+        let children = match expr {
+            RuleExpression::And(exprs) | RuleExpression::Or(exprs) => exprs
+                .iter()
+                .map(|expr| self.trace_expression(expr, context))
+                .collect(),
+            RuleExpression::Not(expr) => vec![self.trace_expression(expr, context)],
+            _ => Vec::new(),
+        };
+
+        // Compound nodes read nothing themselves; custom predicate args are not fields
+        let fields = match expr {
+            RuleExpression::And(_)
+            | RuleExpression::Or(_)
+            | RuleExpression::Not(_)
+            | RuleExpression::Custom { .. } => Vec::new(),
+            leaf => {
+                let mut fields: Vec<_> = super::conflict_resolver::extract_fields(leaf)
+                    .into_iter()
+                    .collect();
+                fields.sort();
+                fields
+                    .into_iter()
+                    .map(|field| {
+                        let value = context.get_path(&field).cloned();
+                        (field, value)
+                    })
+                    .collect()
+            }
+        };
+
+        // Compound results come from the children's traces, short-circuiting
+        // in the same order as `evaluate_expression`, so each leaf runs once
+        let (result, error) = match expr {
+            RuleExpression::And(_) => combine_traces(&children, false),
+            RuleExpression::Or(_) => combine_traces(&children, true),
+            RuleExpression::Not(_) => match &children[0] {
+                ExpressionTrace { error: Some(error), .. } => (None, Some(error.clone())),
+                child => (child.result.map(|result| !result), None),
+            },
+            leaf => match self.evaluate_expression(leaf, context) {
+                Ok(result) => (Some(result), None),
+                Err(e) => (None, Some(e.to_string())),
+            },
+        };
+
+        ExpressionTrace {
+            operator: operator_name(expr).to_string(),
+            fields,
+            result,
+            error,
+            children,
+        }
+    }
+
+    /// Evaluate a policy for a delegate acting on behalf of a delegator
+    ///
+    /// One of `grants` must delegate from `delegator` to `delegate`, be in
+    /// force at the context timestamp, and have a scope overlapping the
+    /// policy's target. The policy is then evaluated as the delegator.
+    pub fn evaluate_on_behalf_of(
+        &self,
+        policy: &Policy,
+        context: &EvaluationContext,
+        delegate: &str,
+        delegator: &str,
+        grants: &[DelegationGrant],
+    ) -> Result<PolicyEvaluation, EvaluationError> {
+        let resolver = crate::services::PolicyConflictResolver::new(
+            crate::aggregate::ConflictResolution::MostRestrictive,
+        );
+        let granted = grants.iter().any(|grant| {
+            grant.delegator == delegator
+                && grant.delegate == delegate
+                && grant.is_valid_at(context.timestamp)
+                && resolver.targets_overlap(&grant.scope, &policy.target)
+        });
+
+        if !granted {
+            return Err(EvaluationError::DelegationNotGranted {
+                delegate: delegate.to_string(),
+                delegator: delegator.to_string(),
+                policy_id: policy.id,
+            });
+        }
+
+        let mut context = context.clone();
+        context.requester = Some(delegator.to_string());
+        self.evaluate(policy, &context)
+    }
+
+    /// Evaluate exemptions and rules without checking whether the policy is in force
+    ///
+    /// With the `tracing-spans` feature each call runs in a `policy.evaluate`
+    /// span carrying the policy id, the context's `correlation_id`
+    /// environment entry, the result and the duration.
+    pub(crate) fn evaluate_rules(
+        &self,
+        policy: &Policy,
+        context: &EvaluationContext,
+    ) -> Result<PolicyEvaluation, EvaluationError> {
+        #[cfg(feature = "tracing-spans")]
+        {
+            let span = tracing::info_span!(
+                "policy.evaluate",
+                policy_id = %policy.id,
+                policy_name = %policy.name,
+                correlation_id = context.environment.get("correlation_id").map(String::as_str),
+                result = tracing::field::Empty,
+                duration_us = tracing::field::Empty,
+            );
+            let start = Instant::now();
+            let evaluation = span.in_scope(|| self.evaluate_rules_untraced(policy, context));
+            let result = match &evaluation {
+                Ok(evaluation) if evaluation.is_compliant() => "compliant",
+                Ok(_) => "non_compliant",
+                Err(_) => "error",
+            };
+            span.record("result", result);
+            span.record("duration_us", start.elapsed().as_micros() as u64);
+            evaluation
+        }
+        #[cfg(not(feature = "tracing-spans"))]
+        {
+            self.evaluate_rules_untraced(policy, context)
+        }
+    }
+
+    fn evaluate_rules_untraced(
+        &self,
+        policy: &Policy,
+        context: &EvaluationContext,
+    ) -> Result<PolicyEvaluation, EvaluationError> {
+        let start = Instant::now();
         let mut evaluation = PolicyEvaluation::new(policy.id, context.clone());
 
-        // Check for exemptions first
+        // Unlimited exemptions waive the rules outright; usage-limited ones
+        // are only spent on requests the rules would otherwise fail
+        let mut limited_exemptions = Vec::new();
         if let Some(exemptions) = self.exemptions.get(&policy.id) {
             for exemption in exemptions {
-                if self.exemption_applies(exemption, context) {
-                    evaluation.overall_result = ComplianceResult::CompliantWithExemption {
-                        exemption_id: exemption.id,
-                    };
-                    evaluation.execution_time_ms = start.elapsed().as_millis() as u64;
-                    return Ok(evaluation);
+                if !self.exemption_applies(exemption, context) {
+                    continue;
+                }
+                if exemption.max_uses.is_some() {
+                    limited_exemptions.push(exemption);
+                    continue;
                 }
+                evaluation.overall_result = ComplianceResult::CompliantWithExemption {
+                    exemption_id: exemption.id,
+                };
+                evaluation.execution_time_ms = start.elapsed().as_millis() as u64;
+                return Ok(evaluation);
             }
         }
 
-        // Evaluate each rule
-        for rule in &policy.rules {
-            let result = self.evaluate_rule(rule, context)?;
-            evaluation.add_rule_result(result);
+        if self.parallel {
+            // Rule spans on worker threads still belong to the policy span
+            #[cfg(feature = "tracing-spans")]
+            let parent = tracing::Span::current();
+            let results = policy
+                .rules
+                .par_iter()
+                .map(|rule| {
+                    #[cfg(feature = "tracing-spans")]
+                    let _entered = parent.enter();
+                    self.evaluate_rule_timed(rule, context)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            for result in results {
+                evaluation.add_rule_result(result);
+            }
+            self.check_time_budget(start)?;
+        } else {
+            // Evaluate each rule, timing them individually
+            for rule in &policy.rules {
+                let result = self.evaluate_rule_timed(rule, context)?;
+                evaluation.add_rule_result(result);
+                self.check_time_budget(start)?;
+            }
+        }
+
+        if !evaluation.is_compliant() {
+            if let Some(exemption) = limited_exemptions
+                .into_iter()
+                .find(|exemption| self.record_exemption_use(exemption, context))
+            {
+                evaluation.overall_result = ComplianceResult::CompliantWithExemption {
+                    exemption_id: exemption.id,
+                };
+            }
         }
 
         evaluation.execution_time_ms = start.elapsed().as_millis() as u64;
         Ok(evaluation)
     }
 
+    /// Evaluate each policy individually, keeping every result
+    ///
+    /// Unlike `evaluate_set`, nothing is collapsed: each policy gets its own
+    /// `PolicyEvaluation`. Policies that are not effective are recorded as
+    /// skipped (`Ok(None)`), and a policy whose evaluation fails records its
+    /// error; neither stops the rest of the batch.
+    pub fn evaluate_each(
+        &self,
+        policies: &[&Policy],
+        context: &EvaluationContext,
+    ) -> Vec<(PolicyId, Result<Option<PolicyEvaluation>, EvaluationError>)> {
+        policies
+            .iter()
+            .map(|policy| {
+                let outcome = match self.evaluate(policy, context) {
+                    Ok(evaluation) => Ok(Some(evaluation)),
+                    Err(EvaluationError::PolicyNotActive(_)) => Ok(None),
+                    Err(e) => Err(e),
+                };
+                (policy.id, outcome)
+            })
+            .collect()
+    }
+
     /// Evaluate multiple policies as a set
+    ///
+    /// Policies that are not in force at the context timestamp are skipped
+    /// rather than failing the whole set. Use `evaluate_set_detailed` to see
+    /// how many were skipped.
     pub fn evaluate_set(
         &self,
         policies: Vec<&Policy>,
         context: &EvaluationContext,
         composition: crate::aggregate::CompositionRule,
     ) -> Result<ComplianceResult, EvaluationError> {
-        let results: Result<Vec<_>, _> = policies
-            .into_iter()
-            .map(|p| self.evaluate(p, context))
+        self.evaluate_set_detailed(policies, context, composition)
+            .map(|outcome| outcome.result)
+    }
+
+    /// Evaluate multiple policies as a set, reporting evaluated and skipped counts
+    pub fn evaluate_set_detailed(
+        &self,
+        policies: Vec<&Policy>,
+        context: &EvaluationContext,
+        composition: crate::aggregate::CompositionRule,
+    ) -> Result<SetEvaluation, EvaluationError> {
+        let mut evaluations = Vec::with_capacity(policies.len());
+        let mut skipped = 0;
+
+        for policy in policies {
+            if policy.is_effective_at(context.timestamp) {
+                evaluations.push(self.evaluate_rules(policy, context)?);
+            } else {
+                skipped += 1;
+            }
+        }
+
+        let result = self.compose(&evaluations, composition);
+
+        Ok(SetEvaluation {
+            result,
+            evaluated: evaluations.len(),
+            skipped,
+        })
+    }
+
+    /// Evaluate the members of a policy set as the set describes
+    ///
+    /// Policies that are not members of the set are ignored. Conflicts
+    /// between members are resolved with the set's `conflict_resolution`,
+    /// dropping superseded policies, and the rest are evaluated with the
+    /// set's `composition_rule`.
+    pub fn evaluate_policy_set(
+        &self,
+        set: &crate::aggregate::PolicySet,
+        policies: &[&Policy],
+        context: &EvaluationContext,
+    ) -> Result<SetEvaluation, EvaluationError> {
+        let members: Vec<Policy> = set
+            .policies
+            .iter()
+            .filter_map(|id| policies.iter().find(|p| p.id == *id))
+            .map(|p| (*p).clone())
             .collect();
 
-        let evaluations = results?;
+        let resolver = crate::services::PolicyConflictResolver::new(set.conflict_resolution);
+        let selected = resolver.select_policies(&members)?;
 
+        self.evaluate_set_detailed(selected.iter().collect(), context, set.composition_rule)
+    }
+
+    /// Combine individual evaluations according to a composition rule
+    fn compose(
+        &self,
+        evaluations: &[PolicyEvaluation],
+        composition: crate::aggregate::CompositionRule,
+    ) -> ComplianceResult {
         match composition {
             crate::aggregate::CompositionRule::All => {
                 // All must be compliant
@@ -106,21 +560,21 @@ impl PolicyEvaluator {
                     .collect();
 
                 if violations.is_empty() {
-                    Ok(ComplianceResult::Compliant)
+                    ComplianceResult::Compliant
                 } else {
-                    Ok(ComplianceResult::NonCompliant { violations })
+                    ComplianceResult::NonCompliant { violations }
                 }
             }
             crate::aggregate::CompositionRule::Any => {
                 // At least one must be compliant
                 if evaluations.iter().any(|e| e.is_compliant()) {
-                    Ok(ComplianceResult::Compliant)
+                    ComplianceResult::Compliant
                 } else {
                     let violations: Vec<_> = evaluations
                         .iter()
                         .flat_map(|e| e.violations())
                         .collect();
-                    Ok(ComplianceResult::NonCompliant { violations })
+                    ComplianceResult::NonCompliant { violations }
                 }
             }
             crate::aggregate::CompositionRule::Majority => {
@@ -129,13 +583,13 @@ impl PolicyEvaluator {
                 let total = evaluations.len();
 
                 if compliant > total / 2 {
-                    Ok(ComplianceResult::Compliant)
+                    ComplianceResult::Compliant
                 } else {
                     let violations: Vec<_> = evaluations
                         .iter()
                         .flat_map(|e| e.violations())
                         .collect();
-                    Ok(ComplianceResult::NonCompliant { violations })
+                    ComplianceResult::NonCompliant { violations }
                 }
             }
             crate::aggregate::CompositionRule::AtLeast(n) => {
@@ -143,13 +597,13 @@ impl PolicyEvaluator {
                 let compliant = evaluations.iter().filter(|e| e.is_compliant()).count();
 
                 if compliant >= n {
-                    Ok(ComplianceResult::Compliant)
+                    ComplianceResult::Compliant
                 } else {
                     let violations: Vec<_> = evaluations
                         .iter()
                         .flat_map(|e| e.violations())
                         .collect();
-                    Ok(ComplianceResult::NonCompliant { violations })
+                    ComplianceResult::NonCompliant { violations }
                 }
             }
         }
@@ -157,8 +611,8 @@ impl PolicyEvaluator {
 
     /// Check if an exemption applies to the context
     fn exemption_applies(&self, exemption: &PolicyExemption, context: &EvaluationContext) -> bool {
-        // Check if exemption is valid
-        if !exemption.is_valid() {
+        // Check if exemption is valid at the time of the context
+        if !exemption.is_valid_at(context.timestamp) {
             return false;
         }
 
@@ -187,12 +641,46 @@ impl PolicyEvaluator {
         }
 
         // Check conditions
-        for condition in &exemption.conditions {
-            if !self.evaluate_condition(condition, context) {
-                return false;
-            }
+        exemption.conditions.iter().all(|condition| self.evaluate_condition(condition, context))
+    }
+
+    /// Count a use of a usage-limited exemption, refusing it once its uses are spent
+    fn record_exemption_use(&self, exemption: &PolicyExemption, context: &EvaluationContext) -> bool {
+        let Some(max_uses) = exemption.max_uses else {
+            return true;
+        };
+
+        let mut uses = self.exemption_uses.lock().unwrap();
+        let count = uses.entry(exemption.id).or_insert(exemption.uses_count);
+        if *count >= max_uses {
+            return false;
         }
+        *count += 1;
 
+        let mut events = self.exemption_events.lock().unwrap();
+        events.push(PolicyEvent::PolicyExemptionUsed(PolicyExemptionUsed {
+            event_id: Uuid::now_v7(),
+            identity: crate::sagas::create_root_command(),
+            exemption_id: exemption.id,
+            policy_id: exemption.policy_id,
+            uses_count: *count,
+            used_at: context.timestamp,
+        }));
+        if *count == max_uses {
+            if self.counted_exemptions.lock().unwrap().remove(&exemption.id) {
+                crate::metrics::adjust_active_exemptions(-1);
+            }
+            events.push(PolicyEvent::PolicyExemptionExhausted(
+                PolicyExemptionExhausted {
+                    event_id: Uuid::now_v7(),
+                    identity: crate::sagas::create_root_command(),
+                    exemption_id: exemption.id,
+                    policy_id: exemption.policy_id,
+                    uses_count: *count,
+                    exhausted_at: context.timestamp,
+                },
+            ));
+        }
         true
     }
 
@@ -220,6 +708,21 @@ impl PolicyEvaluator {
             crate::aggregate::ConditionOperator::LessThan => {
                 self.compare_values(field_value, &condition.value) == Some(std::cmp::Ordering::Less)
             }
+            crate::aggregate::ConditionOperator::Between => {
+                // Inclusive range given as a two-element list
+                match &condition.value {
+                    Value::List(bounds) if bounds.len() == 2 => {
+                        matches!(
+                            self.compare_values(field_value, &bounds[0]),
+                            Some(std::cmp::Ordering::Greater | std::cmp::Ordering::Equal)
+                        ) && matches!(
+                            self.compare_values(field_value, &bounds[1]),
+                            Some(std::cmp::Ordering::Less | std::cmp::Ordering::Equal)
+                        )
+                    }
+                    _ => false,
+                }
+            }
             crate::aggregate::ConditionOperator::Contains => {
                 match (field_value, &condition.value) {
                     (Value::String(s), Value::String(needle)) => s.contains(needle.as_str()),
@@ -242,15 +745,103 @@ impl PolicyEvaluator {
 
     /// Compare two values
     fn compare_values(&self, a: &Value, b: &Value) -> Option<std::cmp::Ordering> {
-        match (a, b) {
-            (Value::Integer(x), Value::Integer(y)) => Some(x.cmp(y)),
-            (Value::Float(x), Value::Float(y)) => x.partial_cmp(y),
-            (Value::String(x), Value::String(y)) => Some(x.cmp(y)),
+        a.compare(b)
+    }
+
+    /// Resolve an arithmetic operand against the context
+    fn resolve_operand<'a>(
+        &self,
+        operand: &'a Operand,
+        context: &'a EvaluationContext,
+    ) -> Result<&'a Value, EvaluationError> {
+        match operand {
+            Operand::Field(field) => context.get_path(field)
+                .ok_or_else(|| EvaluationError::MissingContextField(field.clone())),
+            Operand::Literal(value) => Ok(value),
+        }
+    }
+
+    /// Apply an arithmetic operator
+    ///
+    /// Integer operands stay integral except for division, which always
+    /// produces a Float. Mixed operands are computed as floats.
+    fn compute(&self, left: &Value, op: ArithOp, right: &Value) -> Result<Value, EvaluationError> {
+        let as_float = |v: &Value| match v {
+            Value::Integer(i) => Some(*i as f64),
+            Value::Float(f) => Some(*f),
             _ => None,
+        };
+        let (Some(x), Some(y)) = (as_float(left), as_float(right)) else {
+            return Err(EvaluationError::RuleEvaluationFailed(format!(
+                "Arithmetic requires numeric operands, got {:?} and {:?}",
+                left, right
+            )));
+        };
+
+        if op == ArithOp::Divide && y == 0.0 {
+            return Err(EvaluationError::RuleEvaluationFailed(
+                "Division by zero".to_string()
+            ));
+        }
+
+        if let (Value::Integer(a), Value::Integer(b)) = (left, right) {
+            let result = match op {
+                ArithOp::Add => a.checked_add(*b),
+                ArithOp::Subtract => a.checked_sub(*b),
+                ArithOp::Multiply => a.checked_mul(*b),
+                ArithOp::Divide => return Ok(Value::Float(x / y)),
+            };
+            return result.map(Value::Integer).ok_or_else(|| {
+                EvaluationError::RuleEvaluationFailed(format!("Integer overflow in {:?}", op))
+            });
         }
+
+        Ok(Value::Float(match op {
+            ArithOp::Add => x + y,
+            ArithOp::Subtract => x - y,
+            ArithOp::Multiply => x * y,
+            ArithOp::Divide => x / y,
+        }))
     }
 
     /// Evaluate a single rule
+    fn evaluate_rule_timed(
+        &self,
+        rule: &PolicyRule,
+        context: &EvaluationContext,
+    ) -> Result<RuleResult, EvaluationError> {
+        #[cfg(feature = "tracing-spans")]
+        let span = tracing::info_span!(
+            "policy.rule",
+            rule_id = %rule.id,
+            rule_name = %rule.name,
+            passed = tracing::field::Empty,
+            duration_us = tracing::field::Empty,
+        );
+        #[cfg(feature = "tracing-spans")]
+        let _entered = span.enter();
+
+        let start = Instant::now();
+        let mut result = self.evaluate_rule(rule, context)?;
+        result.execution_time_us = start.elapsed().as_micros() as u64;
+
+        #[cfg(feature = "tracing-spans")]
+        {
+            span.record("passed", result.passed);
+            span.record("duration_us", result.execution_time_us);
+        }
+        Ok(result)
+    }
+
+    fn check_time_budget(&self, start: Instant) -> Result<(), EvaluationError> {
+        if self.time_budget.is_some_and(|budget| start.elapsed() > budget) {
+            return Err(EvaluationError::RuleEvaluationFailed(
+                "time budget exceeded".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
     fn evaluate_rule(
         &self,
         rule: &PolicyRule,
@@ -271,35 +862,51 @@ impl PolicyEvaluator {
             severity: rule.severity,
             actual_value: None,  // Could extract from context
             expected_value: None,  // Could extract from rule
+            execution_time_us: 0,
+            suggested_remediation: if passed { None } else { rule.remediation() },
         };
 
         Ok(result)
     }
 
+    /// Compile a `MatchesRegex` pattern, reusing earlier compilations
+    fn compiled_regex(&self, pattern: &str) -> Result<regex::Regex, EvaluationError> {
+        let mut regexes = self.regexes.lock().unwrap();
+        if let Some(regex) = regexes.get(pattern) {
+            return Ok(regex.clone());
+        }
+
+        let regex = regex::Regex::new(pattern).map_err(|e| {
+            EvaluationError::RuleEvaluationFailed(format!("Invalid pattern '{}': {}", pattern, e))
+        })?;
+        regexes.insert(pattern.to_string(), regex.clone());
+        Ok(regex)
+    }
+
     /// Evaluate a rule expression
-    fn evaluate_expression(
+    pub(crate) fn evaluate_expression(
         &self,
         expr: &RuleExpression,
         context: &EvaluationContext,
     ) -> Result<bool, EvaluationError> {
         match expr {
             RuleExpression::Equal { field, value } => {
-                let field_value = context.get_field(field)
+                let field_value = context.get_path(field)
                     .ok_or_else(|| EvaluationError::MissingContextField(field.clone()))?;
                 Ok(field_value == value)
             }
             RuleExpression::NotEqual { field, value } => {
-                let field_value = context.get_field(field)
+                let field_value = context.get_path(field)
                     .ok_or_else(|| EvaluationError::MissingContextField(field.clone()))?;
                 Ok(field_value != value)
             }
             RuleExpression::GreaterThan { field, value } => {
-                let field_value = context.get_field(field)
+                let field_value = context.get_path(field)
                     .ok_or_else(|| EvaluationError::MissingContextField(field.clone()))?;
                 Ok(self.compare_values(field_value, value) == Some(std::cmp::Ordering::Greater))
             }
             RuleExpression::GreaterThanOrEqual { field, value } => {
-                let field_value = context.get_field(field)
+                let field_value = context.get_path(field)
                     .ok_or_else(|| EvaluationError::MissingContextField(field.clone()))?;
                 Ok(matches!(
                     self.compare_values(field_value, value),
@@ -307,12 +914,12 @@ impl PolicyEvaluator {
                 ))
             }
             RuleExpression::LessThan { field, value } => {
-                let field_value = context.get_field(field)
+                let field_value = context.get_path(field)
                     .ok_or_else(|| EvaluationError::MissingContextField(field.clone()))?;
                 Ok(self.compare_values(field_value, value) == Some(std::cmp::Ordering::Less))
             }
             RuleExpression::LessThanOrEqual { field, value } => {
-                let field_value = context.get_field(field)
+                let field_value = context.get_path(field)
                     .ok_or_else(|| EvaluationError::MissingContextField(field.clone()))?;
                 Ok(matches!(
                     self.compare_values(field_value, value),
@@ -339,17 +946,17 @@ impl PolicyEvaluator {
                 Ok(!self.evaluate_expression(expr, context)?)
             }
             RuleExpression::In { field, values } => {
-                let field_value = context.get_field(field)
+                let field_value = context.get_path(field)
                     .ok_or_else(|| EvaluationError::MissingContextField(field.clone()))?;
                 Ok(values.contains(field_value))
             }
             RuleExpression::NotIn { field, values } => {
-                let field_value = context.get_field(field)
+                let field_value = context.get_path(field)
                     .ok_or_else(|| EvaluationError::MissingContextField(field.clone()))?;
                 Ok(!values.contains(field_value))
             }
             RuleExpression::Contains { field, value } => {
-                let field_value = context.get_field(field)
+                let field_value = context.get_path(field)
                     .ok_or_else(|| EvaluationError::MissingContextField(field.clone()))?;
                 match (field_value, value) {
                     (Value::String(s), Value::String(needle)) => Ok(s.contains(needle.as_str())),
@@ -358,17 +965,25 @@ impl PolicyEvaluator {
                 }
             }
             RuleExpression::Matches { field, pattern } => {
-                let field_value = context.get_field(field)
+                let field_value = context.get_path(field)
                     .ok_or_else(|| EvaluationError::MissingContextField(field.clone()))?;
                 if let Value::String(s) = field_value {
-                    // Simple pattern matching (could use regex)
-                    Ok(s.contains(pattern))
+                    Ok(s.contains(pattern.as_str()))
+                } else {
+                    Ok(false)
+                }
+            }
+            RuleExpression::MatchesRegex { field, pattern } => {
+                let field_value = context.get_path(field)
+                    .ok_or_else(|| EvaluationError::MissingContextField(field.clone()))?;
+                if let Value::String(s) = field_value {
+                    Ok(self.compiled_regex(pattern)?.is_match(s))
                 } else {
                     Ok(false)
                 }
             }
             RuleExpression::StartsWith { field, prefix } => {
-                let field_value = context.get_field(field)
+                let field_value = context.get_path(field)
                     .ok_or_else(|| EvaluationError::MissingContextField(field.clone()))?;
                 if let Value::String(s) = field_value {
                     Ok(s.starts_with(prefix))
@@ -377,7 +992,7 @@ impl PolicyEvaluator {
                 }
             }
             RuleExpression::EndsWith { field, suffix } => {
-                let field_value = context.get_field(field)
+                let field_value = context.get_path(field)
                     .ok_or_else(|| EvaluationError::MissingContextField(field.clone()))?;
                 if let Value::String(s) = field_value {
                     Ok(s.ends_with(suffix))
@@ -386,18 +1001,115 @@ impl PolicyEvaluator {
                 }
             }
             RuleExpression::Exists { field } => {
-                Ok(context.get_field(field).is_some())
+                Ok(context.get_path(field).is_some())
             }
             RuleExpression::NotExists { field } => {
-                Ok(context.get_field(field).is_none())
+                Ok(context.get_path(field).is_none())
             }
-            RuleExpression::Custom { predicate, args: _ } => {
-                // Custom predicates would be registered separately
-                Err(EvaluationError::RuleEvaluationFailed(
-                    format!("Custom predicate '{}' not implemented", predicate)
-                ))
+            RuleExpression::IpInNetwork { field, networks } => {
+                let field_value = context.get_path(field)
+                    .ok_or_else(|| EvaluationError::MissingContextField(field.clone()))?;
+                // Unparseable addresses are never inside a network
+                let ip = match field_value {
+                    Value::String(s) => s.parse::<std::net::IpAddr>().ok(),
+                    _ => None,
+                };
+                Ok(ip.is_some_and(|ip| networks.iter().any(|network| network.contains(&ip))))
             }
+            RuleExpression::WithinGeoRegion { field, regions } => {
+                let field_value = context.get_path(field)
+                    .ok_or_else(|| EvaluationError::MissingContextField(field.clone()))?;
+                let (lat, lon) = coordinates(field_value).ok_or_else(|| {
+                    EvaluationError::RuleEvaluationFailed(format!(
+                        "Field '{}' is not a coordinate: {:?}",
+                        field, field_value
+                    ))
+                })?;
+                Ok(regions.iter().any(|region| region.contains(lat, lon)))
+            }
+            RuleExpression::WithinTimeConstraint { constraint } => {
+                constraint.allows(context.timestamp)
+                    .map_err(|e| EvaluationError::RuleEvaluationFailed(e.to_string()))
+            }
+            RuleExpression::Compute { left, op, right, compare, value } => {
+                let left = self.resolve_operand(left, context)?;
+                let right = self.resolve_operand(right, context)?;
+                let result = self.compute(left, *op, right)?;
+                Ok(compare.holds(self.compare_values(&result, value)))
+            }
+            RuleExpression::Custom { predicate, args } => {
+                let custom = self.predicates.get(predicate).ok_or_else(|| {
+                    EvaluationError::RuleEvaluationFailed(
+                        format!("Custom predicate '{}' not implemented", predicate)
+                    )
+                })?;
+                Ok(custom(args, context))
+            }
+        }
+    }
+}
+
+/// Read a coordinate from `{lat, lon}` (or `latitude`/`longitude`) or `[lat, lon]`
+fn coordinates(value: &Value) -> Option<(f64, f64)> {
+    let number = |v: &Value| match v {
+        Value::Integer(i) => Some(*i as f64),
+        Value::Float(f) => Some(*f),
+        _ => None,
+    };
+
+    match value {
+        Value::Map(map) => {
+            let lat = map.get("lat").or_else(|| map.get("latitude"))?;
+            let lon = map.get("lon").or_else(|| map.get("longitude"))?;
+            Some((number(lat)?, number(lon)?))
+        }
+        Value::List(items) if items.len() == 2 => Some((number(&items[0])?, number(&items[1])?)),
+        _ => None,
+    }
+}
+
+/// Outcome of an `And` (`decisive` false) or `Or` (`decisive` true) from its children's traces
+///
+/// The first child that failed or produced `decisive` settles it, as when
+/// evaluating the expression directly.
+fn combine_traces(children: &[ExpressionTrace], decisive: bool) -> (Option<bool>, Option<String>) {
+    for child in children {
+        if let Some(error) = &child.error {
+            return (None, Some(error.clone()));
         }
+        if child.result == Some(decisive) {
+            return (Some(decisive), None);
+        }
+    }
+    (Some(!decisive), None)
+}
+
+/// Name of an expression's variant, as shown in traces
+fn operator_name(expr: &RuleExpression) -> &'static str {
+    match expr {
+        RuleExpression::Equal { .. } => "Equal",
+        RuleExpression::NotEqual { .. } => "NotEqual",
+        RuleExpression::GreaterThan { .. } => "GreaterThan",
+        RuleExpression::GreaterThanOrEqual { .. } => "GreaterThanOrEqual",
+        RuleExpression::LessThan { .. } => "LessThan",
+        RuleExpression::LessThanOrEqual { .. } => "LessThanOrEqual",
+        RuleExpression::And(_) => "And",
+        RuleExpression::Or(_) => "Or",
+        RuleExpression::Not(_) => "Not",
+        RuleExpression::In { .. } => "In",
+        RuleExpression::NotIn { .. } => "NotIn",
+        RuleExpression::Contains { .. } => "Contains",
+        RuleExpression::Matches { .. } => "Matches",
+        RuleExpression::MatchesRegex { .. } => "MatchesRegex",
+        RuleExpression::StartsWith { .. } => "StartsWith",
+        RuleExpression::EndsWith { .. } => "EndsWith",
+        RuleExpression::Exists { .. } => "Exists",
+        RuleExpression::NotExists { .. } => "NotExists",
+        RuleExpression::IpInNetwork { .. } => "IpInNetwork",
+        RuleExpression::WithinGeoRegion { .. } => "WithinGeoRegion",
+        RuleExpression::WithinTimeConstraint { .. } => "WithinTimeConstraint",
+        RuleExpression::Compute { .. } => "Compute",
+        RuleExpression::Custom { .. } => "Custom",
     }
 }
 
@@ -405,4 +1117,1344 @@ impl Default for PolicyEvaluator {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+impl Drop for PolicyEvaluator {
+    fn drop(&mut self) {
+        let counted = self.counted_exemptions.get_mut().unwrap().len();
+        if counted > 0 {
+            crate::metrics::adjust_active_exemptions(-(counted as i64));
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::{ConflictResolution, PolicySet};
+
+    fn active_policy(name: &str, rule: PolicyRule) -> Policy {
+        let mut policy = Policy::new(name, "Test policy");
+        policy.status = PolicyStatus::Active;
+        policy.add_rule(rule);
+        policy
+    }
+
+    #[test]
+    fn test_evaluate_each_returns_per_policy_results() {
+        let evaluator = PolicyEvaluator::new();
+        let compliant = active_policy("Key Size", PolicyRule::min_key_size(2048));
+        let non_compliant = active_policy("Validity", PolicyRule::max_validity_days(90));
+        let mut inactive = Policy::new("Draft", "Not yet active");
+        inactive.add_rule(PolicyRule::min_key_size(4096));
+
+        let context = EvaluationContext::new()
+            .with_field("key_size", 4096)
+            .with_field("validity_days", 365);
+
+        let outcomes =
+            evaluator.evaluate_each(&[&compliant, &non_compliant, &inactive], &context);
+
+        assert_eq!(outcomes.len(), 3);
+        assert_eq!(outcomes[0].0, compliant.id);
+        assert!(outcomes[0].1.as_ref().unwrap().as_ref().unwrap().is_compliant());
+        assert_eq!(outcomes[1].0, non_compliant.id);
+        assert!(!outcomes[1].1.as_ref().unwrap().as_ref().unwrap().is_compliant());
+        assert_eq!(outcomes[2].0, inactive.id);
+        assert!(outcomes[2].1.as_ref().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_evaluate_and_record_emits_evaluated_event() {
+        let evaluator = PolicyEvaluator::new();
+        let mut policy = active_policy("Key Size", PolicyRule::min_key_size(2048));
+        policy.add_rule(PolicyRule::max_validity_days(90));
+
+        let compliant = EvaluationContext::new()
+            .with_field("key_size", 4096)
+            .with_field("validity_days", 30);
+        let (evaluation, event) = evaluator.evaluate_and_record(&policy, &compliant).unwrap();
+        let PolicyEvent::PolicyEvaluated(recorded) = event else {
+            panic!("expected PolicyEvaluated");
+        };
+        assert_eq!(recorded.policy_id, policy.id);
+        assert_eq!(recorded.evaluation_id, evaluation.id);
+        assert_eq!(recorded.result, ComplianceResult::Compliant);
+        assert_eq!(recorded.violation_count, 0);
+        assert_eq!(recorded.execution_time_ms, evaluation.execution_time_ms);
+        assert_eq!(recorded.context_hash, compliant.fingerprint());
+        assert_eq!(recorded.subject(), "policies.policy.evaluated.v1");
+
+        let non_compliant = EvaluationContext::new()
+            .with_field("key_size", 1024)
+            .with_field("validity_days", 365);
+        let (_, event) = evaluator.evaluate_and_record(&policy, &non_compliant).unwrap();
+        let PolicyEvent::PolicyEvaluated(recorded) = event else {
+            panic!("expected PolicyEvaluated");
+        };
+        assert!(matches!(recorded.result, ComplianceResult::NonCompliant { .. }));
+        assert_eq!(recorded.violation_count, 2);
+        assert_eq!(recorded.context_hash, non_compliant.fingerprint());
+        assert_ne!(recorded.context_hash, compliant.fingerprint());
+    }
+
+    fn slow_evaluator(delay: Duration) -> PolicyEvaluator {
+        let mut evaluator = PolicyEvaluator::new();
+        evaluator.register_predicate("slow", move |_, _| {
+            std::thread::sleep(delay);
+            true
+        });
+        evaluator
+    }
+
+    fn slow_rule() -> PolicyRule {
+        PolicyRule::new(
+            "Slow check",
+            "Deliberately slow predicate",
+            RuleExpression::Custom {
+                predicate: "slow".to_string(),
+                args: HashMap::new(),
+            },
+            Severity::Low,
+        )
+    }
+
+    #[test]
+    fn test_rule_timing_identifies_slowest_rule() {
+        let evaluator = slow_evaluator(Duration::from_millis(5));
+        let mut policy = active_policy("Mixed", PolicyRule::min_key_size(2048));
+        let slow = slow_rule();
+        policy.add_rule(slow.clone());
+
+        let context = EvaluationContext::new().with_field("key_size", 4096);
+        let evaluation = evaluator.evaluate(&policy, &context).unwrap();
+
+        let slowest = evaluation.slowest_rules(1);
+        assert_eq!(slowest.len(), 1);
+        assert_eq!(slowest[0].rule_id, slow.id);
+        assert!(slowest[0].execution_time_us >= 5_000);
+        assert_eq!(evaluation.slowest_rules(5).len(), 2);
+    }
+
+    #[test]
+    fn test_time_budget_aborts_evaluation() {
+        let evaluator =
+            slow_evaluator(Duration::from_millis(20)).with_time_budget(Duration::from_millis(10));
+        let mut policy = active_policy("Slow", slow_rule());
+        policy.add_rule(PolicyRule::min_key_size(2048));
+
+        let context = EvaluationContext::new().with_field("key_size", 4096);
+        let err = evaluator.evaluate(&policy, &context).unwrap_err();
+        assert!(matches!(
+            err,
+            EvaluationError::RuleEvaluationFailed(ref msg) if msg == "time budget exceeded"
+        ));
+
+        // A generous budget lets the same policy through
+        let evaluator =
+            slow_evaluator(Duration::from_millis(1)).with_time_budget(Duration::from_secs(5));
+        assert!(evaluator.evaluate(&policy, &context).unwrap().is_compliant());
+    }
+
+    #[test]
+    fn test_parallel_evaluation_matches_sequential() {
+        let mut policy = active_policy("Large", PolicyRule::min_key_size(2048));
+        for i in 0..200 {
+            let rule = match i % 3 {
+                0 => PolicyRule::min_key_size(1024 + i * 16),
+                1 => PolicyRule::max_validity_days(30 + i),
+                _ => PolicyRule::new(
+                    format!("Pattern {}", i),
+                    "Subject naming",
+                    RuleExpression::MatchesRegex {
+                        field: "subject".to_string(),
+                        pattern: format!("^svc-[a-z]+-{}$", i % 7),
+                    },
+                    Severity::Medium,
+                ),
+            };
+            policy.add_rule(rule);
+        }
+        let context = EvaluationContext::new()
+            .with_field("key_size", 3072)
+            .with_field("validity_days", 120)
+            .with_field("subject", "svc-billing-3");
+
+        let sequential = PolicyEvaluator::new().evaluate(&policy, &context).unwrap();
+        let parallel = PolicyEvaluator::new()
+            .with_parallel(true)
+            .evaluate(&policy, &context)
+            .unwrap();
+
+        let outcomes = |evaluation: &PolicyEvaluation| {
+            evaluation
+                .rule_results
+                .iter()
+                .map(|r| (r.rule_id, r.passed, r.message.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(outcomes(&sequential), outcomes(&parallel));
+        assert_eq!(sequential.overall_result, parallel.overall_result);
+        assert_eq!(parallel.rule_results.len(), 201);
+    }
+
+    #[test]
+    fn test_matches_is_substring_and_matches_regex_is_cached() {
+        let evaluator = PolicyEvaluator::new();
+        let context = EvaluationContext::new().with_field("subject", "svc.billing");
+
+        let substring = |pattern: &str| RuleExpression::Matches {
+            field: "subject".to_string(),
+            pattern: pattern.to_string(),
+        };
+        assert!(evaluator.evaluate_expression(&substring("c.b"), &context).unwrap());
+        assert!(!evaluator.evaluate_expression(&substring("^svc"), &context).unwrap());
+        assert!(evaluator.evaluate_expression(&substring("("), &context).is_ok());
+
+        let regex = |pattern: &str| RuleExpression::MatchesRegex {
+            field: "subject".to_string(),
+            pattern: pattern.to_string(),
+        };
+        for _ in 0..3 {
+            assert!(evaluator.evaluate_expression(&regex("^svc.[a-z]+$"), &context).unwrap());
+        }
+        assert!(!evaluator.evaluate_expression(&regex("^api"), &context).unwrap());
+        assert_eq!(evaluator.regexes.lock().unwrap().len(), 2);
+
+        assert!(matches!(
+            evaluator.evaluate_expression(&regex("("), &context),
+            Err(EvaluationError::RuleEvaluationFailed(_))
+        ));
+        assert_eq!(evaluator.regexes.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_optimize_evaluates_cheap_children_first() {
+        let exists = |field: &str| RuleExpression::Exists {
+            field: field.to_string(),
+        };
+        let any_owner = RuleExpression::Or(vec![exists("owner"), exists("team"), exists("cost_center")]);
+        let no_legacy = RuleExpression::NotExists {
+            field: "legacy_token".to_string(),
+        };
+        let expr = RuleExpression::And(vec![any_owner.clone(), no_legacy.clone()]);
+
+        let optimized = expr.clone().optimize();
+        assert_eq!(optimized, RuleExpression::And(vec![no_legacy, any_owner]));
+
+        let evaluator = PolicyEvaluator::new();
+        for context in [
+            EvaluationContext::new().with_field("team", "platform"),
+            EvaluationContext::new().with_field("legacy_token", "abc"),
+            EvaluationContext::new(),
+        ] {
+            assert_eq!(
+                evaluator.evaluate_expression(&expr, &context).unwrap(),
+                evaluator.evaluate_expression(&optimized, &context).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_optimize_still_reports_missing_field() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut evaluator = PolicyEvaluator::new();
+        let counter = calls.clone();
+        evaluator.register_predicate("audited", move |_, _| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            true
+        });
+
+        let expr = RuleExpression::And(vec![
+            RuleExpression::MatchesRegex {
+                field: "subject".to_string(),
+                pattern: "^svc-".to_string(),
+            },
+            RuleExpression::Custom {
+                predicate: "audited".to_string(),
+                args: HashMap::new(),
+            },
+            RuleExpression::Equal {
+                field: "environment".to_string(),
+                value: Value::String("production".to_string()),
+            },
+            RuleExpression::Exists {
+                field: "owner".to_string(),
+            },
+        ]);
+
+        // Every child but the last can fail or has side effects, so none move
+        let optimized = expr.clone().optimize();
+        assert_eq!(optimized, expr);
+
+        let context = EvaluationContext::new().with_field("environment", "staging");
+        assert!(matches!(
+            evaluator.evaluate_expression(&optimized, &context),
+            Err(EvaluationError::MissingContextField(field)) if field == "subject"
+        ));
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_nnf_pushes_negation_to_leaves_and_preserves_outcome() {
+        let eq = |field: &str, value: &str| RuleExpression::Equal {
+            field: field.to_string(),
+            value: Value::String(value.to_string()),
+        };
+        let not = |expr: RuleExpression| RuleExpression::Not(Box::new(expr));
+        let over_limit = RuleExpression::GreaterThan {
+            field: "amount".to_string(),
+            value: Value::Integer(100),
+        };
+
+        // Not(And(a, Not(Or(b, c > 100)))) => Or(!a, Or(b, c > 100))
+        let expr = not(RuleExpression::And(vec![
+            eq("environment", "production"),
+            not(RuleExpression::Or(vec![
+                RuleExpression::Exists { field: "approved_by".to_string() },
+                over_limit.clone(),
+            ])),
+        ]));
+        let nnf = expr.clone().to_nnf();
+        assert_eq!(
+            nnf,
+            RuleExpression::Or(vec![
+                RuleExpression::NotEqual {
+                    field: "environment".to_string(),
+                    value: Value::String("production".to_string()),
+                },
+                RuleExpression::Or(vec![
+                    RuleExpression::Exists { field: "approved_by".to_string() },
+                    over_limit.clone(),
+                ]),
+            ])
+        );
+
+        // Ordered comparisons stay under Not; double negation cancels
+        let expr2 = not(RuleExpression::Or(vec![over_limit.clone(), not(not(eq("tier", "free")))]));
+        let nnf2 = expr2.clone().to_nnf();
+        assert_eq!(
+            nnf2,
+            RuleExpression::And(vec![
+                not(over_limit),
+                RuleExpression::NotEqual {
+                    field: "tier".to_string(),
+                    value: Value::String("free".to_string()),
+                },
+            ])
+        );
+
+        let evaluator = PolicyEvaluator::new();
+        for environment in ["production", "staging"] {
+            for amount in [50, 500] {
+                for approved in [true, false] {
+                    for tier in ["free", "paid"] {
+                        let mut context = EvaluationContext::new()
+                            .with_field("environment", environment)
+                            .with_field("amount", amount as i64)
+                            .with_field("tier", tier);
+                        if approved {
+                            context = context.with_field("approved_by", "alice");
+                        }
+                        for (original, normalized) in [(&expr, &nnf), (&expr2, &nnf2)] {
+                            assert_eq!(
+                                evaluator.evaluate_expression(original, &context).unwrap(),
+                                evaluator.evaluate_expression(normalized, &context).unwrap()
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "tracing-spans")]
+    #[test]
+    fn test_evaluation_spans_carry_policy_and_rule_attributes() {
+        use std::sync::Mutex;
+        use tracing::field::{Field, Visit};
+        use tracing_subscriber::layer::{Context, SubscriberExt};
+        use tracing_subscriber::registry::LookupSpan;
+
+        #[derive(Debug, Default)]
+        struct CapturedSpan {
+            name: String,
+            parent: Option<String>,
+            fields: HashMap<String, String>,
+        }
+
+        struct Fields<'a>(&'a mut HashMap<String, String>);
+
+        impl Visit for Fields<'_> {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                self.0.insert(field.name().to_string(), format!("{:?}", value));
+            }
+
+            fn record_str(&mut self, field: &Field, value: &str) {
+                self.0.insert(field.name().to_string(), value.to_string());
+            }
+        }
+
+        #[derive(Clone, Default)]
+        struct TestExporter(Arc<Mutex<HashMap<u64, CapturedSpan>>>);
+
+        impl<S: tracing::Subscriber + for<'a> LookupSpan<'a>> tracing_subscriber::Layer<S>
+            for TestExporter
+        {
+            fn on_new_span(
+                &self,
+                attrs: &tracing::span::Attributes<'_>,
+                id: &tracing::span::Id,
+                ctx: Context<'_, S>,
+            ) {
+                let mut span = CapturedSpan {
+                    name: attrs.metadata().name().to_string(),
+                    parent: ctx
+                        .span(id)
+                        .and_then(|s| s.parent())
+                        .map(|p| p.name().to_string()),
+                    ..CapturedSpan::default()
+                };
+                attrs.record(&mut Fields(&mut span.fields));
+                self.0.lock().unwrap().insert(id.into_u64(), span);
+            }
+
+            fn on_record(
+                &self,
+                id: &tracing::span::Id,
+                values: &tracing::span::Record<'_>,
+                _ctx: Context<'_, S>,
+            ) {
+                if let Some(span) = self.0.lock().unwrap().get_mut(&id.into_u64()) {
+                    values.record(&mut Fields(&mut span.fields));
+                }
+            }
+        }
+
+        let exporter = TestExporter::default();
+        let subscriber = tracing_subscriber::registry().with(exporter.clone());
+        let policy = active_policy("Key Size", PolicyRule::min_key_size(2048));
+        let context = EvaluationContext::new()
+            .with_field("key_size", 1024)
+            .with_environment("correlation_id", "corr-42");
+
+        tracing::subscriber::with_default(subscriber, || {
+            PolicyEvaluator::new().evaluate(&policy, &context).unwrap();
+        });
+
+        let spans = exporter.0.lock().unwrap();
+        let policy_span = spans.values().find(|s| s.name == "policy.evaluate").unwrap();
+        assert_eq!(policy_span.fields["policy_id"], policy.id.to_string());
+        assert_eq!(policy_span.fields["correlation_id"], "corr-42");
+        assert_eq!(policy_span.fields["result"], "non_compliant");
+        assert!(policy_span.fields.contains_key("duration_us"));
+
+        let rule_span = spans.values().find(|s| s.name == "policy.rule").unwrap();
+        assert_eq!(rule_span.parent.as_deref(), Some("policy.evaluate"));
+        assert_eq!(rule_span.fields["rule_name"], policy.rules[0].name);
+        assert_eq!(rule_span.fields["passed"], "false");
+        assert!(rule_span.fields.contains_key("duration_us"));
+    }
+
+    #[test]
+    fn test_evaluations_update_metrics() {
+        use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let policy = active_policy("Key Size", PolicyRule::min_key_size(2048));
+
+        ::metrics::with_local_recorder(&recorder, || {
+            let evaluator = PolicyEvaluator::new();
+            for key_size in [4096, 4096, 1024] {
+                let context = EvaluationContext::new().with_field("key_size", key_size);
+                evaluator.evaluate(&policy, &context).unwrap();
+            }
+        });
+
+        let metrics: Vec<_> = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .map(|(key, _, _, value)| (key, value))
+            .collect();
+        let counter = |result: &str| {
+            metrics.iter().find_map(|(key, value)| {
+                let labeled = key.key().labels().any(|l| l.key() == "result" && l.value() == result);
+                match value {
+                    DebugValue::Counter(count)
+                        if key.key().name() == crate::metrics::EVALUATIONS_TOTAL && labeled =>
+                    {
+                        Some(*count)
+                    }
+                    _ => None,
+                }
+            })
+        };
+        assert_eq!(counter("compliant"), Some(2));
+        assert_eq!(counter("non_compliant"), Some(1));
+
+        let durations = metrics.iter().find_map(|(key, value)| match value {
+            DebugValue::Histogram(values)
+                if key.key().name() == crate::metrics::EVALUATION_DURATION_MS =>
+            {
+                Some(values.len())
+            }
+            _ => None,
+        });
+        assert_eq!(durations, Some(3));
+    }
+
+    #[test]
+    fn test_active_exemptions_gauge_sums_evaluators() {
+        use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+        use metrics_util::MetricKind;
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let policy = active_policy("Key Size", PolicyRule::min_key_size(2048));
+
+        let exemption = |valid_for: chrono::Duration| {
+            PolicyExemption::new(
+                PolicyId::new(),
+                "Legacy",
+                "Legacy hardware",
+                "admin",
+                chrono::Utc::now() + valid_for,
+            )
+        };
+        let gauge = || {
+            snapshotter.snapshot().into_vec().into_iter().find_map(|(key, _, _, value)| {
+                match value {
+                    DebugValue::Gauge(v)
+                        if key.kind() == MetricKind::Gauge
+                            && key.key().name() == crate::metrics::ACTIVE_EXEMPTIONS =>
+                    {
+                        Some(v.into_inner())
+                    }
+                    _ => None,
+                }
+            })
+        };
+
+        ::metrics::with_local_recorder(&recorder, || {
+            let mut evaluator = PolicyEvaluator::new();
+            // The lapsed exemption is registered but not counted as active
+            evaluator.register_exemptions(vec![
+                exemption(chrono::Duration::days(30)),
+                exemption(-chrono::Duration::days(1)),
+            ]);
+            assert_eq!(gauge(), Some(1.0));
+
+            // A second evaluator adds to the gauge rather than replacing it
+            let mut other = PolicyEvaluator::new();
+            other.register_exemptions(vec![exemption(chrono::Duration::days(7))]);
+            assert_eq!(gauge(), Some(2.0));
+            drop(other);
+            assert_eq!(gauge(), Some(1.0));
+
+            // Registering more only adds the difference
+            evaluator.register_exemptions(vec![exemption(chrono::Duration::days(7))]);
+            assert_eq!(gauge(), Some(2.0));
+
+            // Spending an exemption's last use stops counting it
+            let mut single_use = exemption(chrono::Duration::days(7));
+            single_use.policy_id = policy.id;
+            single_use.max_uses = Some(1);
+            evaluator.register_exemptions(vec![single_use.clone()]);
+            assert_eq!(gauge(), Some(3.0));
+            let context = EvaluationContext::new().with_field("key_size", 1024);
+            assert!(evaluator.evaluate(&policy, &context).unwrap().is_compliant());
+            assert_eq!(gauge(), Some(2.0));
+
+            // A revoked copy replaces the registered exemption
+            let mut revoked = exemption(chrono::Duration::days(30));
+            evaluator.register_exemptions(vec![revoked.clone()]);
+            assert_eq!(gauge(), Some(3.0));
+            revoked.status = crate::aggregate::ExemptionStatus::Revoked {
+                revoked_by: "admin".to_string(),
+                revoked_at: chrono::Utc::now(),
+                reason: "Hardware replaced".to_string(),
+            };
+            evaluator.register_exemptions(vec![revoked]);
+            assert_eq!(gauge(), Some(2.0));
+
+            // An exemption that lapses stops counting at the next evaluation
+            let lapsing = exemption(chrono::Duration::hours(1));
+            let clock = crate::MockClock::new(lapsing.valid_from);
+            let mut expiring = PolicyEvaluator::new().with_clock(Arc::new(clock.clone()));
+            expiring.register_exemptions(vec![lapsing]);
+            assert_eq!(gauge(), Some(3.0));
+            clock.advance(chrono::Duration::hours(2));
+            let context = EvaluationContext::new().with_field("key_size", 4096);
+            expiring.evaluate(&policy, &context).unwrap();
+            assert_eq!(gauge(), Some(2.0));
+            drop(expiring);
+            assert_eq!(gauge(), Some(2.0));
+
+            // Only exemptions still counted are taken away on drop
+            drop(evaluator);
+            assert_eq!(gauge(), Some(0.0));
+        });
+    }
+
+    #[test]
+    fn test_expression_targets_nested_attribute() {
+        let evaluator = PolicyEvaluator::new();
+        let context = EvaluationContext::from_json(serde_json::json!({
+            "request": { "user": { "department": "security" } }
+        }))
+        .unwrap();
+
+        let expr = RuleExpression::Equal {
+            field: "request.user.department".to_string(),
+            value: Value::String("security".to_string()),
+        };
+        assert!(evaluator.evaluate_expression(&expr, &context).unwrap());
+
+        let missing = RuleExpression::Equal {
+            field: "request.user.team".to_string(),
+            value: Value::String("pki".to_string()),
+        };
+        assert!(matches!(
+            evaluator.evaluate_expression(&missing, &context),
+            Err(EvaluationError::MissingContextField(_))
+        ));
+    }
+
+    fn compute(left: Operand, op: ArithOp, right: Operand, compare: CompareOp, value: Value) -> RuleExpression {
+        RuleExpression::Compute { left, op, right, compare, value }
+    }
+
+    #[test]
+    fn test_compute_mixes_integer_and_float_operands() {
+        let evaluator = PolicyEvaluator::new();
+        let context = EvaluationContext::new()
+            .with_field("amount", 1000)
+            .with_field("limit", 1050)
+            .with_field("fee", 2.5);
+
+        // 1000 * 1.1 = 1100.0 > 1050
+        let margin = compute(
+            Operand::Field("amount".to_string()),
+            ArithOp::Multiply,
+            Operand::Literal(Value::Float(1.1)),
+            CompareOp::GreaterThan,
+            Value::Integer(1050),
+        );
+        assert!(evaluator.evaluate_expression(&margin, &context).unwrap());
+
+        // Integer arithmetic stays integral: 1050 - 1000 == 50
+        let headroom = compute(
+            Operand::Field("limit".to_string()),
+            ArithOp::Subtract,
+            Operand::Field("amount".to_string()),
+            CompareOp::Equal,
+            Value::Integer(50),
+        );
+        assert!(evaluator.evaluate_expression(&headroom, &context).unwrap());
+
+        // 2.5 + 1000 = 1002.5 <= 1002 is false
+        let total = compute(
+            Operand::Field("fee".to_string()),
+            ArithOp::Add,
+            Operand::Field("amount".to_string()),
+            CompareOp::LessThanOrEqual,
+            Value::Integer(1002),
+        );
+        assert!(!evaluator.evaluate_expression(&total, &context).unwrap());
+
+        // Integer division produces a float: 1000 / 3 > 333
+        let share = compute(
+            Operand::Field("amount".to_string()),
+            ArithOp::Divide,
+            Operand::Literal(Value::Integer(3)),
+            CompareOp::GreaterThan,
+            Value::Integer(333),
+        );
+        assert!(evaluator.evaluate_expression(&share, &context).unwrap());
+    }
+
+    #[test]
+    fn test_compute_rejects_division_by_zero() {
+        let evaluator = PolicyEvaluator::new();
+        let context = EvaluationContext::new()
+            .with_field("amount", 1000)
+            .with_field("count", 0);
+
+        let expr = compute(
+            Operand::Field("amount".to_string()),
+            ArithOp::Divide,
+            Operand::Field("count".to_string()),
+            CompareOp::GreaterThan,
+            Value::Integer(10),
+        );
+        assert!(matches!(
+            evaluator.evaluate_expression(&expr, &context),
+            Err(EvaluationError::RuleEvaluationFailed(msg)) if msg.contains("Division by zero")
+        ));
+    }
+
+    #[test]
+    fn test_ip_in_network_matches_ipv4_and_ipv6() {
+        let evaluator = PolicyEvaluator::new();
+        let expr = RuleExpression::IpInNetwork {
+            field: "source_ip".to_string(),
+            networks: vec!["10.0.0.0/8".parse().unwrap(), "2001:db8::/32".parse().unwrap()],
+        };
+        let matches = |ip: &str| {
+            let context = EvaluationContext::new().with_field("source_ip", ip);
+            evaluator.evaluate_expression(&expr, &context).unwrap()
+        };
+
+        assert!(matches("10.42.7.1"));
+        assert!(matches("2001:db8:1234::1"));
+        assert!(!matches("11.0.0.1"));
+        assert!(!matches("2001:db9::1"));
+        assert!(!matches("not-an-address"));
+    }
+
+    #[test]
+    fn test_geo_region_containment() {
+        let evaluator = PolicyEvaluator::new();
+        let berlin = GeoRegion {
+            name: "Berlin".to_string(),
+            center_lat: 52.52,
+            center_lon: 13.405,
+            radius_km: 50.0,
+        };
+        // Straddles the antimeridian near Fiji
+        let fiji = GeoRegion {
+            name: "Fiji".to_string(),
+            center_lat: -17.0,
+            center_lon: 179.9,
+            radius_km: 100.0,
+        };
+        let expr = RuleExpression::WithinGeoRegion {
+            field: "location".to_string(),
+            regions: vec![berlin.clone(), fiji.clone()],
+        };
+        let inside = |location: serde_json::Value| {
+            let context = EvaluationContext::from_json(serde_json::json!({ "location": location })).unwrap();
+            evaluator.evaluate_expression(&expr, &context).unwrap()
+        };
+
+        // Potsdam is ~27 km from central Berlin, Hamburg ~255 km
+        assert!(inside(serde_json::json!({ "lat": 52.39, "lon": 13.06 })));
+        assert!(!inside(serde_json::json!([53.55, 9.99])));
+
+        // Just across the antimeridian from the Fiji center
+        assert!(fiji.contains(-17.0, -179.9));
+        assert!(fiji.distance_km(-17.0, -179.9) < 25.0);
+        assert!(inside(serde_json::json!({ "latitude": -17.0, "longitude": -179.9 })));
+        assert!(!inside(serde_json::json!([-17.0, -170.0])));
+    }
+
+    fn business_hours() -> TimeConstraint {
+        use chrono::Weekday;
+
+        TimeConstraint {
+            timezone: "+02:00".to_string(),
+            allowed_days: vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri],
+            allowed_hours: Some((9, 17)),
+            blackout_periods: vec![BlackoutPeriod {
+                start: chrono::NaiveDate::from_ymd_opt(2025, 12, 24).unwrap(),
+                end: chrono::NaiveDate::from_ymd_opt(2025, 12, 26).unwrap(),
+                reason: Some("Change freeze".to_string()),
+            }],
+        }
+    }
+
+    fn allowed_at(constraint: TimeConstraint, at: &str) -> Result<bool, EvaluationError> {
+        let mut context = EvaluationContext::new();
+        context.timestamp = at.parse().unwrap();
+        PolicyEvaluator::new()
+            .evaluate_expression(&RuleExpression::WithinTimeConstraint { constraint }, &context)
+    }
+
+    #[test]
+    fn test_time_constraint_honours_timezone_days_and_hours() {
+        // Wednesday 07:30 UTC is 09:30 at +02:00
+        assert!(allowed_at(business_hours(), "2025-12-10T07:30:00Z").unwrap());
+        // Wednesday 06:30 UTC is 08:30 local, before opening
+        assert!(!allowed_at(business_hours(), "2025-12-10T06:30:00Z").unwrap());
+        // Saturday is not an allowed day
+        assert!(!allowed_at(business_hours(), "2025-12-13T10:00:00Z").unwrap());
+    }
+
+    #[test]
+    fn test_time_constraint_follows_iana_daylight_saving() {
+        let mut constraint = business_hours();
+        constraint.timezone = "Europe/Berlin".to_string();
+
+        // 08:30 UTC is 09:30 in winter (CET, +01:00)
+        assert!(allowed_at(constraint.clone(), "2025-12-10T08:30:00Z").unwrap());
+        assert!(!allowed_at(constraint.clone(), "2025-12-10T07:30:00Z").unwrap());
+        // and 10:30 in summer (CEST, +02:00), so 07:30 UTC is already open
+        assert!(allowed_at(constraint.clone(), "2025-07-09T07:30:00Z").unwrap());
+        assert!(!allowed_at(constraint, "2025-07-09T15:30:00Z").unwrap());
+    }
+
+    #[test]
+    fn test_time_constraint_blackout_and_invalid_timezone() {
+        // Wednesday inside business hours, but during the change freeze
+        assert!(!allowed_at(business_hours(), "2025-12-24T10:00:00Z").unwrap());
+
+        let mut constraint = business_hours();
+        constraint.timezone = "Mars/Olympus".to_string();
+        assert!(matches!(
+            allowed_at(constraint, "2025-12-10T10:00:00Z"),
+            Err(EvaluationError::RuleEvaluationFailed(_))
+        ));
+    }
+
+    fn conflicting_set(resolution: ConflictResolution) -> (PolicySet, Policy, Policy) {
+        let key_size_rule = |bits| PolicyRule::new(
+            "Key Size",
+            format!("Key size must be exactly {} bits", bits),
+            RuleExpression::Equal { field: "key_size".to_string(), value: Value::Integer(bits) },
+            Severity::High,
+        );
+        let mut lenient = active_policy("Lenient", key_size_rule(2048));
+        lenient.enforcement_level = EnforcementLevel::Soft;
+        let mut strict = active_policy("Strict", key_size_rule(4096));
+        strict.enforcement_level = EnforcementLevel::Hard;
+
+        let mut set = PolicySet::new("Keys", "Key policies");
+        set.add_policy(lenient.id);
+        set.add_policy(strict.id);
+        set.conflict_resolution = resolution;
+        (set, lenient, strict)
+    }
+
+    #[test]
+    fn test_policy_set_evaluation_respects_conflict_resolution() {
+        let evaluator = PolicyEvaluator::new();
+        let context = EvaluationContext::new().with_field("key_size", 2048);
+
+        // The lenient policy comes first and wins
+        let (set, lenient, strict) = conflicting_set(ConflictResolution::FirstWins);
+        let outcome = evaluator
+            .evaluate_policy_set(&set, &[&lenient, &strict], &context)
+            .unwrap();
+        assert!(outcome.result.is_compliant());
+        assert_eq!(outcome.evaluated, 1);
+
+        // The strict policy has the higher enforcement level and wins
+        let (set, lenient, strict) = conflicting_set(ConflictResolution::MostRestrictive);
+        let outcome = evaluator
+            .evaluate_policy_set(&set, &[&lenient, &strict], &context)
+            .unwrap();
+        assert!(!outcome.result.is_compliant());
+        assert_eq!(outcome.evaluated, 1);
+
+        let (set, lenient, strict) = conflicting_set(ConflictResolution::FailOnConflict);
+        assert!(matches!(
+            evaluator.evaluate_policy_set(&set, &[&lenient, &strict], &context),
+            Err(EvaluationError::ConflictResolution(_))
+        ));
+    }
+
+    #[test]
+    fn test_policy_set_keeps_policies_differing_only_in_enforcement() {
+        let evaluator = PolicyEvaluator::new();
+        let mut key_size = active_policy("Key Size", PolicyRule::min_key_size(2048));
+        key_size.enforcement_level = EnforcementLevel::Critical;
+        let mut mfa = active_policy(
+            "MFA",
+            PolicyRule::new(
+                "MFA",
+                "MFA must be enabled",
+                RuleExpression::Exists { field: "mfa".to_string() },
+                Severity::Low,
+            ),
+        );
+        mfa.enforcement_level = EnforcementLevel::Advisory;
+
+        let mut set = PolicySet::new("Baseline", "Unrelated policies");
+        set.add_policy(mfa.id);
+        set.add_policy(key_size.id);
+        set.conflict_resolution = ConflictResolution::LeastRestrictive;
+
+        let context = EvaluationContext::new().with_field("key_size", 512).with_field("mfa", true);
+        let outcome = evaluator.evaluate_policy_set(&set, &[&mfa, &key_size], &context).unwrap();
+        assert_eq!(outcome.evaluated, 2);
+        assert!(!outcome.result.is_compliant());
+    }
+
+    #[test]
+    fn test_delegate_authorized_only_within_scope_and_lifetime() {
+        let evaluator = PolicyEvaluator::new();
+        let mut operations = active_policy("Key Size", PolicyRule::min_key_size(2048));
+        operations.target = PolicyTarget::Role("operator".to_string());
+        let mut admin = operations.clone();
+        admin.target = PolicyTarget::Role("admin".to_string());
+
+        let grant = DelegationGrant::new(
+            "alice",
+            "bob",
+            PolicyTarget::Role("operator".to_string()),
+            chrono::Utc::now() + chrono::Duration::hours(1),
+        );
+        let context = EvaluationContext::new().with_field("key_size", 4096);
+
+        let evaluation = evaluator
+            .evaluate_on_behalf_of(&operations, &context, "bob", "alice", std::slice::from_ref(&grant))
+            .unwrap();
+        assert!(evaluation.is_compliant());
+        assert_eq!(evaluation.context.requester.as_deref(), Some("alice"));
+
+        // Outside the delegated scope
+        assert!(matches!(
+            evaluator.evaluate_on_behalf_of(&admin, &context, "bob", "alice", std::slice::from_ref(&grant)),
+            Err(EvaluationError::DelegationNotGranted { .. })
+        ));
+
+        // Nobody else can use the grant
+        assert!(evaluator
+            .evaluate_on_behalf_of(&operations, &context, "mallory", "alice", std::slice::from_ref(&grant))
+            .is_err());
+
+        // After expiry
+        let mut later = context.clone();
+        later.timestamp = grant.expires_at;
+        assert!(matches!(
+            evaluator.evaluate_on_behalf_of(&operations, &later, "bob", "alice", &[grant]),
+            Err(EvaluationError::DelegationNotGranted { .. })
+        ));
+    }
+
+    #[test]
+    fn test_evaluate_set_skips_expired_policy() {
+        let evaluator = PolicyEvaluator::new();
+        let current = active_policy("Key Size", PolicyRule::min_key_size(2048));
+        let mut expired = active_policy("Validity", PolicyRule::max_validity_days(90));
+        expired.expiry_date = Some(chrono::Utc::now() - chrono::Duration::days(1));
+
+        let context = EvaluationContext::new()
+            .with_field("key_size", 4096)
+            .with_field("validity_days", 365);
+
+        let outcome = evaluator
+            .evaluate_set_detailed(
+                vec![&current, &expired],
+                &context,
+                crate::aggregate::CompositionRule::All,
+            )
+            .unwrap();
+
+        assert!(outcome.result.is_compliant());
+        assert_eq!(outcome.evaluated, 1);
+        assert_eq!(outcome.skipped, 1);
+
+        // The plain form keeps returning just the composed result
+        assert!(evaluator
+            .evaluate_set(vec![&current, &expired], &context, crate::aggregate::CompositionRule::All)
+            .unwrap()
+            .is_compliant());
+    }
+
+    #[test]
+    fn test_evaluate_set_uses_context_timestamp() {
+        let evaluator = PolicyEvaluator::new();
+        let mut policy = active_policy("Validity", PolicyRule::max_validity_days(90));
+        policy.expiry_date = Some(chrono::Utc::now() - chrono::Duration::days(1));
+
+        let mut context = EvaluationContext::new().with_field("validity_days", 365);
+        context.timestamp = chrono::Utc::now() - chrono::Duration::days(2);
+
+        let outcome = evaluator
+            .evaluate_set_detailed(vec![&policy], &context, crate::aggregate::CompositionRule::All)
+            .unwrap();
+
+        assert!(!outcome.result.is_compliant());
+        assert_eq!(outcome.evaluated, 1);
+        assert_eq!(outcome.skipped, 0);
+    }
+
+    #[test]
+    fn test_evaluate_replays_historical_context() {
+        let now = chrono::Utc::now();
+        let mut evaluator = PolicyEvaluator::new();
+        let mut policy = active_policy("Validity", PolicyRule::max_validity_days(90));
+        policy.expiry_date = Some(now - chrono::Duration::days(1));
+
+        let mut exemption = PolicyExemption::new(
+            policy.id,
+            "Migration",
+            "Legacy certificates",
+            "admin",
+            now - chrono::Duration::days(2),
+        );
+        exemption.valid_from = now - chrono::Duration::days(5);
+        evaluator.register_exemptions(vec![exemption]);
+
+        let mut context = EvaluationContext::new().with_field("validity_days", 365);
+        context.timestamp = now - chrono::Duration::days(3);
+        let evaluation = evaluator.evaluate(&policy, &context).unwrap();
+        assert!(matches!(
+            evaluation.overall_result,
+            ComplianceResult::CompliantWithExemption { .. }
+        ));
+
+        context.timestamp = now - chrono::Duration::hours(36);
+        let evaluation = evaluator.evaluate(&policy, &context).unwrap();
+        assert!(!evaluation.is_compliant());
+
+        context.timestamp = now;
+        assert!(matches!(
+            evaluator.evaluate(&policy, &context),
+            Err(EvaluationError::PolicyNotActive(_))
+        ));
+    }
+
+    #[test]
+    fn test_evaluate_each_records_rule_errors_per_policy() {
+        let evaluator = PolicyEvaluator::new();
+        let failing = active_policy("Key Size", PolicyRule::min_key_size(2048));
+        let passing = active_policy("Validity", PolicyRule::max_validity_days(90));
+        let context = EvaluationContext::new().with_field("validity_days", 30);
+
+        let outcomes = evaluator.evaluate_each(&[&failing, &passing], &context);
+
+        assert!(matches!(outcomes[0].1, Err(EvaluationError::MissingContextField(_))));
+        assert!(outcomes[1].1.as_ref().unwrap().as_ref().unwrap().is_compliant());
+    }
+
+    #[test]
+    fn test_usage_limited_exemption_stops_applying_after_max_uses() {
+        let policy = active_policy("Validity", PolicyRule::max_validity_days(90));
+        let mut exemption = PolicyExemption::new(
+            policy.id,
+            "Break glass",
+            "Two emergency renewals",
+            "admin",
+            chrono::Utc::now() + chrono::Duration::days(30),
+        );
+        exemption.max_uses = Some(2);
+        let mut evaluator = PolicyEvaluator::new();
+        evaluator.register_exemptions(vec![exemption.clone()]);
+        let context = EvaluationContext::new().with_field("validity_days", 365);
+
+        // A request that already complies does not spend a use
+        let compliant = EvaluationContext::new().with_field("validity_days", 30);
+        assert_eq!(
+            evaluator.evaluate(&policy, &compliant).unwrap().overall_result,
+            ComplianceResult::Compliant
+        );
+        assert!(evaluator.take_exemption_events().is_empty());
+
+        for _ in 0..2 {
+            assert!(matches!(
+                evaluator.evaluate(&policy, &context).unwrap().overall_result,
+                ComplianceResult::CompliantWithExemption { .. }
+            ));
+        }
+        assert!(!evaluator.evaluate(&policy, &context).unwrap().is_compliant());
+
+        let events = evaluator.take_exemption_events();
+        let kinds: Vec<_> = events.iter().map(cim_domain::DomainEvent::event_type).collect();
+        assert_eq!(
+            kinds,
+            vec!["PolicyExemptionUsed", "PolicyExemptionUsed", "PolicyExemptionExhausted"]
+        );
+        let PolicyEvent::PolicyExemptionExhausted(exhausted) = &events[2] else {
+            panic!("expected PolicyExemptionExhausted, got {:?}", events[2]);
+        };
+        assert_eq!(exhausted.exemption_id, exemption.id);
+        assert_eq!(exhausted.uses_count, 2);
+        assert!(evaluator.take_exemption_events().is_empty());
+
+        // A fresh evaluator seeded from the recorded uses honours the limit
+        let used_once = exemption.apply_event_pure(&events[0]).unwrap();
+        assert_eq!(used_once.uses_count, 1);
+        let mut restarted = PolicyEvaluator::new();
+        restarted.register_exemptions(vec![used_once]);
+        assert!(restarted.evaluate(&policy, &context).unwrap().is_compliant());
+        assert!(!restarted.evaluate(&policy, &context).unwrap().is_compliant());
+
+        // Recording the events brings the aggregate up to date
+        let exemption = events
+            .iter()
+            .try_fold(exemption, |exemption, event| exemption.apply_event_pure(event))
+            .unwrap();
+        assert!(exemption.is_exhausted());
+        assert!(!exemption.is_valid_at(chrono::Utc::now()));
+    }
+
+    #[test]
+    fn test_explain_shows_failing_child_and_actual_value() {
+        let evaluator = PolicyEvaluator::new();
+        let mut policy = Policy::new("Crypto", "Key requirements");
+        policy.add_rule(PolicyRule::new(
+            "Strong keys",
+            "Keys must be large and use an approved algorithm",
+            RuleExpression::And(vec![
+                RuleExpression::In {
+                    field: "algorithm".to_string(),
+                    values: vec![Value::String("RSA".to_string()), Value::String("Ed25519".to_string())],
+                },
+                RuleExpression::GreaterThanOrEqual {
+                    field: "key_size".to_string(),
+                    value: Value::Integer(2048),
+                },
+                RuleExpression::Not(Box::new(RuleExpression::Exists {
+                    field: "revoked".to_string(),
+                })),
+            ]),
+            Severity::High,
+        ));
+        let context = EvaluationContext::new()
+            .with_field("algorithm", "RSA")
+            .with_field("key_size", 1024);
+
+        let trace = evaluator.explain(&policy, &context);
+        assert!(!trace.passed());
+        assert_eq!(trace.rules.len(), 1);
+
+        let and = &trace.rules[0].expression;
+        assert_eq!(and.operator, "And");
+        assert_eq!(and.result, Some(false));
+        assert!(and.fields.is_empty());
+        let results: Vec<_> = and.children.iter().map(|child| child.result).collect();
+        assert_eq!(results, vec![Some(true), Some(false), Some(true)]);
+
+        let failed = &and.children[1];
+        assert_eq!(failed.operator, "GreaterThanOrEqual");
+        assert_eq!(
+            failed.fields,
+            vec![("key_size".to_string(), Some(Value::Integer(1024)))]
+        );
+
+        let not = &and.children[2];
+        assert_eq!(not.children[0].operator, "Exists");
+        assert_eq!(not.children[0].fields, vec![("revoked".to_string(), None)]);
+        assert_eq!(not.children[0].result, Some(false));
+    }
+
+    #[test]
+    fn test_explain_records_evaluation_errors() {
+        let evaluator = PolicyEvaluator::new();
+        let policy = active_policy("Key Size", PolicyRule::min_key_size(2048));
+
+        let trace = evaluator.explain(&policy, &EvaluationContext::new());
+        let expression = &trace.rules[0].expression;
+        assert_eq!(expression.result, None);
+        assert_eq!(expression.fields, vec![("key_size".to_string(), None)]);
+        assert!(expression.error.as_deref().unwrap().contains("key_size"));
+        assert!(!trace.passed());
+    }
+
+    #[test]
+    fn test_explain_evaluates_each_leaf_once_and_agrees_with_evaluation() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut evaluator = PolicyEvaluator::new();
+        let counter = calls.clone();
+        evaluator.register_predicate("counted", move |_, _| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            false
+        });
+        let counted = || RuleExpression::Custom {
+            predicate: "counted".to_string(),
+            args: HashMap::new(),
+        };
+        let missing = RuleExpression::Exists { field: "missing".to_string() };
+        let unreadable = RuleExpression::Equal {
+            field: "missing".to_string(),
+            value: Value::Integer(1),
+        };
+
+        // Nested four deep, with an error that only the outer Or reaches
+        let expr = RuleExpression::Or(vec![
+            RuleExpression::And(vec![
+                RuleExpression::Not(Box::new(RuleExpression::Or(vec![counted(), missing.clone()]))),
+                counted(),
+            ]),
+            RuleExpression::Not(Box::new(counted())),
+            unreadable,
+        ]);
+        let context = EvaluationContext::new();
+
+        let mut policy = Policy::new("Nested", "Nested rule");
+        policy.add_rule(PolicyRule::new("Nested", "Nested rule", expr.clone(), Severity::Low));
+        let trace = evaluator.explain(&policy, &context);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let traced = &trace.rules[0].expression;
+        assert_eq!(traced.result, evaluator.evaluate_expression(&expr, &context).ok());
+        assert_eq!(traced.result, Some(true));
+        assert_eq!(traced.children[0].result, Some(false));
+
+        // A failing child ahead of the deciding one surfaces its error
+        let failing = RuleExpression::And(vec![
+            RuleExpression::Equal {
+                field: "missing".to_string(),
+                value: Value::Integer(1),
+            },
+            missing,
+        ]);
+        let mut policy = Policy::new("Failing", "Failing rule");
+        policy.add_rule(PolicyRule::new("Failing", "Failing rule", failing.clone(), Severity::Low));
+        let traced = &evaluator.explain(&policy, &context).rules[0].expression;
+        assert_eq!(traced.result, None);
+        assert_eq!(
+            traced.error,
+            evaluator.evaluate_expression(&failing, &context).err().map(|e| e.to_string())
+        );
+    }
+
+    #[test]
+    fn test_failed_rules_suggest_remediation() {
+        let evaluator = PolicyEvaluator::new();
+        let rule = |expression: RuleExpression| PolicyRule::new("Rule", "Test rule", expression, Severity::High);
+        let mut policy = active_policy(
+            "Payments",
+            rule(RuleExpression::LessThanOrEqual {
+                field: "amount".to_string(),
+                value: Value::Integer(10000),
+            }),
+        );
+        policy.add_rule(PolicyRule::min_key_size(2048));
+        policy.add_rule(rule(RuleExpression::In {
+            field: "region".to_string(),
+            values: vec![Value::String("eu".to_string()), Value::String("us".to_string())],
+        }));
+        policy.add_rule(rule(RuleExpression::Exists {
+            field: "approver".to_string(),
+        }));
+        let mut custom = rule(RuleExpression::Equal {
+            field: "mfa".to_string(),
+            value: Value::Bool(true),
+        });
+        custom.remediation_hint = Some("Enable {field} in the account settings (expected {value})".to_string());
+        policy.add_rule(custom);
+        policy.add_rule(rule(RuleExpression::MatchesRegex {
+            field: "name".to_string(),
+            pattern: "^svc-".to_string(),
+        }));
+
+        let context = EvaluationContext::new()
+            .with_field("amount", 25000)
+            .with_field("key_size", 1024)
+            .with_field("region", "apac")
+            .with_field("mfa", false)
+            .with_field("name", "billing");
+        let evaluation = evaluator.evaluate(&policy, &context).unwrap();
+        let remediations: Vec<_> = evaluation
+            .rule_results
+            .iter()
+            .map(|result| result.to_violation().suggested_remediation)
+            .collect();
+
+        assert_eq!(
+            remediations,
+            vec![
+                Some("set 'amount' to at most 10000".to_string()),
+                Some("set 'key_size' to at least 2048".to_string()),
+                Some("set 'region' to one of 'eu', 'us'".to_string()),
+                Some("provide 'approver'".to_string()),
+                Some("Enable mfa in the account settings (expected true)".to_string()),
+                None,
+            ]
+        );
+
+        let passing = context.with_field("amount", 50);
+        let evaluation = evaluator.evaluate(&policy, &passing).unwrap();
+        assert_eq!(evaluation.rule_results[0].suggested_remediation, None);
+    }
+
+    fn conditional_exemption(policy: &Policy, condition: crate::aggregate::ExemptionCondition) -> PolicyExemption {
+        let mut exemption = PolicyExemption::new(
+            policy.id,
+            "Certificate migration",
+            "Old certificates are replaced on expiry",
+            "admin",
+            chrono::Utc::now() + chrono::Duration::days(30),
+        );
+        exemption.conditions.push(condition);
+        exemption
+    }
+
+    #[test]
+    fn test_exemption_condition_on_datetime() {
+        let cutoff = "2025-01-01T00:00:00Z".parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+        let policy = active_policy("Validity", PolicyRule::max_validity_days(90));
+        let mut evaluator = PolicyEvaluator::new();
+        evaluator.register_exemptions(vec![conditional_exemption(&policy, crate::aggregate::ExemptionCondition {
+            field: "expiry".to_string(),
+            operator: crate::aggregate::ConditionOperator::LessThan,
+            value: Value::DateTime(cutoff),
+        })]);
+
+        let expiring = EvaluationContext::new()
+            .with_field("validity_days", 365)
+            .with_field("expiry", Value::DateTime(cutoff - chrono::Duration::days(10)));
+        assert!(matches!(
+            evaluator.evaluate(&policy, &expiring).unwrap().overall_result,
+            ComplianceResult::CompliantWithExemption { .. }
+        ));
+
+        let long_lived = EvaluationContext::new()
+            .with_field("validity_days", 365)
+            .with_field("expiry", Value::DateTime(cutoff + chrono::Duration::days(10)));
+        assert!(!evaluator.evaluate(&policy, &long_lived).unwrap().is_compliant());
+    }
+
+    #[test]
+    fn test_datetime_exemption_condition_survives_replay() {
+        use crate::events::{PolicyEvent, PolicyExemptionGranted};
+
+        let cutoff = "2025-01-01T00:00:00Z".parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+        let policy = active_policy("Validity", PolicyRule::max_validity_days(90));
+        let granted = PolicyExemptionGranted {
+            event_id: uuid::Uuid::now_v7(),
+            identity: crate::sagas::create_root_command(),
+            exemption_id: crate::value_objects::ExemptionId::new(),
+            policy_id: policy.id,
+            granted_by: "admin".to_string(),
+            granted_at: chrono::Utc::now(),
+            reason: "Certificate migration".to_string(),
+            justification: "Old certificates are replaced on expiry".to_string(),
+            valid_until: chrono::Utc::now() + chrono::Duration::days(30),
+            risk_acceptance: None,
+            scope: crate::aggregate::ExemptionScope::Global,
+            conditions: vec![crate::aggregate::ExemptionCondition {
+                field: "expiry".to_string(),
+                operator: crate::aggregate::ConditionOperator::LessThan,
+                value: Value::DateTime(cutoff),
+            }],
+            max_uses: None,
+        };
+
+        let stored = serde_json::to_string(&PolicyEvent::PolicyExemptionGranted(granted.clone())).unwrap();
+        let replayed: PolicyEvent = serde_json::from_str(&stored).unwrap();
+        let exemption = PolicyExemption::new(policy.id, "", "", "", granted.valid_until)
+            .apply_event_pure(&replayed)
+            .unwrap();
+        assert_eq!(exemption.conditions, granted.conditions);
+
+        let context = EvaluationContext::new()
+            .with_field("validity_days", 365)
+            .with_field("expiry", Value::DateTime(cutoff - chrono::Duration::days(10)));
+        assert!(PolicyEvaluator::new().exemption_applies(&exemption, &context));
+    }
+
+    #[test]
+    fn test_exemption_condition_between_range() {
+        let evaluator = PolicyEvaluator::new();
+        let policy = active_policy("Validity", PolicyRule::max_validity_days(90));
+        let exemption = conditional_exemption(&policy, crate::aggregate::ExemptionCondition {
+            field: "risk_score".to_string(),
+            operator: crate::aggregate::ConditionOperator::Between,
+            value: Value::List(vec![Value::Float(0.0), Value::Integer(5)]),
+        });
+
+        let score = |value: f64| EvaluationContext::new().with_field("risk_score", value);
+        assert!(evaluator.evaluate_condition(&exemption.conditions[0], &score(2.5)));
+        assert!(evaluator.evaluate_condition(&exemption.conditions[0], &score(5.0)));
+        assert!(!evaluator.evaluate_condition(&exemption.conditions[0], &score(7.5)));
+    }
+}
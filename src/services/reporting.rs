@@ -0,0 +1,351 @@
+//! Compliance reporting over evaluation results
+
+use crate::aggregate::Policy;
+use crate::entities::PolicyEvaluation;
+use crate::value_objects::*;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+/// Number of rules listed in `ComplianceReport::top_violated_rules`
+const TOP_VIOLATED_RULES: usize = 10;
+
+/// Number of detail strings kept per `GroupedViolation`
+const SAMPLE_DETAILS: usize = 3;
+
+/// Compliance status of the policies tagged with one framework
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrameworkStatus {
+    /// Policies tagged with the framework
+    pub policies: usize,
+    /// Evaluations of those policies that were compliant (including exemptions)
+    pub compliant: usize,
+    /// Evaluations of those policies that were not compliant
+    pub non_compliant: usize,
+    /// Most severe violation seen, if any
+    pub worst_severity: Option<Severity>,
+}
+
+/// Summarize evaluations per compliance framework (SOC2, GDPR, PCI-DSS, ...)
+///
+/// Frameworks come from each policy's `metadata.compliance_standards`. A
+/// policy tagged with several frameworks counts towards each of them, and
+/// evaluations of untagged or unknown policies are ignored.
+pub fn compliance_report_by_framework(
+    evaluations: &[PolicyEvaluation],
+    policies: &[Policy],
+) -> HashMap<String, FrameworkStatus> {
+    let mut report: HashMap<String, FrameworkStatus> = HashMap::new();
+    let mut frameworks: HashMap<PolicyId, &[String]> = HashMap::new();
+
+    for policy in policies {
+        frameworks.insert(policy.id, &policy.metadata.compliance_standards);
+        for framework in &policy.metadata.compliance_standards {
+            report.entry(framework.clone()).or_default().policies += 1;
+        }
+    }
+
+    for evaluation in evaluations {
+        let Some(tags) = frameworks.get(&evaluation.policy_id) else {
+            continue;
+        };
+        let worst = evaluation.violations().iter().map(|v| v.severity).max();
+
+        for framework in tags.iter() {
+            let status = report.entry(framework.clone()).or_default();
+            if evaluation.is_compliant() {
+                status.compliant += 1;
+            } else {
+                status.non_compliant += 1;
+            }
+            status.worst_severity = status.worst_severity.max(worst);
+        }
+    }
+
+    report
+}
+
+/// Time window a report covers (start inclusive, end exclusive)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportPeriod {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl ReportPeriod {
+    /// Whether a timestamp falls within the period
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        self.start <= at && at < self.end
+    }
+}
+
+/// How often a rule was violated within a report period
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleViolationCount {
+    pub rule_id: Uuid,
+    pub rule_description: String,
+    pub count: usize,
+}
+
+/// How often an exemption excused an evaluation within a report period
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExemptionUsage {
+    pub exemption_id: ExemptionId,
+    pub count: usize,
+}
+
+/// Exportable summary of enforcement over a period
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComplianceReport {
+    pub generated_at: DateTime<Utc>,
+    pub period: ReportPeriod,
+    pub total_evaluations: usize,
+    pub violations_by_severity: BTreeMap<Severity, usize>,
+    /// Most frequently violated rules, most violations first
+    pub top_violated_rules: Vec<RuleViolationCount>,
+    pub exemptions_used: Vec<ExemptionUsage>,
+}
+
+/// Build a compliance report from the evaluations recorded in a period
+///
+/// Evaluations outside `period` are ignored. Ties in the rule ranking are
+/// broken by first occurrence, so the report is deterministic.
+pub fn generate_compliance_report(
+    evaluations: &[PolicyEvaluation],
+    period: ReportPeriod,
+    generated_at: DateTime<Utc>,
+) -> ComplianceReport {
+    let mut total_evaluations = 0;
+    let mut violations_by_severity = BTreeMap::new();
+    let mut rules: Vec<RuleViolationCount> = Vec::new();
+    let mut exemptions_used: Vec<ExemptionUsage> = Vec::new();
+
+    for evaluation in evaluations.iter().filter(|e| period.contains(e.evaluated_at)) {
+        total_evaluations += 1;
+
+        if let ComplianceResult::CompliantWithExemption { exemption_id } = evaluation.overall_result {
+            match exemptions_used.iter_mut().find(|u| u.exemption_id == exemption_id) {
+                Some(usage) => usage.count += 1,
+                None => exemptions_used.push(ExemptionUsage { exemption_id, count: 1 }),
+            }
+        }
+
+        for violation in evaluation.violations() {
+            *violations_by_severity.entry(violation.severity).or_insert(0) += 1;
+            match rules.iter_mut().find(|r| r.rule_id == violation.rule_id) {
+                Some(rule) => rule.count += 1,
+                None => rules.push(RuleViolationCount {
+                    rule_id: violation.rule_id,
+                    rule_description: violation.rule_description,
+                    count: 1,
+                }),
+            }
+        }
+    }
+
+    rules.sort_by_key(|r| std::cmp::Reverse(r.count));
+    rules.truncate(TOP_VIOLATED_RULES);
+
+    ComplianceReport {
+        generated_at,
+        period,
+        total_evaluations,
+        violations_by_severity,
+        top_violated_rules: rules,
+        exemptions_used,
+    }
+}
+
+/// Violations of one rule, collapsed into a single report line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupedViolation {
+    pub rule_id: Uuid,
+    pub rule_description: String,
+    pub severity: Severity,
+    pub count: usize,
+    /// Up to three distinct details, in order of first occurrence
+    pub sample_details: Vec<String>,
+}
+
+/// Group violations by rule, most severe first and then most frequent
+///
+/// A rule reported with several severities is listed at the highest one.
+pub fn group_violations(violations: &[Violation]) -> Vec<GroupedViolation> {
+    let mut groups: Vec<GroupedViolation> = Vec::new();
+
+    for violation in violations {
+        let group = match groups.iter_mut().position(|g| g.rule_id == violation.rule_id) {
+            Some(index) => &mut groups[index],
+            None => {
+                groups.push(GroupedViolation {
+                    rule_id: violation.rule_id,
+                    rule_description: violation.rule_description.clone(),
+                    severity: violation.severity,
+                    count: 0,
+                    sample_details: Vec::new(),
+                });
+                groups.last_mut().expect("just pushed")
+            }
+        };
+
+        group.count += 1;
+        group.severity = group.severity.max(violation.severity);
+        if group.sample_details.len() < SAMPLE_DETAILS
+            && !group.sample_details.contains(&violation.details)
+        {
+            group.sample_details.push(violation.details.clone());
+        }
+    }
+
+    groups.sort_by_key(|g| std::cmp::Reverse((g.severity, g.count)));
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::PolicyRule;
+    use crate::services::PolicyEvaluator;
+
+    fn tagged_policy(rule: PolicyRule, frameworks: &[&str]) -> Policy {
+        let mut policy = Policy::new(rule.name.clone(), "Test policy");
+        policy.status = PolicyStatus::Active;
+        policy.metadata.compliance_standards = frameworks.iter().map(|f| f.to_string()).collect();
+        policy.add_rule(rule);
+        policy
+    }
+
+    #[test]
+    fn test_report_groups_policies_by_framework() {
+        let key_size = tagged_policy(PolicyRule::min_key_size(2048), &["SOC2", "PCI-DSS"]);
+        let mut validity = PolicyRule::max_validity_days(90);
+        validity.severity = Severity::Medium;
+        let validity = tagged_policy(validity, &["GDPR", "SOC2"]);
+        let untagged = tagged_policy(PolicyRule::min_key_size(1024), &[]);
+
+        let evaluator = PolicyEvaluator::new();
+        let weak = EvaluationContext::new()
+            .with_field("key_size", 1024)
+            .with_field("validity_days", 30);
+        let long_lived = EvaluationContext::new()
+            .with_field("key_size", 4096)
+            .with_field("validity_days", 365);
+        let evaluations = vec![
+            evaluator.evaluate(&key_size, &weak).unwrap(),
+            evaluator.evaluate(&key_size, &long_lived).unwrap(),
+            evaluator.evaluate(&validity, &weak).unwrap(),
+            evaluator.evaluate(&validity, &long_lived).unwrap(),
+            evaluator.evaluate(&untagged, &weak).unwrap(),
+        ];
+
+        let report = compliance_report_by_framework(&evaluations, &[key_size, validity, untagged]);
+
+        assert_eq!(report.len(), 3);
+        let soc2 = &report["SOC2"];
+        assert_eq!((soc2.policies, soc2.compliant, soc2.non_compliant), (2, 2, 2));
+        assert_eq!(soc2.worst_severity, Some(PolicyRule::min_key_size(2048).severity));
+
+        let pci = &report["PCI-DSS"];
+        assert_eq!((pci.policies, pci.compliant, pci.non_compliant), (1, 1, 1));
+
+        let gdpr = &report["GDPR"];
+        assert_eq!((gdpr.policies, gdpr.compliant, gdpr.non_compliant), (1, 1, 1));
+        assert_eq!(gdpr.worst_severity, Some(Severity::Medium));
+    }
+
+    #[test]
+    fn test_report_aggregates_violations_in_period() {
+        use crate::entities::RuleResult;
+
+        let now = Utc::now();
+        let period = ReportPeriod { start: now - chrono::Duration::days(7), end: now };
+        let policy_id = PolicyId::new();
+        let failing = |rule_id: Uuid, name: &str, severity: Severity| RuleResult {
+            rule_id,
+            rule_name: name.to_string(),
+            passed: false,
+            message: format!("{} failed", name),
+            severity,
+            actual_value: None,
+            expected_value: None,
+            execution_time_us: 0,
+            suggested_remediation: None,
+        };
+        let evaluation = |at: DateTime<Utc>, results: Vec<RuleResult>| {
+            let mut evaluation = PolicyEvaluation::new(policy_id, EvaluationContext::new());
+            evaluation.evaluated_at = at;
+            for result in results {
+                evaluation.add_rule_result(result);
+            }
+            evaluation
+        };
+
+        let (key_size, validity) = (Uuid::now_v7(), Uuid::now_v7());
+        let exemption_id = ExemptionId::new();
+        let mut exempted = evaluation(now - chrono::Duration::hours(1), vec![]);
+        exempted.overall_result = ComplianceResult::CompliantWithExemption { exemption_id };
+
+        let evaluations = vec![
+            evaluation(now - chrono::Duration::days(1), vec![
+                failing(validity, "Validity", Severity::Medium),
+            ]),
+            evaluation(now - chrono::Duration::days(2), vec![
+                failing(key_size, "Key size", Severity::High),
+                failing(validity, "Validity", Severity::Medium),
+            ]),
+            evaluation(now - chrono::Duration::days(3), vec![]),
+            exempted,
+            // Outside the period
+            evaluation(now - chrono::Duration::days(30), vec![
+                failing(key_size, "Key size", Severity::High),
+            ]),
+        ];
+
+        let report = generate_compliance_report(&evaluations, period, now);
+
+        assert_eq!(report.total_evaluations, 4);
+        assert_eq!(
+            report.violations_by_severity,
+            BTreeMap::from([(Severity::Medium, 2), (Severity::High, 1)])
+        );
+        assert_eq!(report.top_violated_rules.len(), 2);
+        assert_eq!(report.top_violated_rules[0].rule_id, validity);
+        assert_eq!(report.top_violated_rules[0].count, 2);
+        assert_eq!(report.top_violated_rules[1].rule_id, key_size);
+        assert_eq!(report.exemptions_used, vec![ExemptionUsage { exemption_id, count: 1 }]);
+
+        let exported = serde_json::to_string(&report).unwrap();
+        let imported: ComplianceReport = serde_json::from_str(&exported).unwrap();
+        assert_eq!(imported, report);
+    }
+
+    #[test]
+    fn test_group_violations_by_rule() {
+        let violation = |rule_id: Uuid, severity: Severity, details: &str| Violation {
+            rule_id,
+            rule_description: format!("Rule {}", rule_id),
+            severity,
+            details: details.to_string(),
+            suggested_remediation: None,
+        };
+        let (noisy, rare, critical) = (Uuid::now_v7(), Uuid::now_v7(), Uuid::now_v7());
+
+        let mut violations = vec![violation(rare, Severity::Medium, "once")];
+        for i in 0..5 {
+            violations.push(violation(noisy, Severity::Medium, &format!("host-{}", i % 4)));
+        }
+        violations.push(violation(critical, Severity::Critical, "root key exposed"));
+        violations.push(violation(critical, Severity::Critical, "root key exposed"));
+
+        let groups = group_violations(&violations);
+
+        assert_eq!(
+            groups.iter().map(|g| (g.rule_id, g.count)).collect::<Vec<_>>(),
+            vec![(critical, 2), (noisy, 5), (rare, 1)]
+        );
+        assert_eq!(groups[0].sample_details, vec!["root key exposed"]);
+        assert_eq!(groups[1].sample_details, vec!["host-0", "host-1", "host-2"]);
+        assert_eq!(groups[1].severity, Severity::Medium);
+    }
+}
@@ -0,0 +1,82 @@
+//! Policy selection - which policies apply to a request
+
+use crate::aggregate::Policy;
+use crate::value_objects::EvaluationContext;
+
+/// Select the policies that apply to a request
+///
+/// A policy applies when it is effective at the context's timestamp (active
+/// and within its date window) and its target matches the context. Input
+/// order is preserved, so the result can be passed straight to
+/// `PolicyEvaluator::evaluate_set`.
+pub fn select_applicable<'a>(
+    policies: &'a [Policy],
+    context: &EvaluationContext,
+) -> Vec<&'a Policy> {
+    policies
+        .iter()
+        .filter(|policy| {
+            policy.is_effective_at(context.timestamp) && policy.target.matches(context)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::*;
+    use chrono::Duration;
+    use uuid::Uuid;
+
+    fn active(name: &str, target: PolicyTarget) -> Policy {
+        let mut policy = Policy::new(name, "Selection test policy");
+        policy.status = PolicyStatus::Active;
+        policy.target = target;
+        policy
+    }
+
+    #[test]
+    fn test_selects_only_overlapping_effective_policies() {
+        let org = Uuid::now_v7();
+        let context = EvaluationContext::new()
+            .with_field("organization", org.to_string())
+            .with_field("role", "sre")
+            .with_field("resource", "Key");
+
+        let global = active("Global", PolicyTarget::Global);
+        let own_org = active("Own org", PolicyTarget::Organization(org));
+        let other_org = active("Other org", PolicyTarget::Organization(Uuid::now_v7()));
+        let sre = active("SRE", PolicyTarget::Role("sre".to_string()));
+        let admin = active("Admin", PolicyTarget::Role("admin".to_string()));
+        let keys = active("Keys", PolicyTarget::Resource(ResourceType::Key));
+        let certificates = active(
+            "Certificates",
+            PolicyTarget::Resource(ResourceType::Certificate),
+        );
+        let mut draft = active("Draft", PolicyTarget::Global);
+        draft.status = PolicyStatus::Draft;
+        let mut expired = active("Expired", PolicyTarget::Role("sre".to_string()));
+        expired.expiry_date = Some(context.timestamp - Duration::days(1));
+        let mut future = active("Future", PolicyTarget::Resource(ResourceType::Key));
+        future.effective_date = Some(context.timestamp + Duration::days(1));
+
+        let policies = vec![
+            global,
+            own_org,
+            other_org,
+            sre,
+            admin,
+            keys,
+            certificates,
+            draft,
+            expired,
+            future,
+        ];
+        let selected: Vec<&str> = select_applicable(&policies, &context)
+            .into_iter()
+            .map(|policy| policy.name.as_str())
+            .collect();
+
+        assert_eq!(selected, vec!["Global", "Own org", "SRE", "Keys"]);
+    }
+}
@@ -0,0 +1,156 @@
+//! Policy simulation service
+
+use super::PolicyEvaluator;
+use crate::aggregate::{Policy, PolicyExemption};
+use crate::value_objects::*;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Impact of running a policy against a corpus of evaluation contexts
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulationReport {
+    pub total: usize,
+    pub blocked: usize,
+    pub allowed: usize,
+    pub exempted: usize,
+    /// Contexts that could not be evaluated (e.g. missing fields)
+    pub errors: usize,
+    pub violations_by_severity: BTreeMap<Severity, usize>,
+}
+
+impl SimulationReport {
+    /// Combine two partial reports
+    fn merge(mut self, other: SimulationReport) -> SimulationReport {
+        self.total += other.total;
+        self.blocked += other.blocked;
+        self.allowed += other.allowed;
+        self.exempted += other.exempted;
+        self.errors += other.errors;
+        for (severity, count) in other.violations_by_severity {
+            *self.violations_by_severity.entry(severity).or_insert(0) += count;
+        }
+        self
+    }
+}
+
+/// Service for running what-if simulations of a policy before it is activated
+pub struct PolicySimulator {
+    evaluator: PolicyEvaluator,
+}
+
+impl PolicySimulator {
+    /// Create a new simulator
+    pub fn new() -> Self {
+        Self {
+            evaluator: PolicyEvaluator::new(),
+        }
+    }
+
+    /// Register exemptions for consideration during simulation
+    pub fn register_exemptions(&mut self, exemptions: Vec<PolicyExemption>) {
+        self.evaluator.register_exemptions(exemptions);
+    }
+
+    /// Simulate a policy against historical evaluation contexts
+    ///
+    /// The policy's status is ignored so drafts can be simulated before
+    /// activation. Contexts are evaluated in parallel.
+    pub fn simulate(&self, policy: &Policy, contexts: &[EvaluationContext]) -> SimulationReport {
+        contexts
+            .par_iter()
+            .map(|context| self.simulate_one(policy, context))
+            .reduce(SimulationReport::default, SimulationReport::merge)
+    }
+
+    /// Simulate a single context
+    fn simulate_one(&self, policy: &Policy, context: &EvaluationContext) -> SimulationReport {
+        let mut report = SimulationReport {
+            total: 1,
+            ..SimulationReport::default()
+        };
+
+        match self.evaluator.evaluate_rules(policy, context) {
+            Ok(evaluation) => match evaluation.overall_result {
+                ComplianceResult::CompliantWithExemption { .. } => report.exempted = 1,
+                _ if evaluation.is_compliant() => report.allowed = 1,
+                _ => {
+                    report.blocked = 1;
+                    for violation in evaluation.violations() {
+                        *report.violations_by_severity.entry(violation.severity).or_insert(0) += 1;
+                    }
+                }
+            },
+            Err(_) => report.errors = 1,
+        }
+
+        report
+    }
+}
+
+impl Default for PolicySimulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::PolicyRule;
+    use chrono::{Duration, Utc};
+
+    fn draft_policy() -> Policy {
+        let mut policy = Policy::new("Certificates", "Certificate requirements");
+        policy.add_rule(PolicyRule::min_key_size(2048));
+        policy.add_rule(PolicyRule::max_validity_days(90));
+        policy
+    }
+
+    fn context(key_size: i64, validity_days: i64) -> EvaluationContext {
+        EvaluationContext::new()
+            .with_field("key_size", key_size)
+            .with_field("validity_days", validity_days)
+    }
+
+    #[test]
+    fn test_simulate_counts_outcomes_and_severities() {
+        let simulator = PolicySimulator::new();
+        let policy = draft_policy();
+        let contexts = vec![
+            context(4096, 30),
+            context(4096, 60),
+            context(1024, 30),
+            context(1024, 365),
+            EvaluationContext::new(),
+        ];
+
+        let report = simulator.simulate(&policy, &contexts);
+        assert_eq!(report.total, 5);
+        assert_eq!(report.allowed, 2);
+        assert_eq!(report.blocked, 2);
+        assert_eq!(report.exempted, 0);
+        assert_eq!(report.errors, 1);
+        assert_eq!(report.violations_by_severity.get(&Severity::Critical), Some(&2));
+        assert_eq!(report.violations_by_severity.get(&Severity::High), Some(&1));
+    }
+
+    #[test]
+    fn test_simulate_applies_registered_exemptions() {
+        let mut simulator = PolicySimulator::new();
+        let policy = draft_policy();
+        simulator.register_exemptions(vec![PolicyExemption::new(
+            policy.id,
+            "Legacy devices",
+            "Hardware cannot be upgraded",
+            "security-admin",
+            Utc::now() + Duration::days(30),
+        )]);
+
+        let report = simulator.simulate(&policy, &[context(1024, 365), context(4096, 30)]);
+        assert_eq!(report.total, 2);
+        assert_eq!(report.exempted, 2);
+        assert_eq!(report.blocked, 0);
+        assert!(report.violations_by_severity.is_empty());
+    }
+}
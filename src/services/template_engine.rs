@@ -2,6 +2,10 @@
 
 use crate::aggregate::Policy;
 use crate::entities::{PolicyTemplate, TemplateParameter, ParameterType, PolicyRule};
+use crate::events::{PolicyEvent, PolicyInstantiatedFromTemplate};
+use crate::infrastructure::TemplateRepository;
+use crate::sagas::create_root_command;
+use crate::services::PolicyEvaluator;
 use crate::value_objects::*;
 use std::collections::HashMap;
 use thiserror::Error;
@@ -44,6 +48,22 @@ impl PolicyTemplateEngine {
         engine
     }
 
+    /// Create a template engine with the built-in templates plus the given ones
+    pub fn with_templates(templates: impl IntoIterator<Item = PolicyTemplate>) -> Self {
+        let mut engine = Self::new();
+        for template in templates {
+            engine.register_template(template);
+        }
+        engine
+    }
+
+    /// Create a template engine hydrated from persisted templates
+    pub async fn load_from(
+        repository: &TemplateRepository,
+    ) -> Result<Self, crate::infrastructure::template_repository::RepositoryError> {
+        Ok(Self::with_templates(repository.list().await?))
+    }
+
     /// Register built-in templates
     fn register_builtin_templates(&mut self) {
         // PKI Certificate Template
@@ -58,6 +78,9 @@ impl PolicyTemplateEngine {
             parameter_type: ParameterType::Integer,
             default_value: Some(Value::Integer(2048)),
             required: false,
+            min_length: None,
+            max_length: None,
+            allowed_values: None,
             validation: Some(RuleExpression::GreaterThanOrEqual {
                 field: "value".to_string(),
                 value: Value::Integer(1024),
@@ -69,6 +92,9 @@ impl PolicyTemplateEngine {
             parameter_type: ParameterType::Integer,
             default_value: Some(Value::Integer(365)),
             required: false,
+            min_length: None,
+            max_length: None,
+            allowed_values: None,
             validation: Some(RuleExpression::LessThanOrEqual {
                 field: "value".to_string(),
                 value: Value::Integer(825),
@@ -83,6 +109,9 @@ impl PolicyTemplateEngine {
                 Value::String("ECDSA".to_string()),
             ])),
             required: false,
+            min_length: None,
+            max_length: None,
+            allowed_values: None,
             validation: None,
         });
         pki_template.tags = vec!["pki".to_string(), "certificate".to_string()];
@@ -100,6 +129,9 @@ impl PolicyTemplateEngine {
             parameter_type: ParameterType::String,
             default_value: None,
             required: true,
+            min_length: None,
+            max_length: None,
+            allowed_values: None,
             validation: None,
         });
         auth_template.add_parameter(TemplateParameter {
@@ -108,6 +140,9 @@ impl PolicyTemplateEngine {
             parameter_type: ParameterType::Integer,
             default_value: Some(Value::Integer(1)),
             required: false,
+            min_length: None,
+            max_length: None,
+            allowed_values: None,
             validation: Some(RuleExpression::GreaterThanOrEqual {
                 field: "value".to_string(),
                 value: Value::Integer(0),
@@ -128,6 +163,9 @@ impl PolicyTemplateEngine {
             parameter_type: ParameterType::String,
             default_value: None,
             required: true,
+            min_length: None,
+            max_length: None,
+            allowed_values: None,
             validation: None,
         });
         compliance_template.add_parameter(TemplateParameter {
@@ -136,6 +174,9 @@ impl PolicyTemplateEngine {
             parameter_type: ParameterType::Integer,
             default_value: Some(Value::Integer(90)),
             required: false,
+            min_length: None,
+            max_length: None,
+            allowed_values: None,
             validation: Some(RuleExpression::GreaterThan {
                 field: "value".to_string(),
                 value: Value::Integer(0),
@@ -145,12 +186,21 @@ impl PolicyTemplateEngine {
         self.register_template(compliance_template);
     }
 
-    /// Register a template
+    /// Register a template, replacing any previous version with the same id
     pub fn register_template(&mut self, template: PolicyTemplate) {
+        if let Some(previous) = self.templates.get(&template.id) {
+            self.template_registry.remove(&previous.name);
+        }
         self.template_registry.insert(template.name.clone(), template.id);
         self.templates.insert(template.id, template);
     }
 
+    /// Remove a template by name
+    pub fn remove_template(&mut self, name: &str) -> Option<PolicyTemplate> {
+        let id = self.template_registry.remove(name)?;
+        self.templates.remove(&id)
+    }
+
     /// Get a template by name
     pub fn get_template(&self, name: &str) -> Option<&PolicyTemplate> {
         self.template_registry.get(name)
@@ -184,6 +234,19 @@ impl PolicyTemplateEngine {
         policy_name: String,
         policy_description: String,
     ) -> Result<Policy, TemplateError> {
+        self.instantiate_with_event(template_name, parameters, policy_name, policy_description)
+            .map(|(policy, _)| policy)
+    }
+
+    /// Instantiate a policy from a template, along with the event recording
+    /// which template and resolved parameters were used
+    pub fn instantiate_with_event(
+        &self,
+        template_name: &str,
+        parameters: HashMap<String, Value>,
+        policy_name: String,
+        policy_description: String,
+    ) -> Result<(Policy, PolicyEvent), TemplateError> {
         // Get the template
         let template = self.get_template(template_name)
             .ok_or_else(|| TemplateError::TemplateNotFound(template_name.to_string()))?;
@@ -206,7 +269,17 @@ impl PolicyTemplateEngine {
         // Add template tags to policy
         policy.metadata.tags = template.tags.clone();
 
-        Ok(policy)
+        let event = PolicyEvent::PolicyInstantiatedFromTemplate(PolicyInstantiatedFromTemplate {
+            event_id: Uuid::now_v7(),
+            identity: create_root_command(),
+            policy_id: policy.id,
+            template_id: template.id,
+            template_name: template.name.clone(),
+            parameters: final_parameters,
+            instantiated_at: chrono::Utc::now(),
+        });
+
+        Ok((policy, event))
     }
 
     /// Validate and merge parameters with defaults
@@ -227,31 +300,23 @@ impl PolicyTemplateEngine {
                     ));
                 }
 
+                self.validate_parameter_constraints(param, provided_value)?;
+
                 // Validate against rule if present
                 if let Some(validation) = &param.validation {
-                    let _context = EvaluationContext::new()
+                    let context = EvaluationContext::new()
                         .with_field("value", provided_value.clone());
 
-                    // Simple validation check (in production would use PolicyEvaluator)
-                    // For now, just check basic constraints
-                    match validation {
-                        RuleExpression::GreaterThanOrEqual { field, value } if field == "value" => {
-                            if !self.compare_values(provided_value, value, std::cmp::Ordering::Greater)
-                                && !self.compare_values(provided_value, value, std::cmp::Ordering::Equal) {
-                                return Err(TemplateError::ValidationFailed(
-                                    format!("{} must be >= {:?}", param.name, value)
-                                ));
-                            }
-                        }
-                        RuleExpression::LessThanOrEqual { field, value } if field == "value" => {
-                            if !self.compare_values(provided_value, value, std::cmp::Ordering::Less)
-                                && !self.compare_values(provided_value, value, std::cmp::Ordering::Equal) {
-                                return Err(TemplateError::ValidationFailed(
-                                    format!("{} must be <= {:?}", param.name, value)
-                                ));
-                            }
-                        }
-                        _ => {}
+                    let passed = PolicyEvaluator::new()
+                        .evaluate_expression(validation, &context)
+                        .map_err(|e| TemplateError::ValidationFailed(
+                            format!("{}: {}", param.name, e)
+                        ))?;
+
+                    if !passed {
+                        return Err(TemplateError::ValidationFailed(
+                            format!("{} does not satisfy {:?}", param.name, validation)
+                        ));
                     }
                 }
 
@@ -270,6 +335,47 @@ impl PolicyTemplateEngine {
         Ok(final_parameters)
     }
 
+    /// Validate length and allowed-value constraints of a provided parameter
+    fn validate_parameter_constraints(
+        &self,
+        param: &TemplateParameter,
+        value: &Value,
+    ) -> Result<(), TemplateError> {
+        if let Value::String(s) = value {
+            let length = s.chars().count();
+
+            if param.required && s.trim().is_empty() {
+                return Err(TemplateError::ValidationFailed(
+                    format!("{} is required and must not be empty", param.name)
+                ));
+            }
+            if let Some(min) = param.min_length {
+                if length < min {
+                    return Err(TemplateError::ValidationFailed(
+                        format!("{} must be at least {} characters, got {}", param.name, min, length)
+                    ));
+                }
+            }
+            if let Some(max) = param.max_length {
+                if length > max {
+                    return Err(TemplateError::ValidationFailed(
+                        format!("{} must be at most {} characters, got {}", param.name, max, length)
+                    ));
+                }
+            }
+        }
+
+        if let Some(allowed) = &param.allowed_values {
+            if !allowed.contains(value) {
+                return Err(TemplateError::ValidationFailed(
+                    format!("{} must be one of {:?}, got {:?}", param.name, allowed, value)
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Validate parameter type
     fn validate_parameter_type(&self, value: &Value, expected_type: &ParameterType) -> bool {
         match (value, expected_type) {
@@ -288,17 +394,6 @@ impl PolicyTemplateEngine {
         }
     }
 
-    /// Compare values for validation
-    fn compare_values(&self, a: &Value, b: &Value, expected: std::cmp::Ordering) -> bool {
-        match (a, b) {
-            (Value::Integer(x), Value::Integer(y)) => x.cmp(y) == expected,
-            (Value::Float(x), Value::Float(y)) => {
-                x.partial_cmp(y).map_or(false, |ord| ord == expected)
-            }
-            _ => false,
-        }
-    }
-
     /// Apply parameters to a rule
     fn apply_parameters_to_rule(
         &self,
@@ -325,12 +420,23 @@ impl PolicyTemplateEngine {
         Ok(new_rule)
     }
 
-    /// Substitute parameters in an expression
+    /// Substitute parameters in an expression, recursing into every variant
     fn substitute_expression(
         &self,
         expr: &RuleExpression,
         parameters: &HashMap<String, Value>,
     ) -> Result<RuleExpression, TemplateError> {
+        let substitute_all = |exprs: &[RuleExpression]| {
+            exprs.iter()
+                .map(|e| self.substitute_expression(e, parameters))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let substitute_values = |values: &[Value]| {
+            values.iter()
+                .map(|v| self.substitute_value(v, parameters))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
         match expr {
             RuleExpression::Equal { field, value } => {
                 Ok(RuleExpression::Equal {
@@ -338,27 +444,125 @@ impl PolicyTemplateEngine {
                     value: self.substitute_value(value, parameters)?,
                 })
             }
+            RuleExpression::NotEqual { field, value } => {
+                Ok(RuleExpression::NotEqual {
+                    field: field.clone(),
+                    value: self.substitute_value(value, parameters)?,
+                })
+            }
+            RuleExpression::GreaterThan { field, value } => {
+                Ok(RuleExpression::GreaterThan {
+                    field: field.clone(),
+                    value: self.substitute_value(value, parameters)?,
+                })
+            }
             RuleExpression::GreaterThanOrEqual { field, value } => {
                 Ok(RuleExpression::GreaterThanOrEqual {
                     field: field.clone(),
                     value: self.substitute_value(value, parameters)?,
                 })
             }
+            RuleExpression::LessThan { field, value } => {
+                Ok(RuleExpression::LessThan {
+                    field: field.clone(),
+                    value: self.substitute_value(value, parameters)?,
+                })
+            }
             RuleExpression::LessThanOrEqual { field, value } => {
                 Ok(RuleExpression::LessThanOrEqual {
                     field: field.clone(),
                     value: self.substitute_value(value, parameters)?,
                 })
             }
+            RuleExpression::And(exprs) => Ok(RuleExpression::And(substitute_all(exprs)?)),
+            RuleExpression::Or(exprs) => Ok(RuleExpression::Or(substitute_all(exprs)?)),
+            RuleExpression::Not(expr) => {
+                Ok(RuleExpression::Not(Box::new(self.substitute_expression(expr, parameters)?)))
+            }
             RuleExpression::In { field, values } => {
                 Ok(RuleExpression::In {
                     field: field.clone(),
-                    values: values.iter()
-                        .map(|v| self.substitute_value(v, parameters))
-                        .collect::<Result<Vec<_>, _>>()?,
+                    values: substitute_values(values)?,
+                })
+            }
+            RuleExpression::NotIn { field, values } => {
+                Ok(RuleExpression::NotIn {
+                    field: field.clone(),
+                    values: substitute_values(values)?,
                 })
             }
-            _ => Ok(expr.clone()),
+            RuleExpression::Contains { field, value } => {
+                Ok(RuleExpression::Contains {
+                    field: field.clone(),
+                    value: self.substitute_value(value, parameters)?,
+                })
+            }
+            RuleExpression::Matches { field, pattern } => {
+                Ok(RuleExpression::Matches {
+                    field: field.clone(),
+                    pattern: self.substitute_text(pattern, parameters)?,
+                })
+            }
+            RuleExpression::MatchesRegex { field, pattern } => {
+                Ok(RuleExpression::MatchesRegex {
+                    field: field.clone(),
+                    pattern: self.substitute_text(pattern, parameters)?,
+                })
+            }
+            RuleExpression::StartsWith { field, prefix } => {
+                Ok(RuleExpression::StartsWith {
+                    field: field.clone(),
+                    prefix: self.substitute_text(prefix, parameters)?,
+                })
+            }
+            RuleExpression::EndsWith { field, suffix } => {
+                Ok(RuleExpression::EndsWith {
+                    field: field.clone(),
+                    suffix: self.substitute_text(suffix, parameters)?,
+                })
+            }
+            RuleExpression::Exists { .. }
+            | RuleExpression::NotExists { .. }
+            | RuleExpression::IpInNetwork { .. }
+            | RuleExpression::WithinGeoRegion { .. }
+            | RuleExpression::WithinTimeConstraint { .. } => Ok(expr.clone()),
+            RuleExpression::Compute { left, op, right, compare, value } => {
+                let substitute_operand = |operand: &Operand| match operand {
+                    Operand::Literal(v) => Ok(Operand::Literal(self.substitute_value(v, parameters)?)),
+                    Operand::Field(_) => Ok(operand.clone()),
+                };
+                Ok(RuleExpression::Compute {
+                    left: substitute_operand(left)?,
+                    op: *op,
+                    right: substitute_operand(right)?,
+                    compare: *compare,
+                    value: self.substitute_value(value, parameters)?,
+                })
+            }
+            RuleExpression::Custom { predicate, args } => {
+                let args = args.iter()
+                    .map(|(k, v)| Ok((k.clone(), self.substitute_value(v, parameters)?)))
+                    .collect::<Result<HashMap<_, _>, TemplateError>>()?;
+                Ok(RuleExpression::Custom {
+                    predicate: predicate.clone(),
+                    args,
+                })
+            }
+        }
+    }
+
+    /// Substitute a string-typed operand (pattern, prefix, suffix) with a parameter
+    fn substitute_text(
+        &self,
+        text: &str,
+        parameters: &HashMap<String, Value>,
+    ) -> Result<String, TemplateError> {
+        match self.substitute_value(&Value::String(text.to_string()), parameters)? {
+            Value::String(s) => Ok(s),
+            other => Err(TemplateError::InvalidParameterValue(
+                text.to_string(),
+                format!("Expected a string, got {:?}", other),
+            )),
         }
     }
 
@@ -384,4 +588,206 @@ impl Default for PolicyTemplateEngine {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn engine_with_param(name: &str, parameter_type: ParameterType, validation: RuleExpression) -> PolicyTemplateEngine {
+        let mut template = PolicyTemplate::new("Validated", "Template with a validated parameter");
+        template.add_parameter(TemplateParameter {
+            name: name.to_string(),
+            description: "Validated parameter".to_string(),
+            parameter_type,
+            default_value: None,
+            required: true,
+            min_length: None,
+            max_length: None,
+            allowed_values: None,
+            validation: Some(validation),
+        });
+
+        let mut engine = PolicyTemplateEngine::new();
+        engine.register_template(template);
+        engine
+    }
+
+    fn instantiate(engine: &PolicyTemplateEngine, name: &str, value: Value) -> Result<Policy, TemplateError> {
+        let mut parameters = HashMap::new();
+        parameters.insert(name.to_string(), value);
+        engine.instantiate("Validated", parameters, "Policy".to_string(), "Description".to_string())
+    }
+
+    fn role_engine(max_length: Option<usize>, allowed_values: Option<Vec<Value>>) -> PolicyTemplateEngine {
+        let mut template = PolicyTemplate::new("Validated", "Template with a constrained role");
+        template.add_parameter(TemplateParameter {
+            name: "required_role".to_string(),
+            description: "Role required for access".to_string(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            required: true,
+            min_length: None,
+            max_length,
+            allowed_values,
+            validation: None,
+        });
+
+        let mut engine = PolicyTemplateEngine::new();
+        engine.register_template(template);
+        engine
+    }
+
+    #[test]
+    fn test_empty_required_role_is_rejected() {
+        let engine = role_engine(None, None);
+        for value in ["", "   "] {
+            let result = instantiate(&engine, "required_role", Value::String(value.to_string()));
+            assert!(matches!(
+                result,
+                Err(TemplateError::ValidationFailed(msg)) if msg.contains("must not be empty")
+            ));
+        }
+    }
+
+    #[test]
+    fn test_over_length_role_is_rejected() {
+        let engine = role_engine(Some(8), None);
+        assert!(instantiate(&engine, "required_role", Value::String("operator".to_string())).is_ok());
+        assert!(matches!(
+            instantiate(&engine, "required_role", Value::String("administrator".to_string())),
+            Err(TemplateError::ValidationFailed(msg)) if msg.contains("at most 8 characters, got 13")
+        ));
+    }
+
+    #[test]
+    fn test_role_outside_allowed_values_is_rejected() {
+        let engine = role_engine(
+            None,
+            Some(vec![Value::String("admin".to_string()), Value::String("auditor".to_string())]),
+        );
+        assert!(instantiate(&engine, "required_role", Value::String("auditor".to_string())).is_ok());
+        assert!(matches!(
+            instantiate(&engine, "required_role", Value::String("guest".to_string())),
+            Err(TemplateError::ValidationFailed(msg)) if msg.contains("must be one of")
+        ));
+    }
+
+    #[test]
+    fn test_substitutes_parameters_nested_in_and_or() {
+        let engine = PolicyTemplateEngine::new();
+        let mut parameters = HashMap::new();
+        parameters.insert("min_level".to_string(), Value::Integer(3));
+        parameters.insert("blocked_region".to_string(), Value::String("embargoed".to_string()));
+
+        let expr = RuleExpression::And(vec![
+            RuleExpression::Exists { field: "user".to_string() },
+            RuleExpression::Or(vec![
+                RuleExpression::GreaterThan {
+                    field: "level".to_string(),
+                    value: Value::String("${min_level}".to_string()),
+                },
+                RuleExpression::Not(Box::new(RuleExpression::NotEqual {
+                    field: "region".to_string(),
+                    value: Value::String("${blocked_region}".to_string()),
+                })),
+            ]),
+        ]);
+
+        let substituted = engine.substitute_expression(&expr, &parameters).unwrap();
+        assert_eq!(
+            substituted,
+            RuleExpression::And(vec![
+                RuleExpression::Exists { field: "user".to_string() },
+                RuleExpression::Or(vec![
+                    RuleExpression::GreaterThan {
+                        field: "level".to_string(),
+                        value: Value::Integer(3),
+                    },
+                    RuleExpression::Not(Box::new(RuleExpression::NotEqual {
+                        field: "region".to_string(),
+                        value: Value::String("embargoed".to_string()),
+                    })),
+                ]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_nested_missing_parameter_is_reported() {
+        let engine = PolicyTemplateEngine::new();
+        let expr = RuleExpression::Or(vec![RuleExpression::And(vec![RuleExpression::StartsWith {
+            field: "path".to_string(),
+            prefix: "${root}".to_string(),
+        }])]);
+
+        assert!(matches!(
+            engine.substitute_expression(&expr, &HashMap::new()),
+            Err(TemplateError::MissingParameter(name)) if name == "root"
+        ));
+    }
+
+    #[test]
+    fn test_instantiation_event_carries_resolved_parameters() {
+        let engine = PolicyTemplateEngine::new();
+        let mut parameters = HashMap::new();
+        parameters.insert("min_key_size".to_string(), Value::Integer(4096));
+
+        let (policy, event) = engine
+            .instantiate_with_event(
+                "PKI Certificate Policy",
+                parameters,
+                "Certificates".to_string(),
+                "Certificate policy".to_string(),
+            )
+            .unwrap();
+
+        let PolicyEvent::PolicyInstantiatedFromTemplate(event) = event else {
+            panic!("expected PolicyInstantiatedFromTemplate");
+        };
+        let template = engine.get_template("PKI Certificate Policy").unwrap();
+        assert_eq!(event.policy_id, policy.id);
+        assert_eq!(event.template_id, template.id);
+        assert_eq!(event.template_name, "PKI Certificate Policy");
+        assert_eq!(event.subject(), "policies.policy.instantiated_from_template.v1");
+        assert_eq!(event.parameters.get("min_key_size"), Some(&Value::Integer(4096)));
+        assert_eq!(event.parameters.get("max_validity_days"), Some(&Value::Integer(365)));
+        assert_eq!(event.parameters.len(), 3);
+    }
+
+    #[test]
+    fn test_parameter_validated_by_in_set() {
+        let engine = engine_with_param(
+            "environment",
+            ParameterType::String,
+            RuleExpression::In {
+                field: "value".to_string(),
+                values: vec![Value::String("dev".to_string()), Value::String("prod".to_string())],
+            },
+        );
+
+        assert!(instantiate(&engine, "environment", Value::String("prod".to_string())).is_ok());
+        assert!(matches!(
+            instantiate(&engine, "environment", Value::String("staging".to_string())),
+            Err(TemplateError::ValidationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_parameter_validated_by_regex() {
+        let engine = engine_with_param(
+            "team",
+            ParameterType::String,
+            RuleExpression::MatchesRegex {
+                field: "value".to_string(),
+                pattern: "^[a-z][a-z0-9-]*$".to_string(),
+            },
+        );
+
+        assert!(instantiate(&engine, "team", Value::String("platform-ops".to_string())).is_ok());
+        assert!(matches!(
+            instantiate(&engine, "team", Value::String("Platform Ops".to_string())),
+            Err(TemplateError::ValidationFailed(_))
+        ));
+    }
+}
@@ -4,7 +4,7 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use uuid::Uuid;
 
 /// Unique identifier for a policy
@@ -68,6 +68,36 @@ pub enum PolicyStatus {
     Archived,
 }
 
+impl PolicyStatus {
+    /// Whether a policy in this status may move to `next`
+    ///
+    /// This is the lifecycle's single transition table:
+    ///
+    /// ```text
+    /// Draft -> UnderReview -> Approved -> Active <-> Suspended
+    ///   |        |              ^          |           |
+    ///   |        +-> Draft      |          +-> Revoked <-+ -> Archived
+    ///   +-----------------------+ (approved without a review step)
+    /// ```
+    pub fn can_transition_to(self, next: PolicyStatus) -> bool {
+        use PolicyStatus::*;
+
+        matches!(
+            (self, next),
+            (Draft, UnderReview)
+                | (Draft, Approved)
+                | (UnderReview, Approved)
+                | (UnderReview, Draft)
+                | (Approved, Active)
+                | (Active, Suspended)
+                | (Suspended, Active)
+                | (Active, Revoked)
+                | (Suspended, Revoked)
+                | (Revoked, Archived)
+        )
+    }
+}
+
 /// What/who a policy applies to
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum PolicyTarget {
@@ -87,6 +117,46 @@ pub enum PolicyTarget {
     Composite(Vec<PolicyTarget>),
 }
 
+impl PolicyTarget {
+    /// Check whether an evaluation context falls within this target
+    ///
+    /// The context describes its subject through string fields:
+    /// `organization` and `organization_unit` (UUIDs), `role` (a string or a
+    /// list of strings), `resource` and `operation`. Resource and operation
+    /// names match the variant name case-insensitively, or the custom name
+    /// exactly. A composite target matches if any of its targets does.
+    pub fn matches(&self, context: &EvaluationContext) -> bool {
+        let field = |key: &str| match context.get_field(key) {
+            Some(Value::String(s)) => Some(s.as_str()),
+            _ => None,
+        };
+
+        match self {
+            PolicyTarget::Global => true,
+            PolicyTarget::Organization(id) => {
+                field("organization").and_then(|s| Uuid::parse_str(s).ok()) == Some(*id)
+            }
+            PolicyTarget::OrganizationUnit(id) => {
+                field("organization_unit").and_then(|s| Uuid::parse_str(s).ok()) == Some(*id)
+            }
+            PolicyTarget::Role(role) => match context.get_field("role") {
+                Some(Value::String(s)) => s == role,
+                Some(Value::List(roles)) => roles
+                    .iter()
+                    .any(|r| matches!(r, Value::String(s) if s == role)),
+                _ => false,
+            },
+            PolicyTarget::Resource(resource) => {
+                field("resource").is_some_and(|name| ResourceType::from_name(name) == *resource)
+            }
+            PolicyTarget::Operation(operation) => {
+                field("operation").is_some_and(|name| OperationType::from_name(name) == *operation)
+            }
+            PolicyTarget::Composite(targets) => targets.iter().any(|t| t.matches(context)),
+        }
+    }
+}
+
 /// Types of resources policies can apply to
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ResourceType {
@@ -99,6 +169,42 @@ pub enum ResourceType {
     Custom(String),
 }
 
+impl ResourceType {
+    /// Every resource type other than `Custom`
+    const KNOWN: [ResourceType; 6] = [
+        ResourceType::Certificate,
+        ResourceType::Key,
+        ResourceType::Secret,
+        ResourceType::Document,
+        ResourceType::Service,
+        ResourceType::Network,
+    ];
+
+    /// Name of the resource type as it appears in evaluation contexts
+    pub fn name(&self) -> &str {
+        match self {
+            ResourceType::Certificate => "Certificate",
+            ResourceType::Key => "Key",
+            ResourceType::Secret => "Secret",
+            ResourceType::Document => "Document",
+            ResourceType::Service => "Service",
+            ResourceType::Network => "Network",
+            ResourceType::Custom(name) => name,
+        }
+    }
+
+    /// Resource type for a name, matching known names case-insensitively
+    ///
+    /// Any other name is a `Custom` resource type.
+    pub fn from_name(name: &str) -> Self {
+        Self::KNOWN
+            .iter()
+            .find(|known| known.name().eq_ignore_ascii_case(name))
+            .cloned()
+            .unwrap_or_else(|| ResourceType::Custom(name.to_string()))
+    }
+}
+
 /// Types of operations policies can govern
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum OperationType {
@@ -125,6 +231,58 @@ pub enum OperationType {
     Custom(String),
 }
 
+impl OperationType {
+    /// Every operation type other than `Custom`
+    const KNOWN: [OperationType; 14] = [
+        OperationType::CertificateIssuance,
+        OperationType::CertificateRenewal,
+        OperationType::CertificateRevocation,
+        OperationType::KeyGeneration,
+        OperationType::KeyRotation,
+        OperationType::KeyExport,
+        OperationType::Read,
+        OperationType::Write,
+        OperationType::Delete,
+        OperationType::Execute,
+        OperationType::CreatePolicy,
+        OperationType::ModifyPolicy,
+        OperationType::DeletePolicy,
+        OperationType::GrantExemption,
+    ];
+
+    /// Name of the operation as it appears in evaluation contexts
+    pub fn name(&self) -> &str {
+        match self {
+            OperationType::CertificateIssuance => "CertificateIssuance",
+            OperationType::CertificateRenewal => "CertificateRenewal",
+            OperationType::CertificateRevocation => "CertificateRevocation",
+            OperationType::KeyGeneration => "KeyGeneration",
+            OperationType::KeyRotation => "KeyRotation",
+            OperationType::KeyExport => "KeyExport",
+            OperationType::Read => "Read",
+            OperationType::Write => "Write",
+            OperationType::Delete => "Delete",
+            OperationType::Execute => "Execute",
+            OperationType::CreatePolicy => "CreatePolicy",
+            OperationType::ModifyPolicy => "ModifyPolicy",
+            OperationType::DeletePolicy => "DeletePolicy",
+            OperationType::GrantExemption => "GrantExemption",
+            OperationType::Custom(name) => name,
+        }
+    }
+
+    /// Operation type for a name, matching known names case-insensitively
+    ///
+    /// Any other name is a `Custom` operation.
+    pub fn from_name(name: &str) -> Self {
+        Self::KNOWN
+            .iter()
+            .find(|known| known.name().eq_ignore_ascii_case(name))
+            .cloned()
+            .unwrap_or_else(|| OperationType::Custom(name.to_string()))
+    }
+}
+
 /// How strictly a policy should be enforced
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum EnforcementLevel {
@@ -186,26 +344,32 @@ pub enum RuleExpression {
     // Comparison operations
     Equal {
         field: String,
+        #[serde(with = "wire::value")]
         value: Value
     },
     NotEqual {
         field: String,
+        #[serde(with = "wire::value")]
         value: Value
     },
     GreaterThan {
         field: String,
+        #[serde(with = "wire::value")]
         value: Value
     },
     GreaterThanOrEqual {
         field: String,
+        #[serde(with = "wire::value")]
         value: Value
     },
     LessThan {
         field: String,
+        #[serde(with = "wire::value")]
         value: Value
     },
     LessThanOrEqual {
         field: String,
+        #[serde(with = "wire::value")]
         value: Value
     },
 
@@ -217,22 +381,31 @@ pub enum RuleExpression {
     // Set operations
     In {
         field: String,
+        #[serde(with = "wire::list")]
         values: Vec<Value>
     },
     NotIn {
         field: String,
+        #[serde(with = "wire::list")]
         values: Vec<Value>
     },
     Contains {
         field: String,
+        #[serde(with = "wire::value")]
         value: Value
     },
 
     // String operations
+    /// The field contains `pattern` as a substring
     Matches {
         field: String,
         pattern: String
     },
+    /// The field matches `pattern` as a regular expression
+    MatchesRegex {
+        field: String,
+        pattern: String
+    },
     StartsWith {
         field: String,
         prefix: String
@@ -250,13 +423,431 @@ pub enum RuleExpression {
         field: String
     },
 
+    // Network operations
+    IpInNetwork {
+        field: String,
+        networks: Vec<IpNetwork>
+    },
+
+    // Location operations; the field holds `{lat, lon}` or `[lat, lon]`
+    WithinGeoRegion {
+        field: String,
+        regions: Vec<GeoRegion>
+    },
+
+    // Temporal operations, evaluated at the context timestamp
+    WithinTimeConstraint {
+        constraint: TimeConstraint
+    },
+
+    // Arithmetic on derived values, e.g. `amount * 1.1 > limit`
+    Compute {
+        left: Operand,
+        op: ArithOp,
+        right: Operand,
+        compare: CompareOp,
+        #[serde(with = "wire::value")]
+        value: Value
+    },
+
     // Custom predicates for complex logic
     Custom {
         predicate: String,
+        #[serde(with = "wire::map")]
         args: HashMap<String, Value>
     },
 }
 
+impl RuleExpression {
+    /// Rough relative cost of evaluating this expression
+    ///
+    /// Existence checks and comparisons are cheap, regexes and custom
+    /// predicates expensive; compound expressions cost the sum of their parts.
+    pub fn estimated_cost(&self) -> u32 {
+        match self {
+            RuleExpression::Exists { .. } | RuleExpression::NotExists { .. } => 1,
+            RuleExpression::Equal { .. }
+            | RuleExpression::NotEqual { .. }
+            | RuleExpression::GreaterThan { .. }
+            | RuleExpression::GreaterThanOrEqual { .. }
+            | RuleExpression::LessThan { .. }
+            | RuleExpression::LessThanOrEqual { .. } => 2,
+            RuleExpression::In { .. }
+            | RuleExpression::NotIn { .. }
+            | RuleExpression::Contains { .. }
+            | RuleExpression::Matches { .. }
+            | RuleExpression::StartsWith { .. }
+            | RuleExpression::EndsWith { .. } => 3,
+            RuleExpression::Compute { .. } => 4,
+            RuleExpression::IpInNetwork { .. }
+            | RuleExpression::WithinGeoRegion { .. }
+            | RuleExpression::WithinTimeConstraint { .. } => 5,
+            RuleExpression::MatchesRegex { .. } => 10,
+            RuleExpression::Custom { .. } => 20,
+            RuleExpression::Not(expr) => expr.estimated_cost(),
+            RuleExpression::And(exprs) | RuleExpression::Or(exprs) => {
+                exprs.iter().map(RuleExpression::estimated_cost).sum()
+            }
+        }
+    }
+
+    /// Reorder `And`/`Or` children so the cheapest are evaluated first
+    ///
+    /// The outcome is unchanged, including which error is reported. Only
+    /// children that can never fail are moved, and never past one that can:
+    /// a child that may fail (on a missing field, an invalid pattern or
+    /// timezone, an unregistered predicate) or that runs a custom predicate
+    /// keeps its position relative to every other child. Children of equal
+    /// cost keep their written order.
+    pub fn optimize(self) -> Self {
+        let reorder = |exprs: Vec<RuleExpression>| {
+            let mut ordered = Vec::with_capacity(exprs.len());
+            let mut movable = Vec::new();
+            for expr in exprs.into_iter().map(RuleExpression::optimize) {
+                if expr.is_infallible() {
+                    movable.push(expr);
+                } else {
+                    movable.sort_by_key(RuleExpression::estimated_cost);
+                    ordered.append(&mut movable);
+                    ordered.push(expr);
+                }
+            }
+            movable.sort_by_key(RuleExpression::estimated_cost);
+            ordered.append(&mut movable);
+            ordered
+        };
+
+        match self {
+            RuleExpression::And(exprs) => RuleExpression::And(reorder(exprs)),
+            RuleExpression::Or(exprs) => RuleExpression::Or(reorder(exprs)),
+            RuleExpression::Not(expr) => RuleExpression::Not(Box::new(expr.optimize())),
+            other => other,
+        }
+    }
+
+    /// Whether evaluating this expression can never fail
+    ///
+    /// Existence checks read no value, so only they and combinations of them
+    /// qualify.
+    fn is_infallible(&self) -> bool {
+        match self {
+            RuleExpression::Exists { .. } | RuleExpression::NotExists { .. } => true,
+            RuleExpression::And(exprs) | RuleExpression::Or(exprs) => {
+                exprs.iter().all(RuleExpression::is_infallible)
+            }
+            RuleExpression::Not(expr) => expr.is_infallible(),
+            _ => false,
+        }
+    }
+
+    /// Rewrite into negation normal form, with `Not` only directly on leaves
+    ///
+    /// `Not` is pushed through `And`/`Or` with De Morgan's laws and double
+    /// negations cancel. A negated leaf becomes its complement where one
+    /// exists (`Equal`/`NotEqual`, `In`/`NotIn`, `Exists`/`NotExists`) and
+    /// otherwise stays wrapped in `Not`; ordered comparisons are not flipped
+    /// because they are all false for incomparable values. The result
+    /// evaluates to the same outcome, including which missing field is
+    /// reported.
+    pub fn to_nnf(self) -> Self {
+        match self {
+            RuleExpression::And(exprs) => {
+                RuleExpression::And(exprs.into_iter().map(RuleExpression::to_nnf).collect())
+            }
+            RuleExpression::Or(exprs) => {
+                RuleExpression::Or(exprs.into_iter().map(RuleExpression::to_nnf).collect())
+            }
+            RuleExpression::Not(expr) => expr.negated_nnf(),
+            leaf => leaf,
+        }
+    }
+
+    /// Negation normal form of `Not(self)`
+    fn negated_nnf(self) -> Self {
+        match self {
+            RuleExpression::And(exprs) => {
+                RuleExpression::Or(exprs.into_iter().map(RuleExpression::negated_nnf).collect())
+            }
+            RuleExpression::Or(exprs) => {
+                RuleExpression::And(exprs.into_iter().map(RuleExpression::negated_nnf).collect())
+            }
+            RuleExpression::Not(expr) => expr.to_nnf(),
+            RuleExpression::Equal { field, value } => RuleExpression::NotEqual { field, value },
+            RuleExpression::NotEqual { field, value } => RuleExpression::Equal { field, value },
+            RuleExpression::In { field, values } => RuleExpression::NotIn { field, values },
+            RuleExpression::NotIn { field, values } => RuleExpression::In { field, values },
+            RuleExpression::Exists { field } => RuleExpression::NotExists { field },
+            RuleExpression::NotExists { field } => RuleExpression::Exists { field },
+            leaf => RuleExpression::Not(Box::new(leaf)),
+        }
+    }
+}
+
+/// Grant allowing one subject to act on behalf of another within a scope
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DelegationGrant {
+    pub id: Uuid,
+    pub delegator: String,
+    pub delegate: String,
+    pub scope: PolicyTarget,
+    pub granted_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl DelegationGrant {
+    /// Create a grant starting now
+    pub fn new(
+        delegator: impl Into<String>,
+        delegate: impl Into<String>,
+        scope: PolicyTarget,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            delegator: delegator.into(),
+            delegate: delegate.into(),
+            scope,
+            granted_at: Utc::now(),
+            expires_at,
+        }
+    }
+
+    /// Check whether the grant is in force at the given time
+    pub fn is_valid_at(&self, now: DateTime<Utc>) -> bool {
+        self.granted_at <= now && now < self.expires_at
+    }
+}
+
+/// IPv4 or IPv6 network in CIDR notation, e.g. `10.0.0.0/8`
+///
+/// Deserialization goes through [`IpNetwork::new`], so a stored network
+/// always has a valid prefix length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "IpNetworkFields")]
+pub struct IpNetwork {
+    address: std::net::IpAddr,
+    prefix_len: u8,
+}
+
+/// Unvalidated serialized form of `IpNetwork`
+#[derive(Deserialize)]
+struct IpNetworkFields {
+    address: std::net::IpAddr,
+    prefix_len: u8,
+}
+
+impl TryFrom<IpNetworkFields> for IpNetwork {
+    type Error = crate::PolicyError;
+
+    fn try_from(fields: IpNetworkFields) -> Result<Self, Self::Error> {
+        Self::new(fields.address, fields.prefix_len)
+    }
+}
+
+impl IpNetwork {
+    /// Create a network, validating the prefix length for the address family
+    pub fn new(address: std::net::IpAddr, prefix_len: u8) -> Result<Self, crate::PolicyError> {
+        let max = if address.is_ipv4() { 32 } else { 128 };
+        if prefix_len > max {
+            return Err(crate::PolicyError::ValidationError(format!(
+                "Prefix length {} exceeds {} for {}",
+                prefix_len, max, address
+            )));
+        }
+        Ok(Self { address, prefix_len })
+    }
+
+    /// The network address as given
+    pub fn address(&self) -> std::net::IpAddr {
+        self.address
+    }
+
+    /// Number of leading bits that identify the network
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Check whether an address lies within this network
+    ///
+    /// Addresses of the other family are never contained.
+    pub fn contains(&self, ip: &std::net::IpAddr) -> bool {
+        use std::net::IpAddr;
+
+        match (self.address, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix_len as u32).unwrap_or(0);
+                u32::from(net) & mask == u32::from(*ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix_len as u32).unwrap_or(0);
+                u128::from(net) & mask == u128::from(*ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl std::str::FromStr for IpNetwork {
+    type Err = crate::PolicyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || crate::PolicyError::ValidationError(format!("Invalid CIDR network: {}", s));
+        let (address, prefix_len) = match s.split_once('/') {
+            Some((address, prefix_len)) => (
+                address.parse::<std::net::IpAddr>().map_err(|_| invalid())?,
+                prefix_len.parse::<u8>().map_err(|_| invalid())?,
+            ),
+            None => {
+                let address = s.parse::<std::net::IpAddr>().map_err(|_| invalid())?;
+                (address, if address.is_ipv4() { 32 } else { 128 })
+            }
+        };
+        Self::new(address, prefix_len)
+    }
+}
+
+impl std::fmt::Display for IpNetwork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.address, self.prefix_len)
+    }
+}
+
+/// Circular geographic region
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeoRegion {
+    pub name: String,
+    pub center_lat: f64,
+    pub center_lon: f64,
+    pub radius_km: f64,
+}
+
+impl GeoRegion {
+    /// Mean Earth radius used for great-circle distances
+    const EARTH_RADIUS_KM: f64 = 6371.0;
+
+    /// Great-circle distance from the center in kilometres (haversine)
+    pub fn distance_km(&self, lat: f64, lon: f64) -> f64 {
+        let (lat1, lat2) = (self.center_lat.to_radians(), lat.to_radians());
+        let d_lat = lat2 - lat1;
+        let d_lon = (lon - self.center_lon).to_radians();
+
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+        2.0 * Self::EARTH_RADIUS_KM * a.sqrt().min(1.0).asin()
+    }
+
+    /// Check whether a coordinate lies within the region
+    pub fn contains(&self, lat: f64, lon: f64) -> bool {
+        self.distance_km(lat, lon) <= self.radius_km
+    }
+}
+
+/// Time window in which an action is allowed
+///
+/// Days, hours and blackout dates are interpreted in `timezone`, which is
+/// `UTC`, a fixed offset such as `+02:00`, or an IANA zone such as
+/// `Europe/Berlin` (following its daylight saving changes). Empty
+/// `allowed_days` and a missing `allowed_hours` place no restriction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeConstraint {
+    pub timezone: String,
+    pub allowed_days: Vec<chrono::Weekday>,
+    /// Local hours as `[start, end)`; a start after the end wraps past midnight
+    pub allowed_hours: Option<(u32, u32)>,
+    pub blackout_periods: Vec<BlackoutPeriod>,
+}
+
+/// Inclusive range of local dates on which nothing is allowed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlackoutPeriod {
+    pub start: chrono::NaiveDate,
+    pub end: chrono::NaiveDate,
+    pub reason: Option<String>,
+}
+
+impl TimeConstraint {
+    /// Check whether the constraint allows the given instant
+    pub fn allows(&self, at: DateTime<Utc>) -> Result<bool, crate::PolicyError> {
+        use chrono::{Datelike, Timelike};
+
+        let local = self.local_time(at)?;
+        let date = local.date();
+
+        if self.blackout_periods.iter().any(|p| p.start <= date && date <= p.end) {
+            return Ok(false);
+        }
+        if !self.allowed_days.is_empty() && !self.allowed_days.contains(&local.weekday()) {
+            return Ok(false);
+        }
+        Ok(match self.allowed_hours {
+            Some((start, end)) if start <= end => (start..end).contains(&local.hour()),
+            Some((start, end)) => local.hour() >= start || local.hour() < end,
+            None => true,
+        })
+    }
+
+    /// Wall-clock time in `timezone` at the given instant
+    fn local_time(&self, at: DateTime<Utc>) -> Result<chrono::NaiveDateTime, crate::PolicyError> {
+        match self.timezone.as_str() {
+            "UTC" | "Z" => Ok(at.naive_utc()),
+            tz => {
+                if let Ok(offset) = tz.parse::<chrono::FixedOffset>() {
+                    Ok(at.with_timezone(&offset).naive_local())
+                } else if let Ok(zone) = tz.parse::<chrono_tz::Tz>() {
+                    Ok(at.with_timezone(&zone).naive_local())
+                } else {
+                    Err(crate::PolicyError::ValidationError(format!("Invalid timezone: {}", tz)))
+                }
+            }
+        }
+    }
+}
+
+/// Operand of an arithmetic rule expression
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Operand {
+    /// Value of a context field (dotted paths allowed)
+    Field(String),
+    /// Literal value
+    Literal(#[serde(with = "wire::value")] Value),
+}
+
+/// Arithmetic operator on Integer/Float operands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArithOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+/// Comparison applied to the result of an arithmetic expression
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompareOp {
+    Equal,
+    NotEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    LessThan,
+    LessThanOrEqual,
+}
+
+impl CompareOp {
+    /// Whether a comparison with the given outcome satisfies this operator
+    ///
+    /// `None` (incomparable values) only satisfies `NotEqual`.
+    pub fn holds(self, ordering: Option<std::cmp::Ordering>) -> bool {
+        use std::cmp::Ordering::*;
+        match self {
+            CompareOp::Equal => ordering == Some(Equal),
+            CompareOp::NotEqual => ordering != Some(Equal),
+            CompareOp::GreaterThan => ordering == Some(Greater),
+            CompareOp::GreaterThanOrEqual => matches!(ordering, Some(Greater) | Some(Equal)),
+            CompareOp::LessThan => ordering == Some(Less),
+            CompareOp::LessThanOrEqual => matches!(ordering, Some(Less) | Some(Equal)),
+        }
+    }
+}
+
 /// Value types that can be used in rule expressions
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -320,6 +911,148 @@ impl std::hash::Hash for Value {
     }
 }
 
+impl Value {
+    /// Order two values of comparable types
+    ///
+    /// Integers and floats compare numerically, strings lexically and
+    /// datetimes chronologically; any other pairing is incomparable.
+    pub fn compare(&self, other: &Value) -> Option<std::cmp::Ordering> {
+        match (self, other) {
+            (Value::Integer(x), Value::Integer(y)) => Some(x.cmp(y)),
+            (Value::Float(x), Value::Float(y)) => x.partial_cmp(y),
+            (Value::Integer(x), Value::Float(y)) => (*x as f64).partial_cmp(y),
+            (Value::Float(x), Value::Integer(y)) => x.partial_cmp(&(*y as f64)),
+            (Value::String(x), Value::String(y)) => Some(x.cmp(y)),
+            (Value::DateTime(x), Value::DateTime(y)) => Some(x.cmp(y)),
+            _ => None,
+        }
+    }
+}
+
+/// Exact-variant serialization of `Value`
+///
+/// `Value` is untagged, so on reload a serialized `DateTime` comes back as a
+/// `String` and other variants can be coerced. `WireValue` is adjacently
+/// tagged (`{"type": "Float", "value": 2.0}`) and always restores the
+/// original variant. Map keys are written in sorted order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+pub enum WireValue {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    DateTime(DateTime<Utc>),
+    List(Vec<WireValue>),
+    Map(BTreeMap<String, WireValue>),
+}
+
+impl From<Value> for WireValue {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Null => WireValue::Null,
+            Value::Bool(b) => WireValue::Bool(b),
+            Value::Integer(i) => WireValue::Integer(i),
+            Value::Float(f) => WireValue::Float(f),
+            Value::String(s) => WireValue::String(s),
+            Value::DateTime(dt) => WireValue::DateTime(dt),
+            Value::List(items) => WireValue::List(items.into_iter().map(Into::into).collect()),
+            Value::Map(map) => WireValue::Map(map.into_iter().map(|(k, v)| (k, v.into())).collect()),
+        }
+    }
+}
+
+impl From<WireValue> for Value {
+    fn from(value: WireValue) -> Self {
+        match value {
+            WireValue::Null => Value::Null,
+            WireValue::Bool(b) => Value::Bool(b),
+            WireValue::Integer(i) => Value::Integer(i),
+            WireValue::Float(f) => Value::Float(f),
+            WireValue::String(s) => Value::String(s),
+            WireValue::DateTime(dt) => Value::DateTime(dt),
+            WireValue::List(items) => Value::List(items.into_iter().map(Into::into).collect()),
+            WireValue::Map(map) => Value::Map(map.into_iter().map(|(k, v)| (k, v.into())).collect()),
+        }
+    }
+}
+
+/// Serde helpers writing `Value`s in `RuleExpression` and exemption
+/// conditions as `WireValue`
+///
+/// Reading also accepts the legacy untagged form, so rules persisted before
+/// the tagged format still load. A legacy map holding exactly a `type` and
+/// `value` key is indistinguishable from the tagged form and is read as such.
+pub(crate) mod wire {
+    use super::{Value, WireValue};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum WireOrLegacy {
+        Wire(WireValue),
+        Legacy(Value),
+    }
+
+    impl From<WireOrLegacy> for Value {
+        fn from(value: WireOrLegacy) -> Self {
+            match value {
+                WireOrLegacy::Wire(wire) => wire.into(),
+                WireOrLegacy::Legacy(value) => value,
+            }
+        }
+    }
+
+    pub mod value {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(value: &Value, serializer: S) -> Result<S::Ok, S::Error> {
+            WireValue::from(value.clone()).serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Value, D::Error> {
+            WireOrLegacy::deserialize(deserializer).map(Into::into)
+        }
+    }
+
+    pub mod list {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(values: &[Value], serializer: S) -> Result<S::Ok, S::Error> {
+            let wire: Vec<WireValue> = values.iter().cloned().map(Into::into).collect();
+            wire.serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Value>, D::Error> {
+            let values = Vec::<WireOrLegacy>::deserialize(deserializer)?;
+            Ok(values.into_iter().map(Into::into).collect())
+        }
+    }
+
+    pub mod map {
+        use super::*;
+        use std::collections::BTreeMap;
+
+        pub fn serialize<S: Serializer>(
+            values: &HashMap<String, Value>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            let wire: BTreeMap<&String, WireValue> =
+                values.iter().map(|(k, v)| (k, v.clone().into())).collect();
+            wire.serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<HashMap<String, Value>, D::Error> {
+            let values = HashMap::<String, WireOrLegacy>::deserialize(deserializer)?;
+            Ok(values.into_iter().map(|(k, v)| (k, v.into())).collect())
+        }
+    }
+}
+
 /// Policy metadata
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PolicyMetadata {
@@ -346,6 +1079,214 @@ impl Default for PolicyMetadata {
     }
 }
 
+/// Approvals a policy needs before it is approved
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApprovalRequirements {
+    /// Minimum number of distinct approvers
+    pub min_approvals: usize,
+    /// Approvers that must all approve
+    pub required_approvers: Vec<String>,
+    /// Roles that must each be represented by an approver
+    pub approval_roles: Vec<String>,
+    /// Whether an approval from outside the organization is needed
+    pub requires_external_approval: bool,
+    /// How long an external approval request stays open
+    #[serde(default = "default_external_approval_timeout_hours")]
+    pub external_approval_timeout_hours: u32,
+    /// Minimum number of approvals from humans, however many services approve
+    #[serde(default)]
+    pub min_human_approvals: usize,
+    /// Minimum number of approvals from automated services
+    #[serde(default)]
+    pub min_service_approvals: usize,
+    /// Weight an approval from each role carries; other approvals weigh 1
+    #[serde(default)]
+    pub role_weights: HashMap<String, u32>,
+    /// Minimum total weight of distinct approvers, 0 for no weighted quorum
+    #[serde(default)]
+    pub required_weight: u32,
+    /// Approvers standing in for unavailable required approvers
+    #[serde(default)]
+    pub delegations: Vec<ApprovalDelegation>,
+}
+
+/// Lets `to` approve in place of `from` until `valid_until`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApprovalDelegation {
+    pub from: String,
+    pub to: String,
+    /// Role `from` approves in, which the delegate stands in for
+    #[serde(default)]
+    pub role: Option<String>,
+    pub valid_until: DateTime<Utc>,
+}
+
+impl ApprovalDelegation {
+    /// Whether `approval` was given by the delegate inside the window
+    pub fn covers(&self, approval: &ApprovalRecord) -> bool {
+        approval.approver == self.to && approval.approved_at <= self.valid_until
+    }
+}
+
+fn default_external_approval_timeout_hours() -> u32 {
+    72
+}
+
+impl Default for ApprovalRequirements {
+    fn default() -> Self {
+        Self {
+            min_approvals: 1,
+            required_approvers: Vec::new(),
+            approval_roles: Vec::new(),
+            requires_external_approval: false,
+            external_approval_timeout_hours: default_external_approval_timeout_hours(),
+            min_human_approvals: 0,
+            min_service_approvals: 0,
+            role_weights: HashMap::new(),
+            required_weight: 0,
+            delegations: Vec::new(),
+        }
+    }
+}
+
+impl ApprovalRequirements {
+    /// Describe the internal requirements the recorded approvals don't meet yet
+    pub fn outstanding(&self, approvals: &[ApprovalRecord]) -> Vec<String> {
+        let mut outstanding = Vec::new();
+
+        let approvers: HashSet<&str> = approvals.iter().map(|a| a.approver.as_str()).collect();
+        if approvers.len() < self.min_approvals {
+            outstanding.push(format!(
+                "{} of {} approvals recorded",
+                approvers.len(),
+                self.min_approvals
+            ));
+        }
+
+        let services = approvals.iter().filter(|a| a.is_service()).count();
+        let humans = approvals.len() - services;
+        if humans < self.min_human_approvals {
+            outstanding.push(format!(
+                "{} of {} human approvals recorded",
+                humans, self.min_human_approvals
+            ));
+        }
+        if services < self.min_service_approvals {
+            outstanding.push(format!(
+                "{} of {} service approvals recorded",
+                services, self.min_service_approvals
+            ));
+        }
+
+        let weight = self.approval_weight(approvals);
+        if weight < self.required_weight {
+            outstanding.push(format!(
+                "{} of {} approval weight recorded",
+                weight, self.required_weight
+            ));
+        }
+
+        for approver in &self.required_approvers {
+            if !approvers.contains(approver.as_str()) && !self.delegated_approval(approver, approvals) {
+                outstanding.push(format!("approval from {}", approver));
+            }
+        }
+
+        for role in &self.approval_roles {
+            if !approvals.iter().any(|a| a.role.as_deref() == Some(role.as_str()))
+                && !self.delegated_role(role, approvals)
+            {
+                outstanding.push(format!("approval from role {}", role));
+            }
+        }
+
+        outstanding
+    }
+
+    /// Whether a delegate of `approver` approved while the delegation was valid
+    fn delegated_approval(&self, approver: &str, approvals: &[ApprovalRecord]) -> bool {
+        self.delegations
+            .iter()
+            .filter(|d| d.from == approver)
+            .any(|d| approvals.iter().any(|a| d.covers(a)))
+    }
+
+    /// Whether a delegate approved for someone acting in `role` while the delegation was valid
+    fn delegated_role(&self, role: &str, approvals: &[ApprovalRecord]) -> bool {
+        self.delegations
+            .iter()
+            .filter(|d| d.role.as_deref() == Some(role))
+            .any(|d| approvals.iter().any(|a| d.covers(a)))
+    }
+
+    /// Total weight of the approvals, counting each approver once
+    pub fn approval_weight(&self, approvals: &[ApprovalRecord]) -> u32 {
+        let mut seen = HashSet::new();
+        approvals
+            .iter()
+            .filter(|a| seen.insert(a.approver.as_str()))
+            .map(|a| {
+                a.role
+                    .as_ref()
+                    .and_then(|role| self.role_weights.get(role))
+                    .copied()
+                    .unwrap_or(1)
+            })
+            .sum()
+    }
+
+    /// Whether an external approval is required but not yet recorded
+    pub fn needs_external_approval(&self, approvals: &[ApprovalRecord]) -> bool {
+        self.requires_external_approval && !approvals.iter().any(|a| a.external)
+    }
+}
+
+/// A single approval recorded against a policy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApprovalRecord {
+    pub approver: String,
+    pub role: Option<String>,
+    /// Approval given by an external party
+    pub external: bool,
+    pub approved_at: DateTime<Utc>,
+    /// Who or what approved; approvals without a kind are from humans
+    #[serde(default)]
+    pub kind: Option<ApproverKind>,
+}
+
+impl ApprovalRecord {
+    /// Whether the approval came from an automated service
+    pub fn is_service(&self) -> bool {
+        matches!(self.kind, Some(ApproverKind::Service(_)))
+    }
+}
+
+/// Kind of approver, identified by person or service id
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ApproverKind {
+    Human(Uuid),
+    Service(Uuid),
+}
+
+/// An external approval that has been requested but not yet given
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingExternalApproval {
+    pub request_id: Uuid,
+    pub requested_by: String,
+    pub requested_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Proof that an external party approved a pending request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExternalVerification {
+    /// Identity of the external approver
+    pub verifier: String,
+    /// How the approver was verified (e.g. "yubikey")
+    pub method: String,
+    pub verified_at: DateTime<Utc>,
+}
+
 /// Context for policy evaluation
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EvaluationContext {
@@ -370,9 +1311,87 @@ impl EvaluationContext {
         self
     }
 
+    /// Build a context whose fields are the members of a JSON object
+    pub fn from_json(value: serde_json::Value) -> Result<Self, crate::PolicyError> {
+        match value {
+            serde_json::Value::Object(object) => Ok(Self {
+                fields: object.into_iter().map(|(k, v)| (k, v.into())).collect(),
+                ..Self::new()
+            }),
+            other => Err(crate::PolicyError::ValidationError(format!(
+                "Evaluation context must be a JSON object, got: {}",
+                other
+            ))),
+        }
+    }
+
+    pub fn with_environment(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.environment.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn with_requester(mut self, requester: impl Into<String>) -> Self {
+        self.requester = Some(requester.into());
+        self
+    }
+
     pub fn get_field(&self, key: &str) -> Option<&Value> {
         self.fields.get(key)
     }
+
+    /// Resolve a dotted path such as `request.user.department`
+    ///
+    /// Segments descend into `Value::Map` by key and into `Value::List` by
+    /// index. A field whose name itself contains dots is matched first.
+    pub fn get_path(&self, path: &str) -> Option<&Value> {
+        if let Some(value) = self.fields.get(path) {
+            return Some(value);
+        }
+
+        let mut segments = path.split('.');
+        let root = self.fields.get(segments.next()?)?;
+        segments.try_fold(root, |value, segment| match value {
+            Value::Map(map) => map.get(segment),
+            Value::List(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        })
+    }
+
+    /// SHA-256 of the context's canonical form
+    ///
+    /// Equal contexts hash equally regardless of map insertion order.
+    pub fn content_hash(&self) -> [u8; 32] {
+        content_hash(self).expect("contexts serialize to JSON")
+    }
+
+    /// Hex-encoded `content_hash`, safe to record instead of the context itself
+    pub fn fingerprint(&self) -> String {
+        self.content_hash().iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+/// SHA-256 of a value's canonical JSON form
+///
+/// Canonical means object keys are sorted (as `serde_json::Value` does) and
+/// floats are normalized so `-0.0` and `0.0` hash the same. Returns `None`
+/// for values that fail to serialize, which have no canonical form.
+pub(crate) fn content_hash<T: Serialize>(value: &T) -> Option<[u8; 32]> {
+    use sha2::{Digest, Sha256};
+
+    fn canonicalize(value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Number(n) if n.as_f64() == Some(0.0) && n.is_f64() => {
+                *n = serde_json::Number::from_f64(0.0).expect("zero is finite");
+            }
+            serde_json::Value::Array(items) => items.iter_mut().for_each(canonicalize),
+            serde_json::Value::Object(map) => map.values_mut().for_each(canonicalize),
+            _ => {}
+        }
+    }
+
+    let mut canonical = serde_json::to_value(value).ok()?;
+    canonicalize(&mut canonical);
+    Some(Sha256::digest(canonical.to_string().as_bytes()).into())
 }
 
 // Implement Into<Value> for common types
@@ -418,6 +1437,24 @@ impl From<DateTime<Utc>> for Value {
     }
 }
 
+impl From<serde_json::Value> for Value {
+    fn from(v: serde_json::Value) -> Self {
+        match v {
+            serde_json::Value::Null => Value::Null,
+            serde_json::Value::Bool(b) => Value::Bool(b),
+            serde_json::Value::Number(n) => match n.as_i64() {
+                Some(i) => Value::Integer(i),
+                None => Value::Float(n.as_f64().unwrap_or(f64::NAN)),
+            },
+            serde_json::Value::String(s) => Value::String(s),
+            serde_json::Value::Array(items) => Value::List(items.into_iter().map(Value::from).collect()),
+            serde_json::Value::Object(object) => {
+                Value::Map(object.into_iter().map(|(k, v)| (k, v.into())).collect())
+            }
+        }
+    }
+}
+
 // ============= Claims-Based Authorization (from GitHub version) =============
 
 /// Policy effect - explicit Allow or Deny
@@ -427,6 +1464,28 @@ pub enum PolicyEffect {
     Deny,
 }
 
+/// Decision produced by claims-based evaluation, with the reason for a denial
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EvaluationDecision {
+    Allowed,
+    Denied { reason: String },
+}
+
+impl EvaluationDecision {
+    /// Create a denial with the given reason
+    pub fn denied(reason: impl Into<String>) -> Self {
+        EvaluationDecision::Denied { reason: reason.into() }
+    }
+
+    /// Get the effect of this decision
+    pub fn effect(&self) -> PolicyEffect {
+        match self {
+            EvaluationDecision::Allowed => PolicyEffect::Allow,
+            EvaluationDecision::Denied { .. } => PolicyEffect::Deny,
+        }
+    }
+}
+
 /// Resource pattern for matching resources
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ResourcePattern {
@@ -450,30 +1509,36 @@ impl ResourcePattern {
     }
 
     fn glob_matches(&self, resource: &str) -> bool {
-        // Simple glob matching: * matches any characters
-        let pattern_parts: Vec<&str> = self.pattern.split('*').collect();
-        if pattern_parts.is_empty() {
-            return false;
-        }
+        // `*` matches any sequence of characters (including none). When a literal
+        // mismatch occurs we backtrack to the most recent `*` and let it absorb
+        // one more character, so segments can never overlap.
+        let pattern: Vec<char> = self.pattern.chars().collect();
+        let text: Vec<char> = resource.chars().collect();
 
-        let mut pos = 0;
-        for (i, part) in pattern_parts.iter().enumerate() {
-            if i == 0 && !self.pattern.starts_with('*') {
-                if !resource.starts_with(part) {
-                    return false;
-                }
-                pos = part.len();
-            } else if i == pattern_parts.len() - 1 && !self.pattern.ends_with('*') {
-                if !resource.ends_with(part) {
-                    return false;
-                }
-            } else if let Some(index) = resource[pos..].find(part) {
-                pos += index + part.len();
+        let mut p = 0;
+        let mut t = 0;
+        let mut star: Option<usize> = None;
+        let mut star_text = 0;
+
+        while t < text.len() {
+            if p < pattern.len() && pattern[p] == '*' {
+                star = Some(p);
+                star_text = t;
+                p += 1;
+            } else if p < pattern.len() && pattern[p] == text[t] {
+                p += 1;
+                t += 1;
+            } else if let Some(star_pos) = star {
+                p = star_pos + 1;
+                star_text += 1;
+                t = star_text;
             } else {
                 return false;
             }
         }
-        true
+
+        // Remaining pattern may only consist of wildcards
+        pattern[p..].iter().all(|c| *c == '*')
     }
 
     fn regex_matches(&self, _resource: &str) -> bool {
@@ -511,6 +1576,9 @@ pub struct ClaimSet {
     pub claims: HashSet<Claim>,
     pub issued_at: DateTime<Utc>,
     pub expires_at: Option<DateTime<Utc>>,
+    /// Claims may not be used before this time (JWT `nbf`)
+    #[serde(default)]
+    pub not_before: Option<DateTime<Utc>>,
 }
 
 impl ClaimSet {
@@ -520,6 +1588,7 @@ impl ClaimSet {
             claims: HashSet::new(),
             issued_at: Utc::now(),
             expires_at: None,
+            not_before: None,
         }
     }
 
@@ -531,12 +1600,51 @@ impl ClaimSet {
         self.claims.contains(&Claim::new(claim_type.to_string(), claim_value.to_string()))
     }
 
-    pub fn is_valid(&self) -> bool {
-        if let Some(expires_at) = self.expires_at {
-            Utc::now() < expires_at
-        } else {
-            true
+    /// Whether the claims are valid at the clock's current time
+    pub fn is_valid(&self, clock: &dyn crate::Clock) -> bool {
+        self.is_valid_at(clock.now())
+    }
+
+    /// Whether `now` falls within `[not_before, expires_at)`
+    pub fn is_valid_at(&self, now: DateTime<Utc>) -> bool {
+        self.not_before.is_none_or(|not_before| now >= not_before)
+            && self.expires_at.is_none_or(|expires_at| now < expires_at)
+    }
+}
+
+/// Implications between claims, e.g. `role=admin` implies `role=user`
+///
+/// Implications are transitive and may contain cycles.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClaimHierarchy {
+    implications: HashMap<Claim, Vec<Claim>>,
+}
+
+impl ClaimHierarchy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that holding `parent` implies holding `implied`
+    pub fn imply(mut self, parent: Claim, implied: Claim) -> Self {
+        self.implications.entry(parent).or_default().push(implied);
+        self
+    }
+
+    /// The claim set with every implied claim added
+    pub fn expand(&self, claims: &ClaimSet) -> ClaimSet {
+        let mut expanded = claims.clone();
+        let mut pending: Vec<&Claim> = claims.claims.iter().collect();
+
+        while let Some(claim) = pending.pop() {
+            for implied in self.implications.get(claim).into_iter().flatten() {
+                if expanded.claims.insert(implied.clone()) {
+                    pending.push(implied);
+                }
+            }
         }
+
+        expanded
     }
 }
 
@@ -547,6 +1655,9 @@ pub enum PolicyCondition {
     HasAnyClaim { claim_type: String },
     HasAllClaims { claims: Vec<Claim> },
     HasAnyClaims { claims: Vec<Claim> },
+    /// Some claim of `claim_type`, parsed as the type of `value`, compares
+    /// to `value` under `op`
+    ClaimCompare { claim_type: String, op: CompareOp, value: Value },
     And(Vec<PolicyCondition>),
     Or(Vec<PolicyCondition>),
     Not(Box<PolicyCondition>),
@@ -567,6 +1678,12 @@ impl PolicyCondition {
             PolicyCondition::HasAnyClaims { claims: required } => {
                 required.iter().any(|c| claims.claims.contains(c))
             }
+            PolicyCondition::ClaimCompare { claim_type, op, value } => {
+                claims.claims.iter()
+                    .filter(|c| c.claim_type == *claim_type)
+                    .filter_map(|c| parse_claim_value(&c.claim_value, value))
+                    .any(|parsed| op.holds(parsed.compare(value)))
+            }
             PolicyCondition::And(conditions) => {
                 conditions.iter().all(|c| c.evaluate(claims))
             }
@@ -578,4 +1695,417 @@ impl PolicyCondition {
             }
         }
     }
-}
\ No newline at end of file
+
+    /// Evaluate against the claims after expanding them through `hierarchy`
+    pub fn evaluate_with_hierarchy(&self, claims: &ClaimSet, hierarchy: &ClaimHierarchy) -> bool {
+        self.evaluate(&hierarchy.expand(claims))
+    }
+}
+
+/// Parse a claim value as the type of `like`, if it can be
+fn parse_claim_value(raw: &str, like: &Value) -> Option<Value> {
+    match like {
+        Value::Integer(_) | Value::Float(_) => match raw.parse::<i64>() {
+            Ok(i) => Some(Value::Integer(i)),
+            Err(_) => raw.parse::<f64>().ok().filter(|f| f.is_finite()).map(Value::Float),
+        },
+        Value::String(_) => Some(Value::String(raw.to_string())),
+        Value::DateTime(_) => DateTime::parse_from_rfc3339(raw)
+            .map(|dt| Value::DateTime(dt.with_timezone(&Utc)))
+            .ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_status_transition_matrix() {
+        use PolicyStatus::*;
+
+        let all = [Draft, UnderReview, Approved, Active, Suspended, Revoked, Archived];
+        let legal = [
+            (Draft, UnderReview),
+            (Draft, Approved),
+            (UnderReview, Approved),
+            (UnderReview, Draft),
+            (Approved, Active),
+            (Active, Suspended),
+            (Suspended, Active),
+            (Active, Revoked),
+            (Suspended, Revoked),
+            (Revoked, Archived),
+        ];
+
+        for from in all {
+            for to in all {
+                assert_eq!(
+                    from.can_transition_to(to),
+                    legal.contains(&(from, to)),
+                    "{:?} -> {:?}",
+                    from,
+                    to
+                );
+            }
+        }
+    }
+
+    fn glob(pattern: &str) -> ResourcePattern {
+        ResourcePattern::new(pattern.to_string(), PatternType::Glob)
+    }
+
+    #[test]
+    fn test_glob_single_middle_wildcard() {
+        assert!(glob("a*c").matches("ac"));
+        assert!(glob("a*c").matches("abXc"));
+        assert!(!glob("a*c").matches("abXcd"));
+        assert!(!glob("a*c").matches("bac"));
+    }
+
+    #[test]
+    fn test_glob_multiple_wildcards() {
+        assert!(glob("a*b*c").matches("abc"));
+        assert!(glob("a*b*c").matches("aXXbYYc"));
+        assert!(!glob("a*b*c").matches("aXXcYYb"));
+        assert!(glob("a**c").matches("aXc"));
+    }
+
+    #[test]
+    fn test_glob_leading_and_trailing_wildcards() {
+        assert!(glob("*suffix").matches("some-suffix"));
+        assert!(!glob("*suffix").matches("suffix-not"));
+        assert!(glob("prefix*").matches("prefix-value"));
+        assert!(!glob("prefix*").matches("no-prefix"));
+        assert!(glob("*").matches(""));
+        assert!(glob("*").matches("anything"));
+    }
+
+    #[test]
+    fn test_glob_does_not_overlap_segments() {
+        // Naive prefix/suffix matching would accept these by reusing characters
+        assert!(!glob("a*a").matches("a"));
+        assert!(!glob("ab*ba").matches("aba"));
+        assert!(glob("ab*ba").matches("abba"));
+        assert!(!glob("x*yz*yz").matches("xyz"));
+    }
+
+    fn every_variant() -> Vec<Value> {
+        let timestamp = "2026-03-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        vec![
+            Value::Null,
+            Value::Bool(true),
+            Value::Integer(2),
+            Value::Float(2.0),
+            Value::String("2026-03-01T12:00:00Z".to_string()),
+            Value::DateTime(timestamp),
+            Value::List(vec![Value::Float(1.0), Value::DateTime(timestamp)]),
+            Value::Map(HashMap::from([
+                ("limit".to_string(), Value::Float(10.0)),
+                ("since".to_string(), Value::DateTime(timestamp)),
+            ])),
+        ]
+    }
+
+    #[test]
+    fn test_wire_value_round_trips_every_variant() {
+        for value in every_variant() {
+            let json = serde_json::to_string(&WireValue::from(value.clone())).unwrap();
+            let restored: Value = serde_json::from_str::<WireValue>(&json).unwrap().into();
+            assert_eq!(restored, value, "{}", json);
+        }
+
+        // The untagged form loses the DateTime variant
+        let timestamp = Value::DateTime(Utc::now());
+        let untagged: Value = serde_json::from_str(&serde_json::to_string(&timestamp).unwrap()).unwrap();
+        assert!(matches!(untagged, Value::String(_)));
+    }
+
+    #[test]
+    fn test_rule_expression_round_trips_without_coercion() {
+        let values = every_variant();
+        let mut expressions: Vec<RuleExpression> = values
+            .iter()
+            .map(|value| RuleExpression::Equal { field: "f".to_string(), value: value.clone() })
+            .collect();
+        expressions.push(RuleExpression::In { field: "f".to_string(), values: values.clone() });
+        expressions.push(RuleExpression::Custom {
+            predicate: "p".to_string(),
+            args: values.iter().enumerate().map(|(i, v)| (i.to_string(), v.clone())).collect(),
+        });
+        expressions.push(RuleExpression::Compute {
+            left: Operand::Literal(Value::Float(3.0)),
+            op: ArithOp::Multiply,
+            right: Operand::Field("amount".to_string()),
+            compare: CompareOp::GreaterThan,
+            value: Value::Float(100.0),
+        });
+
+        for expression in expressions {
+            let json = serde_json::to_string(&expression).unwrap();
+            let restored: RuleExpression = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored, expression, "{}", json);
+        }
+
+        // Rules written before the tagged format still load
+        let legacy = r#"{"Equal":{"field":"key_size","value":2048}}"#;
+        let restored: RuleExpression = serde_json::from_str(legacy).unwrap();
+        assert_eq!(
+            restored,
+            RuleExpression::Equal { field: "key_size".to_string(), value: Value::Integer(2048) }
+        );
+    }
+
+    #[test]
+    fn test_context_hash_ignores_insertion_order() {
+        let timestamp = Utc::now();
+        let mut first = EvaluationContext::new()
+            .with_field("key_size", 4096)
+            .with_field("subject", "svc")
+            .with_environment("region", "eu");
+        let mut second = EvaluationContext::new()
+            .with_environment("region", "eu")
+            .with_field("subject", "svc")
+            .with_field("key_size", 4096);
+        first.timestamp = timestamp;
+        second.timestamp = timestamp;
+
+        assert_eq!(first.content_hash(), second.content_hash());
+        assert_eq!(first.fingerprint().len(), 64);
+
+        second = second.with_field("key_size", 2048);
+        assert_ne!(first.content_hash(), second.content_hash());
+    }
+
+    #[test]
+    fn test_unserializable_value_has_no_content_hash() {
+        // JSON object keys must be strings
+        let by_pair: HashMap<(u8, u8), u8> = [((1, 2), 3)].into_iter().collect();
+        assert_eq!(content_hash(&by_pair), None);
+        assert!(content_hash(&"plain").is_some());
+    }
+
+    #[test]
+    fn test_context_from_nested_json() {
+        let context = EvaluationContext::from_json(serde_json::json!({
+            "request": {
+                "user": { "department": "security", "clearance": 3 },
+                "tags": ["pki", 2.5, null, true]
+            },
+            "key_size": 4096
+        }))
+        .unwrap()
+        .with_requester("alice")
+        .with_environment("region", "eu-west");
+
+        let expected_request = Value::Map(HashMap::from([
+            (
+                "user".to_string(),
+                Value::Map(HashMap::from([
+                    ("department".to_string(), Value::String("security".to_string())),
+                    ("clearance".to_string(), Value::Integer(3)),
+                ])),
+            ),
+            (
+                "tags".to_string(),
+                Value::List(vec![
+                    Value::String("pki".to_string()),
+                    Value::Float(2.5),
+                    Value::Null,
+                    Value::Bool(true),
+                ]),
+            ),
+        ]));
+
+        assert_eq!(context.get_field("request"), Some(&expected_request));
+        assert_eq!(context.get_field("key_size"), Some(&Value::Integer(4096)));
+        assert_eq!(context.requester.as_deref(), Some("alice"));
+        assert_eq!(context.environment.get("region").map(String::as_str), Some("eu-west"));
+
+        assert!(EvaluationContext::from_json(serde_json::json!([1, 2])).is_err());
+    }
+
+    #[test]
+    fn test_get_path_resolves_nested_fields() {
+        let context = EvaluationContext::from_json(serde_json::json!({
+            "request": {
+                "user": { "department": "security" },
+                "approvers": ["alice", "bob"]
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            context.get_path("request.user.department"),
+            Some(&Value::String("security".to_string()))
+        );
+        assert_eq!(
+            context.get_path("request.approvers.1"),
+            Some(&Value::String("bob".to_string()))
+        );
+        assert_eq!(context.get_path("request.approvers.2"), None);
+        assert_eq!(context.get_path("request.user.team"), None);
+        assert_eq!(context.get_path("request.user.department.name"), None);
+    }
+
+    #[test]
+    fn test_ip_network_parsing_and_prefix_bounds() {
+        let network: IpNetwork = "192.168.0.0/16".parse().unwrap();
+        assert_eq!(network.to_string(), "192.168.0.0/16");
+        assert!(network.contains(&"192.168.255.1".parse().unwrap()));
+        assert!(!network.contains(&"192.169.0.1".parse().unwrap()));
+        assert!(!network.contains(&"::ffff:192.168.0.1".parse().unwrap()));
+
+        let any: IpNetwork = "0.0.0.0/0".parse().unwrap();
+        assert!(any.contains(&"203.0.113.9".parse().unwrap()));
+
+        assert!("10.0.0.0/33".parse::<IpNetwork>().is_err());
+        assert!("10.0.0/8".parse::<IpNetwork>().is_err());
+    }
+
+    #[test]
+    fn test_ip_network_deserialization_validates_prefix() {
+        let network: IpNetwork = "10.0.0.0/8".parse().unwrap();
+        let json = serde_json::to_string(&network).unwrap();
+        assert_eq!(serde_json::from_str::<IpNetwork>(&json).unwrap(), network);
+
+        let bad = r#"{"address":"10.0.0.0","prefix_len":40}"#;
+        assert!(serde_json::from_str::<IpNetwork>(bad).is_err());
+        let bad_v6 = r#"{"address":"::1","prefix_len":129}"#;
+        assert!(serde_json::from_str::<IpNetwork>(bad_v6).is_err());
+    }
+
+    fn claim(claim_type: &str, claim_value: &str) -> Claim {
+        Claim::new(claim_type.to_string(), claim_value.to_string())
+    }
+
+    #[test]
+    fn test_claim_hierarchy_implies_claims_transitively() {
+        let hierarchy = ClaimHierarchy::new()
+            .imply(claim("role", "admin"), claim("role", "operator"))
+            .imply(claim("role", "operator"), claim("role", "user"))
+            .imply(claim("role", "user"), claim("role", "operator"));
+        let user = PolicyCondition::HasClaim {
+            claim_type: "role".to_string(),
+            claim_value: "user".to_string(),
+        };
+
+        let mut admin = ClaimSet::new("alice".to_string());
+        admin.add_claim(claim("role", "admin"));
+
+        assert!(!user.evaluate(&admin));
+        assert!(user.evaluate_with_hierarchy(&admin, &hierarchy));
+        assert_eq!(hierarchy.expand(&admin).claims.len(), 3);
+
+        let mut guest = ClaimSet::new("bob".to_string());
+        guest.add_claim(claim("role", "guest"));
+        assert!(!user.evaluate_with_hierarchy(&guest, &hierarchy));
+    }
+
+    fn clearance(level: &str) -> ClaimSet {
+        let mut claims = ClaimSet::new("alice".to_string());
+        claims.add_claim(claim("clearance", level));
+        claims
+    }
+
+    #[test]
+    fn test_claim_compare_parses_claim_values() {
+        let at_least_three = PolicyCondition::ClaimCompare {
+            claim_type: "clearance".to_string(),
+            op: CompareOp::GreaterThanOrEqual,
+            value: Value::Integer(3),
+        };
+        assert!(at_least_three.evaluate(&clearance("3")));
+        assert!(at_least_three.evaluate(&clearance("4.5")));
+        assert!(!at_least_three.evaluate(&clearance("2")));
+
+        let is_secret = PolicyCondition::ClaimCompare {
+            claim_type: "clearance".to_string(),
+            op: CompareOp::Equal,
+            value: Value::String("secret".to_string()),
+        };
+        assert!(is_secret.evaluate(&clearance("secret")));
+        assert!(!is_secret.evaluate(&clearance("public")));
+
+        assert!(!at_least_three.evaluate(&clearance("high")));
+        let not_three = PolicyCondition::ClaimCompare {
+            claim_type: "clearance".to_string(),
+            op: CompareOp::NotEqual,
+            value: Value::Integer(3),
+        };
+        assert!(!not_three.evaluate(&clearance("high")));
+        assert!(!at_least_three.evaluate(&ClaimSet::new("bob".to_string())));
+    }
+
+    #[test]
+    fn test_claim_set_validity_window() {
+        let now = Utc::now();
+        let mut claims = ClaimSet::new("alice".to_string());
+        claims.not_before = Some(now);
+        claims.expires_at = Some(now + chrono::Duration::hours(1));
+
+        assert!(!claims.is_valid_at(now - chrono::Duration::seconds(1)));
+        assert!(claims.is_valid_at(now));
+        assert!(claims.is_valid_at(now + chrono::Duration::minutes(30)));
+        assert!(!claims.is_valid_at(now + chrono::Duration::hours(1)));
+    }
+
+    #[test]
+    fn test_resource_and_operation_names_round_trip() {
+        for resource in ResourceType::KNOWN {
+            assert_eq!(ResourceType::from_name(resource.name()), resource);
+            assert_eq!(ResourceType::from_name(&resource.name().to_lowercase()), resource);
+        }
+        for operation in OperationType::KNOWN {
+            assert_eq!(OperationType::from_name(operation.name()), operation);
+            assert_eq!(OperationType::from_name(&operation.name().to_uppercase()), operation);
+        }
+        assert_eq!(ResourceType::from_name("queue"), ResourceType::Custom("queue".to_string()));
+        assert_eq!(OperationType::from_name("deploy").name(), "deploy");
+    }
+
+    #[test]
+    fn test_policy_target_matches_context() {
+        let org = Uuid::now_v7();
+        let unit = Uuid::now_v7();
+        let context = EvaluationContext::new()
+            .with_field("organization", org.to_string())
+            .with_field("organization_unit", unit.to_string())
+            .with_field("role", Value::List(vec!["auditor".into(), "sre".into()]))
+            .with_field("resource", "secret")
+            .with_field("operation", "KeyRotation");
+
+        assert!(PolicyTarget::Global.matches(&context));
+        assert!(PolicyTarget::Organization(org).matches(&context));
+        assert!(!PolicyTarget::Organization(unit).matches(&context));
+        assert!(PolicyTarget::OrganizationUnit(unit).matches(&context));
+        assert!(!PolicyTarget::OrganizationUnit(org).matches(&context));
+        assert!(PolicyTarget::Role("sre".to_string()).matches(&context));
+        assert!(!PolicyTarget::Role("admin".to_string()).matches(&context));
+        assert!(PolicyTarget::Resource(ResourceType::Secret).matches(&context));
+        assert!(!PolicyTarget::Resource(ResourceType::Key).matches(&context));
+        assert!(!PolicyTarget::Resource(ResourceType::Custom("Secret".to_string())).matches(&context));
+        assert!(PolicyTarget::Operation(OperationType::KeyRotation).matches(&context));
+        assert!(!PolicyTarget::Operation(OperationType::KeyExport).matches(&context));
+        assert!(PolicyTarget::Composite(vec![
+            PolicyTarget::Role("admin".to_string()),
+            PolicyTarget::Resource(ResourceType::Secret),
+        ])
+        .matches(&context));
+        assert!(!PolicyTarget::Composite(Vec::new()).matches(&context));
+
+        // Identifiers are compared as UUIDs, not as text
+        let upper = EvaluationContext::new()
+            .with_field("organization", org.to_string().to_uppercase())
+            .with_field("operation", "deploy");
+        assert!(PolicyTarget::Organization(org).matches(&upper));
+        assert!(PolicyTarget::Operation(OperationType::Custom("deploy".to_string())).matches(&upper));
+        assert!(!PolicyTarget::Operation(OperationType::Custom("Deploy".to_string())).matches(&upper));
+
+        let empty = EvaluationContext::new();
+        assert!(PolicyTarget::Global.matches(&empty));
+        assert!(!PolicyTarget::Role("sre".to_string()).matches(&empty));
+        assert!(!PolicyTarget::Organization(org).matches(&empty));
+    }
+}
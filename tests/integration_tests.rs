@@ -31,6 +31,7 @@ fn test_policy_complete_lifecycle() {
         name: "Test".to_string(), description: "Description".to_string(),
         policy_type: "Security".to_string(), created_by: "admin".to_string(),
         created_at: Utc::now(),
+        approval_requirements: Default::default(),
     })).unwrap();
     assert_eq!(p.status, PolicyStatus::Draft);
     
@@ -127,8 +128,9 @@ fn test_exemption_workflows() {
     ex = ex.apply_event_pure(&PolicyEvent::PolicyExemptionGranted(PolicyExemptionGranted {
         event_id: Uuid::now_v7(), identity: msg_id(), exemption_id: eid.clone(),
         policy_id: pid.clone(), granted_by: "admin".to_string(), granted_at: Utc::now(),
-        reason: "Test".to_string(), valid_until: Utc::now() + Duration::days(30),
-        risk_acceptance: None,
+        reason: "Test".to_string(), justification: "Justification".to_string(),
+        valid_until: Utc::now() + Duration::days(30), risk_acceptance: None,
+        scope: ExemptionScope::Global, conditions: Vec::new(), max_uses: None,
     })).unwrap();
     assert_eq!(ex.status, ExemptionStatus::Active);
     
@@ -153,8 +155,9 @@ fn test_exemption_expiration() {
     ex = ex.apply_event_pure(&PolicyEvent::PolicyExemptionGranted(PolicyExemptionGranted {
         event_id: Uuid::now_v7(), identity: msg_id(), exemption_id: eid.clone(),
         policy_id: pid.clone(), granted_by: "admin".to_string(), granted_at: Utc::now(),
-        reason: "Test".to_string(), valid_until: Utc::now() + Duration::days(7),
-        risk_acceptance: None,
+        reason: "Test".to_string(), justification: "Justification".to_string(),
+        valid_until: Utc::now() + Duration::days(7), risk_acceptance: None,
+        scope: ExemptionScope::Global, conditions: Vec::new(), max_uses: None,
     })).unwrap();
     
     // Expired
@@ -175,6 +178,7 @@ fn test_pure_functions_immutability() {
         name: "Changed".to_string(), description: "Changed Description".to_string(),
         policy_type: "Security".to_string(), created_by: "admin".to_string(),
         created_at: Utc::now(),
+        approval_requirements: Default::default(),
     })).unwrap();
     
     // Original unchanged
@@ -182,3 +186,22 @@ fn test_pure_functions_immutability() {
     // New changed
     assert_eq!(new_p.name, "Changed");
 }
+
+#[test]
+fn test_one_policy_type_across_public_api() {
+    // The root re-export and the aggregate module name the same type, and
+    // evaluation, diffing and inheritance all take it
+    let mut policy: cim_domain_policy::Policy = Policy::new("Key Size", "Minimum key size");
+    policy.status = PolicyStatus::Active;
+    policy.add_rule(cim_domain_policy::PolicyRule::min_key_size(2048));
+
+    let evaluation = cim_domain_policy::PolicyEvaluator::new()
+        .evaluate(&policy, &EvaluationContext::new().with_field("key_size", 4096))
+        .unwrap();
+    assert!(evaluation.is_compliant());
+    assert!(policy.diff(&policy.clone()).is_empty());
+
+    let resolved: Policy =
+        cim_domain_policy::services::resolve_with_inheritance(&policy, &|_| None).unwrap();
+    assert_eq!(resolved.id, policy.id);
+}
@@ -0,0 +1,150 @@
+//! Repository tests against a live NATS server with JetStream
+//!
+//! These need a running server (NATS 2.11 or later), so they are ignored by
+//! default. Run them with `cargo test -- --ignored`; `NATS_URL` and
+//! `STREAM_NAME` select the server and stream as for the policy service.
+
+use chrono::Utc;
+use cim_domain_policy::aggregate::Policy;
+use cim_domain_policy::events::*;
+use cim_domain_policy::infrastructure::policy_repository::{PolicyRepository, RepositoryError};
+use cim_domain_policy::infrastructure::NatsEventStore;
+use cim_domain_policy::sagas::create_root_command;
+use cim_domain_policy::value_objects::*;
+use std::env;
+use std::sync::Arc;
+use uuid::Uuid;
+
+async fn event_store() -> Arc<NatsEventStore> {
+    let nats_url = env::var("NATS_URL").unwrap_or_else(|_| "nats://localhost:4222".to_string());
+    let stream_name = env::var("STREAM_NAME").unwrap_or_else(|_| "POLICY_EVENTS".to_string());
+    let client = async_nats::connect(nats_url).await.unwrap();
+    let jetstream = async_nats::jetstream::new(client);
+    Arc::new(NatsEventStore::new(jetstream, stream_name).await.unwrap())
+}
+
+fn created(policy_id: PolicyId) -> PolicyEvent {
+    PolicyEvent::PolicyCreated(PolicyCreated {
+        event_id: Uuid::now_v7(),
+        identity: create_root_command(),
+        policy_id,
+        name: "Key Policy".to_string(),
+        description: "Key size requirements".to_string(),
+        policy_type: "Security".to_string(),
+        created_by: "alice".to_string(),
+        created_at: Utc::now(),
+        approval_requirements: Default::default(),
+    })
+}
+
+fn updated(policy_id: PolicyId, updated_by: &str) -> PolicyEvent {
+    PolicyEvent::PolicyUpdated(PolicyUpdated {
+        event_id: Uuid::now_v7(),
+        identity: create_root_command(),
+        policy_id,
+        version: 2,
+        changes: Vec::new(),
+        updated_by: updated_by.to_string(),
+        updated_at: Utc::now(),
+    })
+}
+
+#[tokio::test]
+#[ignore = "requires a NATS server with JetStream"]
+async fn test_second_concurrent_save_conflicts() {
+    let repository = PolicyRepository::new(event_store().await);
+    let policy_id = PolicyId::new();
+    repository.save(policy_id, vec![created(policy_id)], 0).await.unwrap();
+
+    // Two writers load the same stream version
+    let (_, first) = repository.load_versioned(policy_id).await.unwrap().unwrap();
+    let (_, second) = repository.load_versioned(policy_id).await.unwrap().unwrap();
+
+    // The first save succeeds and advances the stream
+    repository
+        .save(policy_id, vec![updated(policy_id, "alice")], first)
+        .await
+        .unwrap();
+    let (_, current) = repository.load_versioned(policy_id).await.unwrap().unwrap();
+    assert!(current > first);
+
+    // The second save was produced against a stale version and writes nothing
+    match repository
+        .save(policy_id, vec![updated(policy_id, "bob")], second)
+        .await
+    {
+        Err(RepositoryError::ConcurrencyConflict { expected, actual }) => {
+            assert_eq!(expected, second);
+            assert_eq!(actual, current);
+        }
+        other => panic!("expected concurrency conflict, got {:?}", other),
+    }
+    let (policy, version) = repository.load_versioned(policy_id).await.unwrap().unwrap();
+    assert_eq!(version, current);
+    assert_eq!(policy.version, 2);
+}
+
+#[tokio::test]
+#[ignore = "requires a NATS server with JetStream"]
+async fn test_creating_an_existing_policy_conflicts() {
+    let repository = PolicyRepository::new(event_store().await);
+    let policy_id = PolicyId::new();
+    repository.save(policy_id, vec![created(policy_id)], 0).await.unwrap();
+
+    assert!(matches!(
+        repository.save(policy_id, vec![created(policy_id)], 0).await,
+        Err(RepositoryError::ConcurrencyConflict { expected: 0, .. })
+    ));
+}
+
+#[tokio::test]
+#[ignore = "requires a NATS server with JetStream"]
+async fn test_load_resumes_from_snapshot_and_retains_latest_snapshots() {
+    let store = event_store().await;
+    let repository = PolicyRepository::new(store.clone())
+        .with_snapshot_frequency(2)
+        .with_max_snapshots(2);
+    let policy_id = PolicyId::new();
+
+    let mut version = 0;
+    for round in 0..4 {
+        let events = if round == 0 {
+            vec![created(policy_id), updated(policy_id, "alice")]
+        } else {
+            vec![updated(policy_id, "alice"), updated(policy_id, "bob")]
+        };
+        repository.save(policy_id, events, version).await.unwrap();
+
+        // Each load replays two new events and snapshots them
+        let (policy, current) = repository.load_versioned(policy_id).await.unwrap().unwrap();
+        assert_eq!(policy.version, 2);
+        version = current;
+
+        let snapshot = store
+            .load_snapshot::<Policy>(policy_id.0)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(snapshot.stream_sequence, current);
+        assert_eq!(snapshot.event_count, 2 * (round + 1));
+    }
+
+    // Only the events after the latest snapshot are read back
+    let replay = store.load_since_snapshot::<Policy>(policy_id.0).await.unwrap();
+    assert!(replay.events.is_empty());
+    assert_eq!(replay.last_sequence, version);
+    assert_eq!(replay.event_count(), 8);
+
+    // Four snapshots were taken and only the two latest were retained
+    assert_eq!(store.compact_snapshots(policy_id.0, 2).await.unwrap(), 0);
+    assert_eq!(store.compact_snapshots(policy_id.0, 1).await.unwrap(), 1);
+    assert_eq!(
+        store
+            .load_snapshot::<Policy>(policy_id.0)
+            .await
+            .unwrap()
+            .unwrap()
+            .stream_sequence,
+        version
+    );
+}